@@ -0,0 +1,42 @@
+//! Read paths of `DataBuffer` as used by the inspector, status bar and selection commands.
+
+use cosmic_hex::buffer::{byte_stats, DataBuffer, Endian};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+mod common;
+
+fn reads(c: &mut Criterion) {
+    let mut group = c.benchmark_group("buffer");
+    group.sample_size(10);
+    for (name, len) in common::sizes() {
+        let mut buffer = DataBuffer::new(common::data(len));
+        // One edit near the end, so the modified lookups have a run to find.
+        buffer.set_byte(len - 16, !buffer.get_byte(len - 16));
+        group.throughput(Throughput::Bytes(len as u64));
+
+        group.bench_with_input(BenchmarkId::new("get_uint", name), &buffer, |b, buffer| {
+            b.iter(|| {
+                (0..len - 8)
+                    .step_by(4096)
+                    .filter_map(|offset| buffer.get_uint(offset, 8, Endian::Big))
+                    .fold(0, u64::wrapping_add)
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("stats", name), &buffer, |b, buffer| {
+            b.iter(|| byte_stats(&[black_box(&buffer.data)]))
+        });
+        group.bench_with_input(BenchmarkId::new("is_modified", name), &buffer, |b, buffer| {
+            b.iter(|| buffer.is_modified(black_box(0..len)))
+        });
+        group.bench_with_input(BenchmarkId::new("next_modified", name), &buffer, |b, buffer| {
+            b.iter(|| buffer.next_modified(black_box(0)))
+        });
+        group.bench_with_input(BenchmarkId::new("text_run", name), &buffer, |b, buffer| {
+            b.iter(|| buffer.text_run(black_box(len / 2)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, reads);
+criterion_main!(benches);
@@ -0,0 +1,27 @@
+//! Synthetic data shared by the benchmarks.
+
+/// 1 MB and 100 MB always, 1 GB only with `COSMIC_HEX_BENCH_LARGE` set so CI runs stay short.
+pub fn sizes() -> Vec<(&'static str, usize)> {
+    let mut sizes = vec![("1MB", 1 << 20), ("100MB", 100 << 20)];
+    if std::env::var_os("COSMIC_HEX_BENCH_LARGE").is_some() {
+        sizes.push(("1GB", 1 << 30));
+    }
+    sizes
+}
+
+/// Reproducible bytes that mix text runs with binary noise, like a typical executable.
+pub fn data(len: usize) -> Vec<u8> {
+    let mut state = 0x2545_F491_4F6C_DD1Du64;
+    (0..len)
+        .map(|i| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            if (i / 64) % 4 == 0 {
+                b'a' + (state % 26) as u8
+            } else {
+                state as u8
+            }
+        })
+        .collect()
+}
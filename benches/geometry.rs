@@ -0,0 +1,86 @@
+//! Row geometry computed on every redraw: the selection rectangles of the visible rows and hit
+//! testing, at the start, in the middle and at the end of the file.
+
+use cosmic_hex::{layout::Layout, selection::Selection};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+// The geometry only depends on the length of the file, not on its bytes.
+#[allow(dead_code)]
+mod common;
+
+/// Rows shown in a window about 1000 pixels high.
+const VISIBLE_ROWS: usize = 60;
+
+/// 32 bytes per row in groups of 8, with both panes shown.
+fn layout() -> Layout {
+    Layout {
+        char_width: 9.0,
+        gutter_width: 100.0,
+        cell_width: 27.0,
+        text_cell_width: 9.0,
+        numbers_in_row: 32,
+        group_size: 8,
+        group_gap: 9.0,
+        pane_spacing: 18.0,
+        show_hex: true,
+        show_text: true,
+    }
+}
+
+/// First rows of the viewport scrolled to the start, the middle and the end of `len` bytes.
+fn first_rows(layout: &Layout, len: usize) -> [usize; 3] {
+    let rows = len.div_ceil(layout.numbers_in_row);
+    [0, rows / 2, rows.saturating_sub(VISIBLE_ROWS)]
+}
+
+/// The selection rectangles of the visible rows from `first_row`, as the overlay fills them.
+fn selection_spans(layout: &Layout, selection: &Selection, first_row: usize, len: usize) -> Vec<(f32, f32)> {
+    let numbers_in_row = layout.numbers_in_row;
+    let mut spans = Vec::new();
+    for row in first_row..first_row + VISIBLE_ROWS {
+        let offset = row * numbers_in_row;
+        let row_end = (offset + numbers_in_row).min(len);
+        for run in selection.runs(offset..row_end) {
+            let (column, count) = (run.start - offset, run.len());
+            spans.extend([layout.hex_span(column, count), layout.text_span(column, count)].into_iter().flatten());
+        }
+    }
+    spans
+}
+
+fn geometry(c: &mut Criterion) {
+    let mut group = c.benchmark_group("geometry");
+    group.sample_size(10);
+    let layout = layout();
+    for (name, len) in common::sizes() {
+        let selections = [
+            ("selection", Selection::new(7, len - 7)),
+            ("block_selection", Selection::block(3, len - 20, layout.numbers_in_row)),
+        ];
+        let first_rows = first_rows(&layout, len);
+        group.throughput(Throughput::Elements((first_rows.len() * VISIBLE_ROWS) as u64));
+        for (kind, selection) in &selections {
+            group.bench_with_input(BenchmarkId::new(*kind, name), selection, |b, selection| {
+                b.iter(|| {
+                    first_rows
+                        .iter()
+                        .map(|first_row| selection_spans(&layout, black_box(selection), *first_row, len).len())
+                        .sum::<usize>()
+                })
+            });
+        }
+        let width = layout.extra_columns_x() as usize;
+        group.bench_with_input(BenchmarkId::new("hit_test", name), &first_rows, |b, first_rows| {
+            b.iter(|| {
+                first_rows
+                    .iter()
+                    .flat_map(|row| (0..width).filter_map(move |x| layout.hit_test(black_box(x as f32), *row)))
+                    .count()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, geometry);
+criterion_main!(benches);
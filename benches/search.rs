@@ -0,0 +1,29 @@
+//! Wildcard pattern search as used by find and `--grep`.
+
+use cosmic_hex::pattern::Pattern;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+mod common;
+
+fn find_all(c: &mut Criterion) {
+    let mut group = c.benchmark_group("search");
+    group.sample_size(10);
+    let patterns = [
+        ("exact", Pattern::parse("7F 45 4C 46").unwrap()),
+        ("wildcard", Pattern::parse("?? 00 ?? 00 FF").unwrap()),
+        ("text", Pattern::parse("61 62 63").unwrap()),
+    ];
+    for (name, len) in common::sizes() {
+        let data = common::data(len);
+        group.throughput(Throughput::Bytes(len as u64));
+        for (kind, pattern) in &patterns {
+            group.bench_with_input(BenchmarkId::new(*kind, name), &data, |b, data| {
+                b.iter(|| pattern.find_all(black_box(data)).len())
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, find_all);
+criterion_main!(benches);
@@ -1,9 +1,25 @@
 app-title = COSMIC Hex
 
+# Numbers
+number-group-separator = ,
+number-decimal-separator = .
+size-bytes = {$value} bytes
+size-kib = {$value} KiB
+size-mib = {$value} MiB
+size-gib = {$value} GiB
+size-tib = {$value} TiB
+
 #Prompt save changes
 prompt-save-changes-title = Unsaved changes
 prompt-unsaved-changes = You have unsaved changes. Save?
+prompt-revert-title = Revert to saved file?
+prompt-revert = All unsaved changes will be lost and the edit history cleared.
 cancel = Cancel
+prompt-overwrite-title = Replace existing file?
+prompt-overwrite = {$file} already exists. Saving will replace its contents.
+overwrite = Replace
+save-failed-title = Couldn't save the file
+save-failed = {$file} couldn't be written: {$error}
 discard = Discard Changes
 save-all = Save All
 
@@ -11,27 +27,254 @@ save-all = Save All
 find-placeholder = Find…
 find-previous = Find Previous
 find-next = Find Next
+find-count = Count
+find-history = Recent searches
+replace-placeholder = Replace with…
+replace-all = Replace All
+replace-all-tabs = Replace in All Tabs
+replace-all-tabs-body = Choose the tabs to apply the replacement to. Each tab can be undone separately.
+search-hex = Hex
+search-text = Text
+search-number = Number
+find-match-count = {$count ->
+    [one] 1 match
+   *[other] {$count} matches
+}
+
+# Status bar
+status-offset = Offset: {$offset}
+status-uint = uint: {$value}
+status-selection = Selection: {$start}–{$end}
+status-length = Length: 0x{$hex} ({$dec})
+status-stats = Sum: {$sum} Min: {$min} Max: {$max}
+status-u8 = u8: {$value}
+status-u16 = u16: {$value}
+status-u64 = u64: {$value}
+status-i32 = i32: {$value}
+status-f32 = f32: {$value}
+status-binary = bin: {$value}
+status-file-size = Size: {$size}
+status-encoding = Encoding: {$encoding}
+status-insert = INS
+empty-file = Empty file – switch to insert mode to add bytes
+status-overwrite = OVR
+status-read-only = RO
+status-read-write = RW
+status-section = Section: {$name}
+status-symbol = Symbol: {$name}
+status-region = Region: {$name}
+status-record = Record: {$record} +0x{$offset}
+status-save-selection = Saving selection… {$percent}%
+status-mark = Mark: {$mark} Distance: {$hex} ({$dec})
+tooltip-offset = Offset: 0x{$hex} ({$dec})
+a11y-hex-view = Hex view of {$file}
+a11y-caret = Offset {$offset} hex, byte {$hex} hex, {$dec} decimal, {$character}
+a11y-end-of-data = Offset {$offset} hex, end of data
+a11y-space = space
+a11y-no-character = no printable character
+a11y-modified = , modified
+tooltip-relative = Relative: {$offset}
+tooltip-position = Row {$row}, column {$column}
+preview-u16 = u16: {$value}
+preview-u32 = u32: {$value}
+preview-u64 = u64: {$value}
+preview-f32 = f32: {$value}
+preview-f64 = f64: {$value}
 
 # Menu
 
 ## File
 file = File
 open-file = Open File…
+open-folder = Open Folder…
 open-recent-file = Open recent File
+toggle-pin-file = Pin/Unpin Current File
+clear-recent-files = Clear Recent Files
 close-file = Close File
+duplicate-view = Duplicate View
 save = Save
 save-as = Save as…
+save-selection-as = Save Selection as…
 quit = Quit
+revert = Revert
+verify-checksum = Verify Checksum
+verify-checksum-title = Verify Checksum
+checksum-match = The {$kind} checksum matches.
+checksum-mismatch = The {$kind} checksum does not match.
+    Expected: {$expected}
+    Actual: {$actual}
+checksum-unreadable = No checksum could be read from {$file}.
+regenerate-checksum-title = Update Checksum File?
+regenerate-checksum = The file has a checksum file that no longer matches the saved contents.
+regenerate-checksum-update = Update
+export-hex-dump = Export Hex Dump…
+export-ips = Export Changes as IPS Patch…
+ips-patch = IPS patch
+export = Export
+export-start = Start offset
+export-end = End offset (inclusive)
+export-pdf = PDF document
+export-text = Text file
+properties = Properties…
+properties-location = Location
+properties-size = Size
+properties-bytes = {$size} ({$bytes} bytes)
+properties-type = Type
+properties-permissions = Permissions
+properties-owner = Owner (uid:gid)
+properties-modified = Modified
+properties-accessed = Accessed
+properties-created = Created
+properties-unknown = Unknown
+properties-read-only = Read-only
+properties-read-write = Read/write
+properties-compute-hashes = Compute Hashes
+close = Close
+properties-title = File Properties
 menu-settings = Settings…
 
 ## Edit
 edit = Edit
 undo = Undo
 redo = Redo
+paste = Paste
+copy-as = Copy as
+copy-as-html = HTML
+select-run = Select Run
+transform-selection = Transform Selection
+transform-rot13 = ROT13
+transform-atbash = Atbash
+transform-caesar = Caesar…
+reorder-selection = Reorder Selection
+reorder-reverse = Reverse Bytes
+reorder-interleave = Interleave Halves
+reorder-deinterleave = Split Even/Odd Bytes
+transform-caesar-title = Caesar Cipher
+transform-caesar-body = Shifts the letters in the selection, a negative shift reverses a positive one.
+transform-caesar-shift = Shift
+revert-selection = Revert Selection
+resize-file = Resize File…
+resize-title = Resize File
+resize-body = Truncate the data or extend it with a fill byte.
+resize-length = New length
+resize-fill = Fill byte (hex)
+fill-selection = Fill Selection
+fill-zero = Zeros
+fill-nop = { $arch } NOP
+fill-nop-tab = NOP for This Tab ({ $arch })
+fill-nop-no-architecture = NOP for This Tab (no architecture set)
+toggle-bookmark = Toggle Bookmark
+toggle-mark = Set Mark
+next-bookmark = Next Bookmark
+previous-bookmark = Previous Bookmark
+import-bookmarks = Import Bookmarks…
+export-bookmarks = Export Bookmarks…
+bookmark-list = Bookmark list
+import-listing = Import Disassembler Listing…
+disassembler-listing = Ghidra or IDA listing
+jump-to = Jump to…
+jump-go-to = Go to {$target}
+follow-pointer = Follow Pointer
+next-modified = Next Modified Byte
+previous-modified = Previous Modified Byte
+records = Records…
+records-title = Records
+records-body = Split the data into records of a fixed size.
+record-size = Record size
+go-to-record = Go to record
+next-record = Next Record
+previous-record = Previous Record
+apply = Apply
+jump-filter = Filter
+jump-bookmark = Bookmark
+jump-edit = Edit
+jump-match = Search match
+jump-field = Protobuf field #{ $number }
 find = Find
+copy-offset = Copy Offset
+copy-relative-offset = Copy Offset Relative to Base
+set-offset-base = Set Offset Base Here
+
+## Tools
+tools = Tools
+decode-protobuf = Decode as Protobuf
+compare-with-clipboard = Compare Selection with Clipboard
+compare-identical = The selection matches the clipboard.
+compare-differences = {$count} bytes differ, the first at offset {$offset}.
+image-preview = Image Preview
+audio-preview = Audio Preview
+
+## Analyze
+analyze = Analyze
+scan-yara = Scan with YARA Rules…
+yara-rules = YARA rules
+yara-results = YARA Matches
+yara-scanning = Scanning…
+yara-error = The rules could not be used: {$error}
+yara-no-matches = No rule matched.
+yara-matches = {$count} matches
+xor-search = XOR Crib Search…
+xor-search-title = XOR Crib Search
+xor-search-body = Finds known plain text hidden under single byte or short repeating XOR keys.
+xor-crib = Plain text
+xor-max-key-len = Longest key (bytes)
+xor-searching = Searching…
+xor-hit = {$offset}  key {$key}
+xor-hit-count = {$count} candidates
+compare-ranges = Compare Ranges…
+compare-ranges-title = Compare Ranges
+compare-ranges-body = Compares two ranges of the same length byte by byte.
+compare-first = First offset
+compare-second = Second offset
+compare-length = Length
+compare = Compare
+compare-equal = The ranges are equal ({$count} bytes).
+compare-mismatch-count = {$count} of {$compared} bytes differ
+compare-mismatch = {$position}  {$first} ≠ {$second}
+search = Search
 
 ## View
 view = View
+data-inspector = Data Inspector
+watches = Watches
+add-watch = Add Watch
+symbols = Symbols
+virtual-addresses = Show Virtual Addresses
+symbols-filter = Filter symbols
+symbols-none = No ELF or PE symbols or imported regions found
+symbols-sections = Sections
+highlight-layers = Highlight Layers
+layer-annotation = Annotations
+layer-template = Template fields
+layer-diff = Differences
+layer-search = Search hits
+highlight-colors = Highlight colors
+highlight-color-default = Default
+highlight-color-low-contrast = Low contrast with the hex digits ({$ratio}:1)
+reduce-motion = Reduce motion
+reduce-motion-description = No caret blinking or smooth scrolling. Also on when the desktop turns animations off.
+high-contrast = High contrast hex view
+double-width-text = Double width text cells
+double-width-text-description = Gives every character of the text pane two columns, for fonts with wide CJK glyphs.
+group-gaps = Space between byte groups
+show-hex-pane = Show hex pane
+show-text-pane = Show text pane
+symbols-regions = Imported regions
+byte-order = Byte Order
+byte-order-default = Use Default
+tab-font = Font for This Tab
+tab-font-default = Use Default Font
+tab-options = View Options for This Tab…
+tab-options-title = View Options for This Tab
+tab-option-default = Use Default
+bytes-per-row = Bytes per row
+bytes-per-row-fit = Fit to window
+base-address = Base address
+architecture = Architecture
+architecture-none = None
+view-as-text = View as Text
+encoding = Encoding
+text-view-truncated = Showing the first {$lines} lines or {$size} of the file.
 about = About COSMIC Hex…
 
 # Settings
@@ -47,3 +290,96 @@ syntax-dark = Syntax Dark
 syntax-light = Syntax Light
 default-font = Default Font
 default-font-size = Default Font Size
+
+## Files
+files = Files
+backup = Backup
+backup-settings = Settings file
+import-settings = Import…
+export-settings = Export…
+settings-file = Settings
+recent-files-max = Recent files to remember
+scrolling = Scrolling and Navigation
+numeric-column = Decimal column
+numeric-column-none = None
+numeric-column-u8 = Unsigned bytes
+numeric-column-i8 = Signed bytes
+numeric-column-u16 = Unsigned 16-bit
+numeric-column-i16 = Signed 16-bit
+row-checksum = Row checksum
+row-checksum-none = None
+row-checksum-sum = Sum
+row-checksum-crc8 = CRC-8
+smooth-scrolling = Smooth scrolling
+scroll-lines = Rows per wheel notch
+group-size = Ctrl+Left/Right step
+default-endian = Default byte order
+little-endian = Little endian
+big-endian = Big endian
+group-size-bytes = { $bytes } bytes
+
+## Status bar
+status-bar = Status Bar
+field-offset-hex = Offset (hex)
+field-offset-dec = Offset (decimal)
+field-u8 = Unsigned 8-bit
+field-u16 = Unsigned 16-bit
+field-u32 = Unsigned 32-bit
+field-u64 = Unsigned 64-bit
+field-i32 = Signed 32-bit
+field-f32 = Float 32-bit
+field-binary = Binary
+field-selection = Selection
+field-selection-length = Selection length
+field-file-size = File size
+field-encoding = Encoding
+field-edit-mode = Edit mode
+
+# Data inspector
+inspector-no-file = No file opened
+inspector-invalid = Invalid
+inspector-timestamps = Timestamps
+inspector-unix32 = Unix time (32-bit)
+inspector-unix64 = Unix time (64-bit)
+inspector-filetime = Windows FILETIME
+inspector-dos-time = DOS date/time
+inspector-hfs-time = HFS+ time
+inspector-cocoa-time = Apple Cocoa time
+inspector-varints = Variable-length integers
+inspector-varint = Varint / ULEB128
+inspector-zigzag = Zig-zag varint
+inspector-sleb128 = SLEB128
+inspector-color = Color
+inspector-rgb = RGB
+inspector-rgba = RGBA
+inspector-bgr = BGR
+inspector-new-color = New color
+inspector-write-color = Write
+inspector-varint-value = {$value} ({$len ->
+    [one] 1 byte
+   *[other] {$len} bytes
+})
+
+# Protobuf
+protobuf = Protobuf
+protobuf-invalid = The selection is not a valid protobuf message
+protobuf-bytes = {$len} bytes
+protobuf-message = message ({$count} fields)
+copy-value = Copy Value
+copy-as-json = Copy as JSON
+copy-all-as-json = Copy All as JSON
+
+# Image preview
+image-offset = Offset
+image-width = Width
+image-format = Pixel format
+image-zoom = Zoom
+image-invalid = Enter a valid offset and width
+
+# Audio preview
+audio-sample-rate = Sample rate
+audio-bit-depth = Bit depth
+audio-channels = Channels
+audio-mono = Mono
+audio-stereo = Stereo
+audio-play = Play
@@ -0,0 +1,38 @@
+//! Desktop accessibility preferences read through the XDG settings portal.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use ashpd::desktop::settings::Settings;
+
+use crate::config::Config;
+
+/// Set once the portal reported that animations are turned off.
+static SYSTEM_REDUCE_MOTION: AtomicBool = AtomicBool::new(false);
+
+/// Minimum contrast ratio between the text and a highlight color, WCAG AA for large text.
+pub const MIN_CONTRAST: f32 = 3.0;
+
+/// Whether the desktop asks for animations to be turned off.
+pub async fn system_reduce_motion() -> bool {
+    let result = async {
+        let settings = Settings::new().await?;
+        settings.read::<bool>("org.gnome.desktop.interface", "enable-animations").await
+    }
+    .await;
+    match result {
+        Ok(animations) => !animations,
+        Err(err) => {
+            log::debug!("no reduced motion preference from the settings portal: {}", err);
+            false
+        }
+    }
+}
+
+pub fn set_system_reduce_motion(reduce: bool) {
+    SYSTEM_REDUCE_MOTION.store(reduce, Ordering::Relaxed);
+}
+
+/// Caret blinking and smooth scrolling stay off if either the desktop or the settings ask for it.
+pub fn reduce_motion(config: &Config) -> bool {
+    config.reduce_motion || SYSTEM_REDUCE_MOTION.load(Ordering::Relaxed)
+}
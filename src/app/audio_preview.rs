@@ -0,0 +1,141 @@
+use std::{ops::Range, rc::Rc};
+
+use crate::{fl, hex_view::pcm::PcmFormat};
+use cosmic::{
+    iced::{mouse, Color, Length, Point, Rectangle, Renderer},
+    widget::{self, canvas, segmented_button::Entity},
+    Element, Theme,
+};
+
+use super::{tab::Tab, Action, AppModel};
+
+/// Upper bound for the bytes taken from the cursor when nothing is selected.
+const DEFAULT_PREVIEW_LEN: usize = 1024 * 1024;
+
+/// The last decoded samples. The preview is drawn on every frame, the samples are only decoded
+/// again when the tab, the range, the format or the data changes.
+#[derive(Default)]
+pub struct AudioCache {
+    key: Option<(Entity, Range<usize>, PcmFormat, u64)>,
+    samples: Rc<Vec<f32>>,
+}
+
+struct Waveform {
+    samples: Rc<Vec<f32>>,
+    channels: usize,
+    color: Color,
+}
+
+impl canvas::Program<Action, Theme, Renderer> for Waveform {
+    type State = ();
+
+    fn draw(&self, _state: &(), renderer: &Renderer, _theme: &Theme, bounds: Rectangle, _cursor: mouse::Cursor) -> Vec<canvas::Geometry> {
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+        let frames = self.samples.len() / self.channels.max(1);
+        let columns = bounds.width as usize;
+        if frames > 0 && columns > 0 {
+            let mid = bounds.height / 2.0;
+            let per_column = frames.div_ceil(columns);
+            for (x, chunk) in self.samples.chunks(per_column * self.channels.max(1)).enumerate() {
+                // Only the first channel is drawn.
+                let (min, max) = chunk
+                    .iter()
+                    .step_by(self.channels.max(1))
+                    .fold((0.0f32, 0.0f32), |(min, max), s| (min.min(*s), max.max(*s)));
+                let x = x as f32 + 0.5;
+                frame.stroke(
+                    &canvas::Path::line(Point::new(x, mid - max * mid), Point::new(x, mid - min * mid)),
+                    canvas::Stroke::default().with_color(self.color),
+                );
+            }
+        }
+        vec![frame.into_geometry()]
+    }
+}
+
+/// Plays the samples on a background thread.
+pub fn play(samples: Vec<f32>, format: PcmFormat) {
+    std::thread::spawn(move || {
+        let (_stream, handle) = match rodio::OutputStream::try_default() {
+            Ok(output) => output,
+            Err(err) => {
+                log::error!("failed to open audio output: {}", err);
+                return;
+            }
+        };
+        let sink = match rodio::Sink::try_new(&handle) {
+            Ok(sink) => sink,
+            Err(err) => {
+                log::error!("failed to create audio sink: {}", err);
+                return;
+            }
+        };
+        sink.append(rodio::buffer::SamplesBuffer::new(format.channels, format.sample_rate, samples));
+        sink.sleep_until_end();
+    });
+}
+
+impl AppModel {
+    /// Samples of the current selection, or of the bytes following the cursor.
+    pub(crate) fn audio_samples(&self) -> Rc<Vec<f32>> {
+        let entity = self.tab_model.active();
+        let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(entity) else {
+            return Rc::default();
+        };
+        let Some(buffer) = &tab.hex_view.buffer else {
+            return Rc::default();
+        };
+        let range = match tab.hex_view.selection {
+            Some(selection) => selection.range(),
+            None => {
+                let start = (tab.hex_view.cursor.position / 2).min(buffer.len());
+                start..(start + DEFAULT_PREVIEW_LEN).min(buffer.len())
+            }
+        };
+        let key = Some((entity, range.clone(), self.pcm_format, buffer.version()));
+        let mut cache = self.audio_cache.borrow_mut();
+        if cache.key != key {
+            cache.samples = Rc::new(self.pcm_format.to_samples(buffer.get_bytes(range).unwrap_or_default()));
+            cache.key = key;
+        }
+        cache.samples.clone()
+    }
+
+    pub(crate) fn audio_preview_view(&self) -> Element<Action> {
+        let rate_selected = sample_rates.iter().position(|r| *r == self.pcm_format.sample_rate);
+        let bits_selected = bit_depths.iter().position(|b| *b == self.pcm_format.bits);
+        let channels_selected = Some(self.pcm_format.channels as usize - 1);
+
+        let controls = widget::settings::section()
+            .add(widget::settings::item::builder(fl!("audio-sample-rate")).control(widget::dropdown(
+                &sample_rate_names,
+                rate_selected,
+                Action::AudioSampleRate,
+            )))
+            .add(widget::settings::item::builder(fl!("audio-bit-depth")).control(widget::dropdown(&bit_depth_names, bits_selected, Action::AudioBitDepth)))
+            .add(widget::settings::item::builder(fl!("audio-channels")).control(widget::dropdown(&channel_names, channels_selected, Action::AudioChannels)));
+
+        let waveform = canvas::Canvas::new(Waveform {
+            samples: self.audio_samples(),
+            channels: self.pcm_format.channels as usize,
+            color: cosmic::theme::active().cosmic().accent_color().into(),
+        })
+        .width(Length::Fill)
+        .height(Length::Fixed(120.0));
+
+        widget::column::with_capacity(3)
+            .spacing(8)
+            .push(controls)
+            .push(waveform)
+            .push(widget::button::suggested(fl!("audio-play")).on_press(Action::PlayAudio))
+            .into()
+    }
+}
+
+lazy_static::lazy_static! {
+    pub(super) static ref sample_rates: Vec<u32> = vec![8000, 11025, 16000, 22050, 32000, 44100, 48000];
+    static ref sample_rate_names: Vec<String> = sample_rates.iter().map(|rate| format!("{} Hz", rate)).collect();
+    pub(super) static ref bit_depths: Vec<u16> = vec![8, 16, 24, 32];
+    static ref bit_depth_names: Vec<String> = bit_depths.iter().map(|bits| format!("{} bit", bits)).collect();
+    static ref channel_names: Vec<String> = vec![fl!("audio-mono"), fl!("audio-stereo")];
+}
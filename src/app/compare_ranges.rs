@@ -0,0 +1,106 @@
+use cosmic::{cosmic_theme, iced::Length, widget, Element};
+
+use crate::{
+    fl,
+    hex_view::{parse_offset, HexView},
+    i18n::format_number,
+};
+
+use super::{Action, AppModel};
+
+/// Mismatches listed at most.
+const MAX_MISMATCHES: usize = 500;
+
+/// Offsets and length typed in the compare ranges dialog, with the result of the last comparison.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CompareRanges {
+    pub first: String,
+    pub second: String,
+    pub length: String,
+    /// Offsets into the ranges where the bytes differ with the two bytes, `None` before the first comparison.
+    pub mismatches: Option<Vec<(usize, u8, u8)>>,
+    /// Bytes compared by the last comparison, shorter than asked for at the end of the data.
+    pub compared: usize,
+}
+
+impl CompareRanges {
+    /// Starts with the selection as the first range.
+    pub fn new(hex_view: &HexView) -> Self {
+        let (start, len) = hex_view
+            .selection
+            .map_or((hex_view.cursor.position / 2, 0), |selection| (selection.start(), selection.len()));
+        Self {
+            first: format!("0x{:X}", start),
+            length: if len > 0 { len.to_string() } else { String::new() },
+            ..Self::default()
+        }
+    }
+
+    pub(super) fn ranges(&self) -> Option<(usize, usize, usize)> {
+        Some((parse_offset(&self.first)?, parse_offset(&self.second)?, parse_offset(&self.length)?))
+    }
+
+    /// Compares the two ranges of `data`, both cut at the end of the data.
+    pub fn compare(&mut self, data: &[u8]) {
+        let Some((first, second, length)) = self.ranges() else {
+            return;
+        };
+        let a = data.get(first..).unwrap_or_default();
+        let b = data.get(second..).unwrap_or_default();
+        self.compared = length.min(a.len()).min(b.len());
+        self.mismatches = Some((0..self.compared).filter(|i| a[*i] != b[*i]).map(|i| (i, a[i], b[i])).collect());
+    }
+}
+
+impl AppModel {
+    pub(crate) fn compare_ranges_dialog<'a>(&'a self, compare: &'a CompareRanges) -> Element<'a, Action> {
+        let cosmic_theme::Spacing { space_xxs, .. } = self.core().system_theme().cosmic().spacing;
+        let mut control = widget::column::with_capacity(5)
+            .spacing(space_xxs)
+            .push(
+                widget::settings::item::builder(fl!("compare-first"))
+                    .control(widget::text_input::text_input("", &compare.first).on_input(Action::CompareFirst)),
+            )
+            .push(
+                widget::settings::item::builder(fl!("compare-second"))
+                    .control(widget::text_input::text_input("", &compare.second).on_input(Action::CompareSecond)),
+            )
+            .push(
+                widget::settings::item::builder(fl!("compare-length"))
+                    .control(widget::text_input::text_input("", &compare.length).on_input(Action::CompareLength)),
+            );
+
+        if let (Some(mismatches), Some((first, _, _))) = (&compare.mismatches, compare.ranges()) {
+            let summary = if mismatches.is_empty() {
+                fl!("compare-equal", count = format_number(compare.compared as u64))
+            } else {
+                fl!(
+                    "compare-mismatch-count",
+                    count = format_number(mismatches.len() as u64),
+                    compared = format_number(compare.compared as u64)
+                )
+            };
+            let mut list = widget::column::with_capacity(mismatches.len().min(MAX_MISMATCHES)).spacing(space_xxs);
+            for (i, a, b) in mismatches.iter().take(MAX_MISMATCHES) {
+                let text = fl!(
+                    "compare-mismatch",
+                    position = format!("+0x{:X}", i),
+                    first = format!("{:02X}", a),
+                    second = format!("{:02X}", b)
+                );
+                list = list.push(widget::button::text(text).on_press(Action::JumpTo(first + i)).width(Length::Fill));
+            }
+            control = control
+                .push(widget::text::body(summary))
+                .push(widget::scrollable(list).height(Length::Fixed(240.0)));
+        }
+
+        widget::dialog::Dialog::new()
+            .title(fl!("compare-ranges-title"))
+            .body(fl!("compare-ranges-body"))
+            .control(control)
+            .primary_action(widget::button::suggested(fl!("compare")).on_press_maybe(compare.ranges().map(|_| Action::CompareRun)))
+            .secondary_action(widget::button::text(fl!("close")).on_press(Action::DialogCancel))
+            .into()
+    }
+}
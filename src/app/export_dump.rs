@@ -0,0 +1,66 @@
+use cosmic::{cosmic_theme, widget, Element};
+
+use crate::{
+    fl,
+    hex_view::{export, parse_offset, HexView},
+};
+
+use super::{Action, AppModel};
+
+/// Range and format chosen in the export hex dump dialog.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExportDump {
+    pub start: String,
+    pub end: String,
+    pub pdf: bool,
+}
+
+impl ExportDump {
+    /// Starts with the selection or, without one, the whole file.
+    pub fn new(hex_view: &HexView) -> Self {
+        let len = hex_view.buffer.as_ref().map_or(0, |buffer| buffer.len());
+        let (start, end) = hex_view
+            .selection
+            .map_or((0, len.saturating_sub(1)), |selection| (selection.start(), selection.end()));
+        Self {
+            start: format!("0x{:X}", start),
+            end: format!("0x{:X}", end),
+            pdf: false,
+        }
+    }
+
+    /// Renders the chosen inclusive range, `None` if the range is invalid.
+    pub fn render(&self, hex_view: &HexView) -> Option<Vec<u8>> {
+        let buffer = hex_view.buffer.as_ref()?;
+        let start = parse_offset(&self.start)?;
+        let end = parse_offset(&self.end)?;
+        let data = buffer.data.get(start..=end)?;
+        let title = format!("{} — {}", hex_view.path.display(), chrono::Local::now().format("%Y-%m-%d %H:%M"));
+        Some(if self.pdf {
+            export::to_pdf(data, start, &title)
+        } else {
+            export::to_text(data, start, &title).into_bytes()
+        })
+    }
+}
+
+impl AppModel {
+    pub(crate) fn export_dump_dialog<'a>(&'a self, export: &'a ExportDump) -> Element<'a, Action> {
+        let cosmic_theme::Spacing { space_xxs, .. } = self.core().system_theme().cosmic().spacing;
+        let control = widget::column::with_capacity(3)
+            .spacing(space_xxs)
+            .push(
+                widget::settings::item::builder(fl!("export-start"))
+                    .control(widget::text_input::text_input("", &export.start).on_input(Action::ExportDumpStart)),
+            )
+            .push(widget::settings::item::builder(fl!("export-end")).control(widget::text_input::text_input("", &export.end).on_input(Action::ExportDumpEnd)))
+            .push(widget::settings::item::builder(fl!("export-pdf")).toggler(export.pdf, Action::ExportDumpPdf));
+
+        widget::dialog::Dialog::new()
+            .title(fl!("export-hex-dump"))
+            .control(control)
+            .primary_action(widget::button::suggested(fl!("export")).on_press(Action::ExportDumpConfirm))
+            .secondary_action(widget::button::text(fl!("cancel")).on_press(Action::DialogCancel))
+            .into()
+    }
+}
@@ -0,0 +1,38 @@
+//! File choosers that don't block the UI. rfd asks the XDG desktop portal, which also works inside
+//! a Flatpak sandbox.
+
+use std::path::PathBuf;
+
+use cosmic::app::Task;
+use rfd::{AsyncFileDialog, FileHandle};
+
+use super::Action;
+
+/// Lets the user pick an existing file and sends `action` with its path.
+pub fn open(dialog: AsyncFileDialog, action: impl FnOnce(PathBuf) -> Action + Send + 'static) -> Task<Action> {
+    Task::perform(dialog.pick_file(), move |handle| message(handle, action))
+}
+
+/// Like [`open`], but several files can be picked at once.
+pub fn open_many(dialog: AsyncFileDialog, action: impl FnOnce(Vec<PathBuf>) -> Action + Send + 'static) -> Task<Action> {
+    Task::perform(dialog.pick_files(), move |handles| match handles {
+        Some(handles) => cosmic::app::Message::App(action(handles.iter().map(|handle| handle.path().to_path_buf()).collect())),
+        None => cosmic::app::Message::None,
+    })
+}
+
+pub fn folder(dialog: AsyncFileDialog, action: impl FnOnce(PathBuf) -> Action + Send + 'static) -> Task<Action> {
+    Task::perform(dialog.pick_folder(), move |handle| message(handle, action))
+}
+
+/// Lets the user choose where to save and sends `action` with the path.
+pub fn save(dialog: AsyncFileDialog, action: impl FnOnce(PathBuf) -> Action + Send + 'static) -> Task<Action> {
+    Task::perform(dialog.save_file(), move |handle| message(handle, action))
+}
+
+fn message(handle: Option<FileHandle>, action: impl FnOnce(PathBuf) -> Action) -> cosmic::app::Message<Action> {
+    match handle {
+        Some(handle) => cosmic::app::Message::App(action(handle.path().to_path_buf())),
+        None => cosmic::app::Message::None,
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{
+    fl,
+    hex_view::bitmap::{self, PixelFormat},
+};
+use cosmic::{
+    iced::{widget::image::FilterMethod, Length},
+    widget, Element,
+};
+
+use super::{tab::Tab, Action, AppModel};
+
+const MAX_PREVIEW_HEIGHT: usize = 1024;
+
+pub struct ImagePreview {
+    pub offset: String,
+    pub width: String,
+    pub format: PixelFormat,
+    pub zoom: usize,
+}
+
+impl Default for ImagePreview {
+    fn default() -> Self {
+        Self {
+            offset: "0".to_string(),
+            width: "64".to_string(),
+            format: PixelFormat::Rgb24,
+            zoom: 2,
+        }
+    }
+}
+
+impl AppModel {
+    /// Renders the buffer from the chosen offset as a bitmap.
+    pub(crate) fn image_preview_view(&self) -> Element<Action> {
+        let preview = &self.image_preview;
+        let format_selected = PixelFormat::ALL.iter().position(|f| *f == preview.format);
+        let zoom_selected = zoom_levels.iter().position(|z| *z == preview.zoom);
+
+        let controls = widget::settings::section()
+            .add(
+                widget::settings::item::builder(fl!("image-offset"))
+                    .control(widget::text_input::text_input("0", &preview.offset).on_input(Action::ImagePreviewOffset)),
+            )
+            .add(
+                widget::settings::item::builder(fl!("image-width"))
+                    .control(widget::text_input::text_input("64", &preview.width).on_input(Action::ImagePreviewWidth)),
+            )
+            .add(widget::settings::item::builder(fl!("image-format")).control(widget::dropdown(
+                &pixel_format_names,
+                format_selected,
+                Action::ImagePreviewFormat,
+            )))
+            .add(widget::settings::item::builder(fl!("image-zoom")).control(widget::dropdown(&zoom_names, zoom_selected, Action::ImagePreviewZoom)));
+
+        let mut column = widget::column::with_capacity(2).spacing(8).push(controls);
+
+        let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(self.tab_model.active()) else {
+            return column.into();
+        };
+        let Some(buffer) = &tab.hex_view.buffer else {
+            return column.into();
+        };
+        let (Some(offset), Some(width)) = (crate::hex_view::parse_offset(&preview.offset), preview.width.trim().parse::<usize>().ok()) else {
+            return column.push(widget::text::body(fl!("image-invalid"))).into();
+        };
+        let data = buffer.data.get(offset..).unwrap_or_default();
+        let (height, pixels) = bitmap::to_rgba(data, width, preview.format, MAX_PREVIEW_HEIGHT);
+        if height > 0 {
+            let handle = widget::image::Handle::from_rgba(width as u32, height as u32, pixels);
+            column = column.push(widget::scrollable(
+                widget::image(handle)
+                    .width(Length::Fixed((width * preview.zoom) as f32))
+                    .height(Length::Fixed((height * preview.zoom) as f32))
+                    .filter_method(FilterMethod::Nearest),
+            ));
+        }
+        column.into()
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref zoom_levels: Vec<usize> = vec![1, 2, 4, 8];
+    static ref zoom_names: Vec<String> = zoom_levels.iter().map(|zoom| format!("{}×", zoom)).collect();
+    static ref pixel_format_names: Vec<String> = PixelFormat::ALL.iter().map(|format| format.name().to_string()).collect();
+}
@@ -0,0 +1,199 @@
+use crate::{
+    fl,
+    hex_view::{buffer::Endian, decode, HexView},
+};
+use cosmic::{
+    iced::{Background, Color, Length},
+    theme, widget, Element,
+};
+
+use super::{tab::Tab, Action, AppModel};
+
+/// Byte layouts for the color swatches.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorFormat {
+    Rgb,
+    Rgba,
+    Bgr,
+}
+
+impl ColorFormat {
+    const ALL: [ColorFormat; 3] = [ColorFormat::Rgb, ColorFormat::Rgba, ColorFormat::Bgr];
+
+    fn len(self) -> usize {
+        match self {
+            ColorFormat::Rgba => 4,
+            ColorFormat::Rgb | ColorFormat::Bgr => 3,
+        }
+    }
+
+    fn to_color(self, bytes: &[u8]) -> Color {
+        match self {
+            ColorFormat::Rgb => Color::from_rgb8(bytes[0], bytes[1], bytes[2]),
+            ColorFormat::Rgba => Color::from_rgba8(bytes[0], bytes[1], bytes[2], bytes[3] as f32 / 255.0),
+            ColorFormat::Bgr => Color::from_rgb8(bytes[2], bytes[1], bytes[0]),
+        }
+    }
+
+    /// Encodes a `#RRGGBB` or `#RRGGBBAA` string into the bytes of this format.
+    pub fn encode(self, input: &str) -> Option<Vec<u8>> {
+        let digits = input.trim().trim_start_matches('#');
+        if digits.len() != 6 && digits.len() != 8 {
+            return None;
+        }
+        let mut rgba = [0xFF; 4];
+        for (i, chunk) in digits.as_bytes().chunks(2).enumerate() {
+            rgba[i] = u8::from_str_radix(std::str::from_utf8(chunk).ok()?, 16).ok()?;
+        }
+        Some(match self {
+            ColorFormat::Rgb => rgba[..3].to_vec(),
+            ColorFormat::Rgba => rgba.to_vec(),
+            ColorFormat::Bgr => vec![rgba[2], rgba[1], rgba[0]],
+        })
+    }
+
+    fn name(self) -> String {
+        match self {
+            ColorFormat::Rgb => fl!("inspector-rgb"),
+            ColorFormat::Rgba => fl!("inspector-rgba"),
+            ColorFormat::Bgr => fl!("inspector-bgr"),
+        }
+    }
+}
+
+/// Reads `N` bytes at `offset` in little endian order, swapping them if the view is big endian.
+fn read<const N: usize>(data: &[u8], offset: usize, endian: Endian) -> Option<[u8; N]> {
+    Some(endian.normalize(data.get(offset..offset + N)?.try_into().ok()?))
+}
+
+impl AppModel {
+    /// The data inspector shows interpretations of the bytes at the caret.
+    pub(crate) fn inspector(&self) -> Element<Action> {
+        let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(self.tab_model.active()) else {
+            return widget::text::body(fl!("inspector-no-file")).into();
+        };
+        widget::settings::view_column(vec![
+            Self::timestamp_section(&tab.hex_view).into(),
+            Self::varint_section(&tab.hex_view).into(),
+            self.color_section(&tab.hex_view).into(),
+        ])
+        .into()
+    }
+
+    fn timestamp_section<'a>(hex_view: &HexView) -> widget::settings::Section<'a, Action> {
+        let data = hex_view.buffer.as_ref().map(|buffer| buffer.data.as_slice()).unwrap_or_default();
+        let offset = hex_view.cursor.position / 2;
+        let endian = hex_view.endian;
+        let or_invalid = |date: Option<String>| date.unwrap_or_else(|| fl!("inspector-invalid"));
+        const DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+        let rows = [
+            (
+                fl!("inspector-unix32"),
+                or_invalid(
+                    read(data, offset, endian)
+                        .and_then(decode::unix_time32)
+                        .map(|d| d.format(DATE_FORMAT).to_string()),
+                ),
+            ),
+            (
+                fl!("inspector-unix64"),
+                or_invalid(
+                    read(data, offset, endian)
+                        .and_then(decode::unix_time64)
+                        .map(|d| d.format(DATE_FORMAT).to_string()),
+                ),
+            ),
+            (
+                fl!("inspector-filetime"),
+                or_invalid(read(data, offset, endian).and_then(decode::filetime).map(|d| d.format(DATE_FORMAT).to_string())),
+            ),
+            (
+                fl!("inspector-dos-time"),
+                or_invalid(
+                    read(data, offset, endian)
+                        .and_then(decode::dos_date_time)
+                        .map(|d| d.format(DATE_FORMAT).to_string()),
+                ),
+            ),
+            (
+                fl!("inspector-hfs-time"),
+                or_invalid(read(data, offset, endian).and_then(decode::hfs_time).map(|d| d.format(DATE_FORMAT).to_string())),
+            ),
+            (
+                fl!("inspector-cocoa-time"),
+                or_invalid(
+                    read(data, offset, endian)
+                        .and_then(decode::cocoa_time)
+                        .map(|d| d.format(DATE_FORMAT).to_string()),
+                ),
+            ),
+        ];
+
+        let mut section = widget::settings::section().title(fl!("inspector-timestamps"));
+        for (label, value) in rows {
+            section = section.add(widget::settings::item::builder(label).control(widget::text::body(value)));
+        }
+        section
+    }
+
+    fn varint_section<'a>(hex_view: &HexView) -> widget::settings::Section<'a, Action> {
+        let data = hex_view.buffer.as_ref().map(|buffer| buffer.data.as_slice()).unwrap_or_default();
+        let data = data.get(hex_view.cursor.position / 2..).unwrap_or_default();
+        let with_len = |value: Option<(String, usize)>| match value {
+            Some((value, len)) => fl!("inspector-varint-value", value = value, len = len),
+            None => fl!("inspector-invalid"),
+        };
+
+        let rows = [
+            (fl!("inspector-varint"), with_len(decode::uleb128(data).map(|(v, len)| (v.to_string(), len)))),
+            (
+                fl!("inspector-zigzag"),
+                with_len(decode::uleb128(data).map(|(v, len)| (decode::zigzag(v).to_string(), len))),
+            ),
+            (fl!("inspector-sleb128"), with_len(decode::sleb128(data).map(|(v, len)| (v.to_string(), len)))),
+        ];
+
+        let mut section = widget::settings::section().title(fl!("inspector-varints"));
+        for (label, value) in rows {
+            section = section.add(widget::settings::item::builder(label).control(widget::text::body(value)));
+        }
+        section
+    }
+
+    fn color_section<'a>(&'a self, hex_view: &HexView) -> widget::settings::Section<'a, Action> {
+        let data = hex_view.buffer.as_ref().map(|buffer| buffer.data.as_slice()).unwrap_or_default();
+        let data = data.get(hex_view.cursor.position / 2..).unwrap_or_default();
+
+        let mut section = widget::settings::section().title(fl!("inspector-color"));
+        for format in ColorFormat::ALL {
+            let Some(bytes) = data.get(..format.len()) else {
+                continue;
+            };
+            let color = format.to_color(bytes);
+            let swatch = widget::container(widget::Space::new(Length::Fixed(32.0), Length::Fixed(16.0))).class(theme::Container::custom(move |_theme| {
+                widget::container::Style {
+                    background: Some(Background::Color(color)),
+                    ..Default::default()
+                }
+            }));
+            let write_button = widget::button::standard(fl!("inspector-write-color"));
+            let write_button = if format.encode(&self.color_input).is_some() {
+                write_button.on_press(Action::WriteColor(format))
+            } else {
+                write_button
+            };
+            section = section.add(
+                widget::settings::item::builder(format.name()).control(
+                    widget::row::with_children(vec![swatch.into(), write_button.into()])
+                        .spacing(8)
+                        .align_y(cosmic::iced::Alignment::Center),
+                ),
+            );
+        }
+        section.add(
+            widget::settings::item::builder(fl!("inspector-new-color"))
+                .control(widget::text_input::text_input("#RRGGBBAA", &self.color_input).on_input(Action::ColorInputChanged)),
+        )
+    }
+}
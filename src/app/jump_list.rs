@@ -0,0 +1,81 @@
+use cosmic::{cosmic_theme, iced::Length, widget, Element};
+
+use crate::{
+    fl,
+    hex_view::{parse_offset, protobuf::ProtoField, protobuf::ProtoValue, HexView},
+};
+
+use super::{Action, AppModel};
+
+/// Search hits listed at most, the rest is reachable with find next.
+const MAX_MATCHES: usize = 200;
+
+/// Returns true if all characters of `pattern` appear in `text` in order, ignoring case.
+fn fuzzy_match(pattern: &str, text: &str) -> bool {
+    let mut text = text.chars().flat_map(char::to_lowercase);
+    pattern.chars().flat_map(char::to_lowercase).all(|p| text.any(|t| t == p))
+}
+
+fn push_fields(entries: &mut Vec<(String, usize)>, fields: &[ProtoField]) {
+    for field in fields {
+        entries.push((fl!("jump-field", number = field.number), field.range.start));
+        if let ProtoValue::Message(children) = &field.value {
+            push_fields(entries, children);
+        }
+    }
+}
+
+impl AppModel {
+    /// Offsets worth jumping to in `hex_view`, labelled by where they come from.
+    fn jump_entries(&self, hex_view: &HexView) -> Vec<(String, usize)> {
+        let mut entries = Vec::new();
+        for offset in &hex_view.bookmarks {
+            let label = match hex_view.bookmark_notes.get(offset) {
+                Some(note) => format!("{}: {}", fl!("jump-bookmark"), note),
+                None => fl!("jump-bookmark"),
+            };
+            entries.push((label, *offset));
+        }
+        for offset in hex_view.edit_positions.iter().rev() {
+            entries.push((fl!("jump-edit"), *offset));
+        }
+        if let Some(fields) = &self.protobuf_fields {
+            push_fields(&mut entries, fields);
+        }
+        for offset in hex_view.matches.iter().take(MAX_MATCHES) {
+            entries.push((fl!("jump-match"), *offset));
+        }
+        entries
+    }
+
+    pub(crate) fn jump_list_dialog<'a>(&'a self, hex_view: &'a HexView, filter: &'a str) -> Element<'a, Action> {
+        let cosmic_theme::Spacing { space_xxs, .. } = self.core().system_theme().cosmic().spacing;
+        let mut list = widget::column::with_capacity(16).spacing(space_xxs);
+        // An offset (or address in virtual address mode) typed as filter can be jumped to directly.
+        if let Some(offset) = parse_offset(filter).and_then(|value| hex_view.resolve_address(value as u64)) {
+            let text = fl!("jump-go-to", target = filter.trim());
+            list = list.push(widget::button::text(text).on_press(Action::JumpTo(offset)).width(Length::Fill));
+        }
+        for (label, offset) in self.jump_entries(hex_view) {
+            let text = format!("{:08X}  {}", offset, label);
+            if !fuzzy_match(filter, &text) {
+                continue;
+            }
+            list = list.push(widget::button::text(text).on_press(Action::JumpTo(offset)).width(Length::Fill));
+        }
+
+        let filter_input = widget::text_input::text_input(fl!("jump-filter"), filter)
+            .id(self.jump_filter_id.clone())
+            .on_input(Action::JumpListFilter);
+        let control = widget::column::with_capacity(2)
+            .spacing(space_xxs)
+            .push(filter_input)
+            .push(widget::scrollable(list).height(Length::Fixed(320.0)));
+
+        widget::dialog::Dialog::new()
+            .title(fl!("jump-to"))
+            .control(control)
+            .secondary_action(widget::button::text(fl!("cancel")).on_press(Action::DialogCancel))
+            .into()
+    }
+}
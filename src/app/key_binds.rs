@@ -1,5 +1,5 @@
 use cosmic::{
-    iced::keyboard::Key,
+    iced::keyboard::{key::Named, Key},
     widget::menu::{key_bind::Modifier, KeyBind},
 };
 use std::collections::HashMap;
@@ -13,6 +13,13 @@ fn bind_key(key: char) -> KeyBind {
     }
 }
 
+fn bind_named(key: Named, modifier: Modifier) -> KeyBind {
+    KeyBind {
+        key: Key::Named(key),
+        modifiers: vec![modifier],
+    }
+}
+
 fn bind_key_ctrl_shift(key: char) -> KeyBind {
     KeyBind {
         key: Key::Character(key.to_string().into()),
@@ -20,6 +27,13 @@ fn bind_key_ctrl_shift(key: char) -> KeyBind {
     }
 }
 
+fn bind_named_ctrl_shift(key: Named) -> KeyBind {
+    KeyBind {
+        key: Key::Named(key),
+        modifiers: vec![Modifier::Ctrl, Modifier::Shift],
+    }
+}
+
 pub fn get_key_binds() -> HashMap<KeyBind, MenuAction> {
     HashMap::from([
         // File
@@ -30,6 +44,26 @@ pub fn get_key_binds() -> HashMap<KeyBind, MenuAction> {
         // Edit
         (bind_key('z'), MenuAction::Undo),
         (bind_key_ctrl_shift('z'), MenuAction::Redo),
+        (bind_key('v'), MenuAction::Paste),
         (bind_key('f'), MenuAction::Find),
+        (bind_key_ctrl_shift('r'), MenuAction::SelectRun),
+        (bind_key('b'), MenuAction::ToggleBookmark),
+        (bind_key('m'), MenuAction::ToggleMark),
+        (bind_key('j'), MenuAction::JumpList),
+        (bind_key('g'), MenuAction::FollowPointer),
+        (bind_named(Named::Delete, Modifier::Ctrl), MenuAction::ZeroFill),
+        (bind_named_ctrl_shift(Named::Delete), MenuAction::NopFillTab),
+        // Navigation
+        (bind_named(Named::ArrowRight, Modifier::Ctrl), MenuAction::NextGroup),
+        (bind_named(Named::ArrowLeft, Modifier::Ctrl), MenuAction::PreviousGroup),
+        (bind_named(Named::ArrowDown, Modifier::Alt), MenuAction::JumpRowsDown),
+        (bind_named(Named::ArrowUp, Modifier::Alt), MenuAction::JumpRowsUp),
+        (bind_key_ctrl_shift('n'), MenuAction::NextModified),
+        (bind_key_ctrl_shift('p'), MenuAction::PreviousModified),
+        (bind_named(Named::PageDown, Modifier::Alt), MenuAction::NextRecord),
+        (bind_named(Named::PageUp, Modifier::Alt), MenuAction::PreviousRecord),
+        // View
+        (bind_key('i'), MenuAction::ShowInspector),
+        (bind_key('t'), MenuAction::ToggleTextView),
     ])
 }
@@ -1,11 +1,22 @@
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::Path;
 
-use crate::fl;
-use cosmic::{widget::menu, Element};
+use crate::{
+    fl,
+    hex_view::{arch::Architecture, buffer::Endian, highlight::Layer, reorder::Reorder, transform::TextTransform, Message},
+};
+use cosmic::{
+    iced::Length,
+    widget::{self, menu},
+    Element,
+};
 
-use super::{Action, AppModel, ContextPage};
+use super::{font_names, tab::Tab, Action, AppModel, ContextPage};
 
-fn format_path(path: &PathBuf) -> String {
+/// Rows moved by Alt+Up/Down.
+const JUMP_ROWS: isize = 16;
+
+pub(super) fn format_path(path: &Path) -> String {
     let home_dir_opt = dirs::home_dir();
     if let Some(home_dir) = &home_dir_opt {
         if let Ok(part) = path.strip_prefix(home_dir) {
@@ -15,15 +26,97 @@ fn format_path(path: &PathBuf) -> String {
     path.display().to_string()
 }
 
+/// Menu button that is greyed out when its action doesn't apply.
+fn button(label: String, action: MenuAction, enabled: bool) -> menu::Item<MenuAction, String> {
+    if enabled {
+        menu::Item::Button(label, None, action)
+    } else {
+        menu::Item::ButtonDisabled(label, None, action)
+    }
+}
+
 impl AppModel {
     pub(crate) fn menu_bar(&self) -> Element<Action> {
-        let recent_files = self
+        let tab = match self.tab_model.data::<Tab>(self.tab_model.active()) {
+            Some(Tab::Editor(tab)) => Some(tab),
+            _ => None,
+        };
+        let has_tab = tab.is_some();
+        let writable = tab.is_some_and(|tab| !tab.hex_view.read_only);
+        let can_undo = writable && tab.is_some_and(|tab| !tab.hex_view.undo_buffer.is_empty());
+        let can_redo = writable && tab.is_some_and(|tab| !tab.hex_view.redo_buffer.is_empty());
+        let has_selection = tab.is_some_and(|tab| tab.hex_view.selection.is_some());
+        let is_executable = tab.is_some_and(|tab| tab.hex_view.image.is_some());
+        let has_sidecar = tab.is_some_and(|tab| tab.sidecar.is_some());
+        let has_records = tab.is_some_and(|tab| tab.hex_view.record_size.is_some());
+        let mut tab_fonts = vec![button(fl!("tab-font-default"), MenuAction::SetTabFont(None), has_tab), menu::Item::Divider];
+        tab_fonts.extend(
+            font_names
+                .iter()
+                .enumerate()
+                .map(|(i, font_name)| button(font_name.clone(), MenuAction::SetTabFont(Some(i)), has_tab)),
+        );
+        let layer_items = Layer::ALL
+            .into_iter()
+            .map(|layer| menu::Item::CheckBox(layer.name(), None, !self.config.hidden_layers.contains(&layer), MenuAction::ToggleLayer(layer)))
+            .collect();
+        let architecture = tab.and_then(|tab| tab.options.architecture);
+        let mut fill_items = vec![
+            button(fl!("fill-zero"), MenuAction::ZeroFill, writable && has_selection),
+            button(
+                match architecture {
+                    Some(arch) => fl!("fill-nop-tab", arch = arch.name()),
+                    None => fl!("fill-nop-no-architecture"),
+                },
+                MenuAction::NopFillTab,
+                writable && has_selection && architecture.is_some(),
+            ),
+            menu::Item::Divider,
+        ];
+        fill_items.extend(
+            Architecture::ALL
+                .iter()
+                .map(|arch| button(fl!("fill-nop", arch = arch.name()), MenuAction::NopFill(*arch), writable && has_selection)),
+        );
+        let transform_items = vec![
+            button(fl!("transform-rot13"), MenuAction::Transform(TextTransform::Rot13), writable && has_selection),
+            button(fl!("transform-atbash"), MenuAction::Transform(TextTransform::Atbash), writable && has_selection),
+            button(fl!("transform-caesar"), MenuAction::Caesar, writable && has_selection),
+        ];
+        let reorder_items = vec![
+            button(fl!("reorder-reverse"), MenuAction::Reorder(Reorder::Reverse), writable && has_selection),
+            button(fl!("reorder-interleave"), MenuAction::Reorder(Reorder::Interleave), writable && has_selection),
+            button(
+                fl!("reorder-deinterleave"),
+                MenuAction::Reorder(Reorder::Deinterleave),
+                writable && has_selection,
+            ),
+        ];
+        let any_dirty = self
+            .tab_model
+            .iter()
+            .any(|entity| self.tab_model.data::<Tab>(entity).is_some_and(|tab| tab.is_dirty()));
+
+        let mut recent_files = self
             .config_state
-            .recent_files
+            .pinned_files
             .iter()
             .enumerate()
-            .map(|(i, path)| menu::Item::Button(format_path(path), None, MenuAction::OpenRecentFile(i)))
+            .map(|(i, path)| menu::Item::Button(format!("📌 {}", format_path(path)), None, MenuAction::OpenPinnedFile(i)))
             .collect::<Vec<_>>();
+        if !recent_files.is_empty() {
+            recent_files.push(menu::Item::Divider);
+        }
+        recent_files.extend(
+            self.config_state
+                .recent_files
+                .iter()
+                .enumerate()
+                .map(|(i, path)| menu::Item::Button(format_path(path), None, MenuAction::OpenRecentFile(i))),
+        );
+        recent_files.push(menu::Item::Divider);
+        recent_files.push(button(fl!("toggle-pin-file"), MenuAction::TogglePinFile, has_tab));
+        recent_files.push(menu::Item::Button(fl!("clear-recent-files"), None, MenuAction::ClearRecentFiles));
 
         menu::bar(vec![
             menu::Tree::with_children(
@@ -32,12 +125,21 @@ impl AppModel {
                     &self.key_binds,
                     vec![
                         menu::Item::Button(fl!("open-file"), None, MenuAction::Open),
+                        menu::Item::Button(fl!("open-folder"), None, MenuAction::OpenFolder),
                         menu::Item::Folder(fl!("open-recent-file"), recent_files),
-                        menu::Item::Button(fl!("close-file"), None, MenuAction::CloseFile),
+                        button(fl!("close-file"), MenuAction::CloseFile, has_tab),
+                        button(fl!("duplicate-view"), MenuAction::DuplicateView, has_tab),
+                        menu::Item::Divider,
+                        button(fl!("save"), MenuAction::Save, has_tab),
+                        button(fl!("save-as"), MenuAction::SaveAs, has_tab),
+                        button(fl!("save-selection-as"), MenuAction::SaveSelectionAs, has_selection),
+                        button(fl!("save-all"), MenuAction::SaveAll, any_dirty),
+                        button(fl!("revert"), MenuAction::Revert, has_tab),
                         menu::Item::Divider,
-                        menu::Item::Button(fl!("save"), None, MenuAction::Save),
-                        menu::Item::Button(fl!("save-as"), None, MenuAction::SaveAs),
-                        menu::Item::Button(fl!("save-all"), None, MenuAction::SaveAll),
+                        button(fl!("export-hex-dump"), MenuAction::ExportDump, has_tab),
+                        button(fl!("export-ips"), MenuAction::ExportIps, has_tab),
+                        button(fl!("properties"), MenuAction::Properties, has_tab),
+                        button(fl!("verify-checksum"), MenuAction::VerifyChecksum, has_sidecar),
                         menu::Item::Divider,
                         menu::Item::Button(fl!("quit"), None, MenuAction::Quit),
                     ],
@@ -48,10 +150,59 @@ impl AppModel {
                 menu::items(
                     &self.key_binds,
                     vec![
-                        menu::Item::Button(fl!("undo"), None, MenuAction::Undo),
-                        menu::Item::Button(fl!("redo"), None, MenuAction::Redo),
+                        button(fl!("undo"), MenuAction::Undo, can_undo),
+                        button(fl!("redo"), MenuAction::Redo, can_redo),
                         menu::Item::Divider,
-                        menu::Item::Button(fl!("find"), None, MenuAction::Find),
+                        button(fl!("paste"), MenuAction::Paste, writable && self.clipboard_has_data),
+                        menu::Item::Folder(fl!("copy-as"), vec![button(fl!("copy-as-html"), MenuAction::CopyAsHtml, has_selection)]),
+                        menu::Item::Folder(fl!("fill-selection"), fill_items),
+                        menu::Item::Folder(fl!("transform-selection"), transform_items),
+                        menu::Item::Folder(fl!("reorder-selection"), reorder_items),
+                        button(fl!("revert-selection"), MenuAction::RevertSelection, writable && has_selection),
+                        button(fl!("resize-file"), MenuAction::Resize, writable),
+                        menu::Item::Divider,
+                        button(fl!("select-run"), MenuAction::SelectRun, has_tab),
+                        menu::Item::Divider,
+                        button(fl!("toggle-bookmark"), MenuAction::ToggleBookmark, has_tab),
+                        button(fl!("toggle-mark"), MenuAction::ToggleMark, has_tab),
+                        button(fl!("next-bookmark"), MenuAction::NextBookmark, has_tab),
+                        button(fl!("previous-bookmark"), MenuAction::PreviousBookmark, has_tab),
+                        button(fl!("import-bookmarks"), MenuAction::ImportBookmarks, has_tab),
+                        button(fl!("export-bookmarks"), MenuAction::ExportBookmarks, has_tab),
+                        button(fl!("import-listing"), MenuAction::ImportListing, has_tab),
+                        button(fl!("jump-to"), MenuAction::JumpList, has_tab),
+                        button(fl!("follow-pointer"), MenuAction::FollowPointer, has_tab),
+                        button(fl!("next-modified"), MenuAction::NextModified, has_tab),
+                        button(fl!("previous-modified"), MenuAction::PreviousModified, has_tab),
+                        menu::Item::Divider,
+                        button(fl!("records"), MenuAction::Records, has_tab),
+                        button(fl!("next-record"), MenuAction::NextRecord, has_records),
+                        button(fl!("previous-record"), MenuAction::PreviousRecord, has_records),
+                        menu::Item::Divider,
+                        button(fl!("find"), MenuAction::Find, has_tab),
+                    ],
+                ),
+            ),
+            menu::Tree::with_children(
+                menu::root(fl!("tools")),
+                menu::items(
+                    &self.key_binds,
+                    vec![
+                        button(fl!("decode-protobuf"), MenuAction::DecodeProtobuf, has_tab),
+                        button(fl!("image-preview"), MenuAction::ImagePreview, has_tab),
+                        button(fl!("audio-preview"), MenuAction::AudioPreview, has_tab),
+                        button(fl!("compare-with-clipboard"), MenuAction::CompareWithClipboard, has_selection),
+                    ],
+                ),
+            ),
+            menu::Tree::with_children(
+                menu::root(fl!("analyze")),
+                menu::items(
+                    &self.key_binds,
+                    vec![
+                        button(fl!("scan-yara"), MenuAction::ScanYara, has_tab),
+                        button(fl!("xor-search"), MenuAction::XorSearch, has_tab),
+                        button(fl!("compare-ranges"), MenuAction::CompareRanges, has_tab),
                     ],
                 ),
             ),
@@ -60,6 +211,23 @@ impl AppModel {
                 menu::items(
                     &self.key_binds,
                     vec![
+                        button(fl!("view-as-text"), MenuAction::ToggleTextView, has_tab),
+                        menu::Item::Button(fl!("data-inspector"), None, MenuAction::ShowInspector),
+                        menu::Item::Button(fl!("watches"), None, MenuAction::ShowWatches),
+                        menu::Item::Button(fl!("symbols"), None, MenuAction::ShowSymbols),
+                        menu::Item::Folder(
+                            fl!("byte-order"),
+                            vec![
+                                button(fl!("byte-order-default"), MenuAction::SetTabEndian(None), has_tab),
+                                button(fl!("little-endian"), MenuAction::SetTabEndian(Some(Endian::Little)), has_tab),
+                                button(fl!("big-endian"), MenuAction::SetTabEndian(Some(Endian::Big)), has_tab),
+                            ],
+                        ),
+                        menu::Item::Folder(fl!("tab-font"), tab_fonts),
+                        button(fl!("tab-options"), MenuAction::TabOptions, has_tab),
+                        menu::Item::Folder(fl!("highlight-layers"), layer_items),
+                        button(fl!("virtual-addresses"), MenuAction::ToggleVirtualAddresses, is_executable),
+                        menu::Item::Divider,
                         menu::Item::Button(fl!("menu-settings"), None, MenuAction::ShowSettings),
                         menu::Item::Button(fl!("about"), None, MenuAction::About),
                     ],
@@ -70,20 +238,110 @@ impl AppModel {
     }
 }
 
+fn context_menu_entries() -> Vec<(String, MenuAction)> {
+    vec![
+        (fl!("copy-offset"), MenuAction::CopyOffset),
+        (fl!("copy-relative-offset"), MenuAction::CopyRelativeOffset),
+        (fl!("set-offset-base"), MenuAction::SetOffsetBase),
+    ]
+}
+
+/// Entries of the hex view context menu.
+pub(super) fn context_menu(key_binds: &HashMap<menu::KeyBind, MenuAction>) -> Vec<menu::Tree<Action>> {
+    menu::items(
+        key_binds,
+        context_menu_entries()
+            .into_iter()
+            .map(|(label, action)| menu::Item::Button(label, None, action))
+            .collect(),
+    )
+}
+
+/// The context menu as a popup, for touch long presses that the context menu widget doesn't react to.
+pub(super) fn touch_context_menu<'a>() -> Element<'a, Action> {
+    let mut column = widget::column::with_capacity(3);
+    for (label, action) in context_menu_entries() {
+        column = column.push(widget::button::text(label).on_press(Action::ContextMenuAction(action)).width(Length::Fill));
+    }
+    widget::container(column.width(Length::Fixed(240.0)))
+        .padding(4)
+        .class(cosmic::theme::Container::Dropdown)
+        .into()
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum MenuAction {
     Open,
+    OpenFolder,
     CloseFile,
+    DuplicateView,
     About,
     OpenRecentFile(usize),
+    OpenPinnedFile(usize),
+    TogglePinFile,
+    ClearRecentFiles,
     Save,
     SaveAs,
+    SaveSelectionAs,
     SaveAll,
     Quit,
     ShowSettings,
+    ShowInspector,
+    ShowWatches,
+    ShowSymbols,
+    ToggleLayer(Layer),
     Find,
     Undo,
     Redo,
+    Paste,
+    DecodeProtobuf,
+    ImagePreview,
+    AudioPreview,
+    ToggleTextView,
+    SelectRun,
+    Properties,
+    VerifyChecksum,
+    CopyOffset,
+    CopyRelativeOffset,
+    SetOffsetBase,
+    ToggleBookmark,
+    ToggleMark,
+    NextBookmark,
+    PreviousBookmark,
+    NextGroup,
+    NextModified,
+    ToggleVirtualAddresses,
+    FollowPointer,
+    PreviousModified,
+    PreviousGroup,
+    JumpRowsDown,
+    JumpRowsUp,
+    JumpList,
+    Records,
+    Resize,
+    NextRecord,
+    PreviousRecord,
+    CopyAsHtml,
+    ExportDump,
+    ExportIps,
+    ImportBookmarks,
+    ExportBookmarks,
+    ImportListing,
+    Revert,
+    SetTabEndian(Option<Endian>),
+    SetTabFont(Option<usize>),
+    TabOptions,
+    ZeroFill,
+    RevertSelection,
+    Transform(TextTransform),
+    Reorder(Reorder),
+    Caesar,
+    NopFill(Architecture),
+    NopFillTab,
+    CompareWithClipboard,
+    ScanYara,
+    XorSearch,
+    CompareRanges,
 }
 
 impl menu::action::MenuAction for MenuAction {
@@ -94,17 +352,76 @@ impl menu::action::MenuAction for MenuAction {
             MenuAction::Open => {
                 return Action::ChooseOpenFile;
             }
+            MenuAction::OpenFolder => Action::ChooseOpenFolder,
             MenuAction::CloseFile => Action::TabClose(None),
+            MenuAction::DuplicateView => Action::DuplicateView,
             MenuAction::About => Action::ToggleContextPage(ContextPage::About),
             MenuAction::OpenRecentFile(i) => Action::OpenRecentFile(*i),
+            MenuAction::OpenPinnedFile(i) => Action::OpenPinnedFile(*i),
+            MenuAction::TogglePinFile => Action::TogglePinFile,
+            MenuAction::ClearRecentFiles => Action::ClearRecentFiles,
             MenuAction::Quit => Action::QuitForce,
             MenuAction::ShowSettings => Action::ToggleContextPage(ContextPage::Settings),
+            MenuAction::ShowInspector => Action::ToggleContextPage(ContextPage::Inspector),
+            MenuAction::ShowWatches => Action::ToggleContextPage(ContextPage::Watches),
+            MenuAction::ShowSymbols => Action::ToggleContextPage(ContextPage::Symbols),
+            MenuAction::ToggleLayer(layer) => Action::ToggleLayer(*layer),
             MenuAction::Find => Action::Find,
             MenuAction::Undo => Action::Undo,
             MenuAction::Redo => Action::Redo,
+            MenuAction::Paste => Action::Paste,
             MenuAction::Save => Action::Save(None),
             MenuAction::SaveAs => Action::SaveAs,
+            MenuAction::SaveSelectionAs => Action::SaveSelectionAs,
             MenuAction::SaveAll => Action::SaveAll,
+            MenuAction::DecodeProtobuf => Action::DecodeProtobuf,
+            MenuAction::CompareWithClipboard => Action::CompareWithClipboard,
+            MenuAction::ScanYara => Action::ScanYara,
+            MenuAction::XorSearch => Action::ShowXorSearch,
+            MenuAction::CompareRanges => Action::ShowCompareRanges,
+            MenuAction::ImagePreview => Action::ShowImagePreview,
+            MenuAction::AudioPreview => Action::ShowAudioPreview,
+            MenuAction::ToggleTextView => Action::ToggleTextView,
+            MenuAction::SelectRun => Action::SelectRun,
+            MenuAction::Properties => Action::ShowProperties,
+            MenuAction::VerifyChecksum => Action::VerifyChecksum,
+            MenuAction::CopyOffset => Action::CopyOffset(false),
+            MenuAction::CopyRelativeOffset => Action::CopyOffset(true),
+            MenuAction::SetOffsetBase => Action::SetOffsetBase,
+            MenuAction::ToggleBookmark => Action::HexAction(Message::ToggleBookmark),
+            MenuAction::ToggleMark => Action::HexAction(Message::ToggleMark),
+            MenuAction::NextBookmark => Action::HexAction(Message::NextBookmark),
+            MenuAction::PreviousBookmark => Action::HexAction(Message::PreviousBookmark),
+            MenuAction::NextGroup => Action::HexAction(Message::MoveGroup(true)),
+            MenuAction::ToggleVirtualAddresses => Action::HexAction(Message::ToggleVirtualAddresses),
+            MenuAction::FollowPointer => Action::HexAction(Message::FollowPointer),
+            MenuAction::NextModified => Action::HexAction(Message::MoveModified(true)),
+            MenuAction::PreviousModified => Action::HexAction(Message::MoveModified(false)),
+            MenuAction::PreviousGroup => Action::HexAction(Message::MoveGroup(false)),
+            MenuAction::JumpRowsDown => Action::HexAction(Message::MoveRows(JUMP_ROWS)),
+            MenuAction::JumpRowsUp => Action::HexAction(Message::MoveRows(-JUMP_ROWS)),
+            MenuAction::JumpList => Action::ShowJumpList,
+            MenuAction::Records => Action::ShowRecords,
+            MenuAction::Resize => Action::ShowResize,
+            MenuAction::NextRecord => Action::HexAction(Message::MoveRecords(1)),
+            MenuAction::PreviousRecord => Action::HexAction(Message::MoveRecords(-1)),
+            MenuAction::CopyAsHtml => Action::CopyAsHtml,
+            MenuAction::ExportDump => Action::ShowExportDump,
+            MenuAction::ExportIps => Action::ExportIps,
+            MenuAction::ImportBookmarks => Action::ImportBookmarks,
+            MenuAction::ExportBookmarks => Action::ExportBookmarks,
+            MenuAction::ImportListing => Action::ImportListing,
+            MenuAction::Revert => Action::Revert,
+            MenuAction::SetTabEndian(endian) => Action::SetTabEndian(*endian),
+            MenuAction::SetTabFont(index) => Action::SetTabFont(*index),
+            MenuAction::TabOptions => Action::ToggleTabOptions,
+            MenuAction::RevertSelection => Action::HexAction(Message::RevertSelection),
+            MenuAction::Transform(transform) => Action::HexAction(Message::TransformSelection(*transform)),
+            MenuAction::Reorder(reorder) => Action::HexAction(Message::ReorderSelection(*reorder)),
+            MenuAction::Caesar => Action::ShowCaesar,
+            MenuAction::ZeroFill => Action::HexAction(Message::FillSelection(vec![0])),
+            MenuAction::NopFill(arch) => Action::HexAction(Message::FillSelection(arch.nop().to_vec())),
+            MenuAction::NopFillTab => Action::NopFill,
         }
     }
 }
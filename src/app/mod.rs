@@ -1,14 +1,21 @@
 // SPDX-License-Identifier: {{LICENSE}}
 
-use crate::config::{AppTheme, Config};
-use crate::hex_view::buffer::DataBuffer;
+use crate::config::{AppTheme, Config, StatusField};
+use crate::hex_view::bookmarks;
+use crate::hex_view::buffer::{DataBuffer, Endian};
+use crate::hex_view::checksum::RowChecksum;
 use crate::hex_view::hexviewwidget::HexViewWidget;
+use crate::hex_view::highlight::{self, Layer};
+use crate::hex_view::numeric::NumericColumn;
+use crate::hex_view::selection::Selection;
+use crate::hex_view::theme::{blend, contrast_ratio};
 use crate::hex_view::Message;
+use crate::i18n::format_number;
 use crate::{fl, SYNTAX_SYSTEM};
 use cosmic::app::{context_drawer, Core, Task};
 use cosmic::cosmic_config::cosmic_config_derive::CosmicConfigEntry;
 use cosmic::cosmic_config::{self, CosmicConfigEntry};
-use cosmic::iced::{event, keyboard, Alignment, Length, Subscription};
+use cosmic::iced::{event, keyboard, window, Alignment, Length, Subscription};
 use cosmic::iced_wgpu::graphics::text::font_system;
 use cosmic::widget::menu::Action as _;
 use cosmic::widget::segmented_button::Entity;
@@ -21,9 +28,32 @@ use std::path::PathBuf;
 use std::{fs, process};
 use tab::Tab;
 
+mod accessibility;
+mod audio_preview;
+mod compare_ranges;
+mod export_dump;
+mod file_chooser;
+mod image_preview;
+mod inspector;
+mod jump_list;
 mod key_binds;
 mod menu_bar;
+mod project;
+mod properties;
+mod protobuf;
+mod records;
+mod resize;
+mod sandbox;
+mod save_selection;
+mod sidecar;
+mod status_bar;
+mod symbols;
 mod tab;
+mod tab_options;
+mod text_view;
+mod watches;
+mod xor_search;
+mod yara_scan;
 
 const REPOSITORY: &str = "https://github.com/mkrueger/cosmic-hex";
 const APP_ICON: &[u8] = include_bytes!("../../res/icons/hicolor/scalable/apps/icon.svg");
@@ -34,6 +64,7 @@ pub struct AppModel {
     core: Core,
     context_page: ContextPage,
     tab_model: segmented_button::SingleSelectModel,
+    nav_model: widget::nav_bar::Model,
     dialog_page_opt: Option<DialogPage>,
     key_binds: HashMap<menu::KeyBind, menu_bar::MenuAction>,
     config_handler: Option<cosmic_config::Config>,
@@ -42,17 +73,61 @@ pub struct AppModel {
     config_state: ConfigState,
 
     find_search_id: widget::Id,
+    jump_filter_id: widget::Id,
     find: bool,
     search_pattern: String,
+    search_mode: SearchMode,
     needle: Vec<u8>,
+    match_count: Option<usize>,
+    replace_pattern: String,
+
+    protobuf_fields: Option<Vec<crate::hex_view::protobuf::ProtoField>>,
+    color_input: String,
+    watches: Vec<watches::Watch>,
+    watch_input: String,
+    symbol_filter: String,
+    /// Matches of the last YARA scan, `None` while it runs.
+    yara_results: Option<Result<Vec<yara_scan::YaraMatch>, String>>,
+    /// The selection currently written by Save Selection As.
+    save_selection: Option<save_selection::SaveSelection>,
+    image_preview: image_preview::ImagePreview,
+    pcm_format: crate::hex_view::pcm::PcmFormat,
+    audio_cache: std::cell::RefCell<audio_preview::AudioCache>,
 
     modifiers: keyboard::Modifiers,
+    window_title: String,
+    /// Whether the clipboard held text when last checked, enables Paste.
+    clipboard_has_data: bool,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 enum DialogPage {
     PromptSaveClose(segmented_button::Entity),
     PromptSaveQuit(Vec<segmented_button::Entity>),
+    PromptRevert(segmented_button::Entity),
+    /// The needle and replacement as parsed when the dialog opened, per tab match counts (`None`
+    /// until counted) and whether the tab takes part in the replacement.
+    ReplaceAll(Vec<u8>, Vec<u8>, Vec<(segmented_button::Entity, Option<usize>, bool)>),
+    Properties(properties::FileProperties),
+    /// Jump list of the active tab with the current filter.
+    JumpList(String),
+    ExportDump(export_dump::ExportDump),
+    Records(records::Records),
+    Resize(resize::Resize),
+    XorSearch(xor_search::XorSearch),
+    CompareRanges(compare_ranges::CompareRanges),
+    /// Result of checking the file against its checksum file.
+    ChecksumResult(sidecar::Verification),
+    /// Offers to update the checksum file of the tab after saving edits.
+    PromptRegenerateSidecar(segmented_button::Entity),
+    /// Asks before Save As replaces an existing file.
+    PromptOverwrite(segmented_button::Entity, PathBuf),
+    /// Save As couldn't write the file, with the error.
+    SaveFailed(PathBuf, String),
+    /// Shift typed in the Caesar cipher dialog.
+    Caesar(String),
+    /// Number of differing bytes and the first one, from comparing the selection with the clipboard.
+    CompareResult(usize, Option<usize>),
 }
 
 /// Messages emitted by the application and its widgets.
@@ -62,16 +137,59 @@ pub enum Action {
     SubscriptionChannel,
     ToggleContextPage(ContextPage),
     UpdateConfig(Config),
+    ExportSettings,
+    ExportSettingsTo(PathBuf),
+    ImportSettings,
+    ImportSettingsFrom(PathBuf),
     ChooseOpenFile,
-    OpenFile(PathBuf),
+    ChooseOpenFolder,
+    OpenFolder(PathBuf),
+    /// Writes the data to the path chosen for an export.
+    WriteFile(PathBuf, Vec<u8>),
+    /// Opens one tab per file.
+    OpenFile(Vec<PathBuf>),
     OpenRecentFile(usize),
+    /// Replaces a recent or pinned file with its persistent document portal path.
+    RecentDocument(PathBuf, PathBuf),
+    OpenPinnedFile(usize),
+    TogglePinFile,
+    ClearRecentFiles,
+    ChangeRecentFilesMax(usize),
+    ChangeScrollLines(usize),
+    ChangeGroupSize(usize),
+    ChangeEndian(usize),
+    SetTabEndian(Option<Endian>),
+    SetTabArchitecture(Option<crate::hex_view::arch::Architecture>),
+    /// Fills the selection with NOPs of the tab's architecture.
+    NopFill,
+    ToggleTabOptions,
+    TabBytesPerRow(usize),
+    TabGroupSize(usize),
+    TabBaseAddress(String),
+    /// Index into the font list, `None` resets the tab to the configured font.
+    SetTabFont(Option<usize>),
+    ToggleSmoothScrolling(bool),
+    ToggleReduceMotion(bool),
+    ToggleDoubleWidthText(bool),
+    ToggleGroupGaps(bool),
+    ToggleHexPane(bool),
+    ToggleTextPane(bool),
+    ToggleHighContrast(bool),
+    SystemReduceMotion(bool),
+    ChangeRowChecksum(usize),
+    ChangeNumericColumn(usize),
 
     QuitForce,
     TabActivate(Entity),
     TabClose(Option<Entity>),
     HexAction(Message),
+    /// A message for the hex view of a tab that isn't necessarily the active one.
+    TabHexAction(Entity, Message),
+    /// An entry of the touch context menu was chosen.
+    ContextMenuAction(menu_bar::MenuAction),
     PromptSaveChanges(Entity),
     TabCloseForce(Entity),
+    DuplicateView,
     Save(Option<Entity>),
     DialogCancel,
     SaveAll,
@@ -80,14 +198,119 @@ pub enum Action {
     ChangeSyntaxTheme(usize, bool),
     ChangeFont(usize),
     ChangeFontSize(usize),
+    ToggleLayer(Layer),
+    ChangeLayerColor(Layer, String),
+    ToggleStatusField(StatusField, bool),
 
     Find,
+    Escape,
     Undo,
     Redo,
     SearchPatternChanged(String),
+    SearchModeChanged(usize),
+    SearchHistorySelected(usize),
     FindNext,
     FindPrevious,
+    CountMatches,
+    ReplacePatternChanged(String),
+    ReplaceAllPreview,
+    /// Counts the matches of the tab at the index in the Replace All dialog, one tab per message.
+    ReplaceAllCount(usize),
+    ReplaceAllToggle(usize, bool),
+    ReplaceAllConfirm,
+    /// Offsets of all matches in a tab with the needle and data version they were searched for.
+    SearchMatches(segmented_button::Entity, (Vec<u8>, u64), Vec<usize>),
+    /// Sum, minimum and maximum of the selected bytes in a tab with the selection and data version
+    /// they were computed for.
+    SelectionStats(segmented_button::Entity, (Selection, u64), (u64, u8, u8)),
     SaveAs,
+    SaveAsChosen(segmented_button::Entity, PathBuf),
+    SaveAsPath(segmented_button::Entity, PathBuf),
+    DecodeProtobuf,
+    /// Copies the decoded field at the path, as JSON if set. An empty path copies all fields.
+    CopyProtoField(Vec<usize>, bool),
+    CompareWithClipboard,
+    CompareClipboardRead(Option<String>),
+    Paste,
+    PasteRead(Option<String>),
+    CheckClipboard,
+    ClipboardChecked(bool),
+    ColorInputChanged(String),
+    WatchInput(String),
+    AddWatch,
+    RemoveWatch(usize),
+    SymbolFilter(String),
+    ScanYara,
+    ScanYaraWith(PathBuf),
+    ShowXorSearch,
+    ShowCompareRanges,
+    CompareFirst(String),
+    CompareSecond(String),
+    CompareLength(String),
+    CompareRun,
+    ShowCaesar,
+    VerifyChecksum,
+    ChecksumVerified(sidecar::Verification),
+    /// Result of checking the checksum file of a tab after saving it.
+    SidecarChecked(segmented_button::Entity, sidecar::Verification),
+    RegenerateSidecar(segmented_button::Entity),
+    SidecarRegenerated(Result<(), String>),
+    CaesarShift(String),
+    CaesarConfirm,
+    XorCrib(String),
+    XorMaxKeyLen(String),
+    XorSearchRun,
+    XorSearchDone(Vec<crate::hex_view::xor::XorHit>),
+    /// Results of the YARA scan of a tab.
+    YaraScanned(segmented_button::Entity, Result<Vec<yara_scan::YaraMatch>, String>),
+    SaveSelectionAs,
+    SaveSelectionTo(PathBuf),
+    SaveSelectionProgress,
+    SelectionSaved(Result<(), String>),
+    WriteColor(inspector::ColorFormat),
+    ShowImagePreview,
+    ImagePreviewOffset(String),
+    ImagePreviewWidth(String),
+    ImagePreviewFormat(usize),
+    ImagePreviewZoom(usize),
+    ShowAudioPreview,
+    AudioSampleRate(usize),
+    AudioBitDepth(usize),
+    AudioChannels(usize),
+    PlayAudio,
+    ToggleTextView,
+    ChangeEncoding(usize),
+    SelectRun,
+    ShowProperties,
+    ShowJumpList,
+    ShowRecords,
+    ShowResize,
+    ResizeLength(String),
+    ResizeFill(String),
+    ResizeConfirm,
+    RecordsSize(String),
+    RecordsGoTo(String),
+    RecordsConfirm,
+    CopyAsHtml,
+    Revert,
+    RevertForce(Entity),
+    ShowExportDump,
+    ExportIps,
+    ExportBookmarks,
+    ImportBookmarks,
+    ImportBookmarksFrom(PathBuf),
+    ImportListing,
+    ImportListingFrom(PathBuf),
+    ExportDumpStart(String),
+    ExportDumpEnd(String),
+    ExportDumpPdf(bool),
+    ExportDumpConfirm,
+    JumpListFilter(String),
+    JumpTo(usize),
+    CopyOffset(bool),
+    SetOffsetBase,
+    ComputeHashes,
+    HashesComputed(Vec<(String, String)>),
 
     KeyPressed(keyboard::Modifiers, keyboard::Key),
     ModifiersChanged(keyboard::Modifiers),
@@ -99,7 +322,7 @@ impl Application for AppModel {
     type Executor = cosmic::executor::Default;
 
     /// Data that your application receives to its init method.
-    type Flags = ();
+    type Flags = Vec<crate::cli::Location>;
 
     /// Messages which the application and its widgets will emit.
     type Message = Action;
@@ -116,7 +339,7 @@ impl Application for AppModel {
     }
 
     /// Initializes the application with any given flags and startup commands.
-    fn init(core: Core, _flags: Self::Flags) -> (Self, Task<Self::Message>) {
+    fn init(core: Core, flags: Self::Flags) -> (Self, Task<Self::Message>) {
         let (config_handler, config) = match cosmic_config::Config::new(AppModel::APP_ID, crate::config::CONFIG_VERSION) {
             Ok(config_handler) => {
                 let config = Config::get_entry(&config_handler).unwrap_or_else(|(errs, config)| {
@@ -150,6 +373,7 @@ impl Application for AppModel {
             core,
             context_page: ContextPage::default(),
             tab_model: segmented_button::Model::builder().build(),
+            nav_model: widget::nav_bar::Model::default(),
             key_binds: key_binds::get_key_binds(),
             dialog_page_opt: None,
             // Optional configuration file for an application.
@@ -159,16 +383,57 @@ impl Application for AppModel {
             config_state,
             find: false,
             search_pattern: String::new(),
+            search_mode: SearchMode::Hex,
             find_search_id: widget::Id::unique(),
+            jump_filter_id: widget::Id::unique(),
             needle: Vec::new(),
+            match_count: None,
+            replace_pattern: String::new(),
+
+            protobuf_fields: None,
+            color_input: String::new(),
+            watches: Vec::new(),
+            watch_input: String::new(),
+            symbol_filter: String::new(),
+            yara_results: None,
+            save_selection: None,
+            image_preview: image_preview::ImagePreview::default(),
+            pcm_format: crate::hex_view::pcm::PcmFormat::default(),
+            audio_cache: Default::default(),
 
             modifiers: keyboard::Modifiers::default(),
+            window_title: String::new(),
+            clipboard_has_data: false,
         };
 
-        // Create a startup command that sets the window title.
-        let command = app.update_title();
+        set_monospace_font(&app.config.font_name);
+
+        if let Some(project_root) = app.config_state.project_root.clone() {
+            if project_root.is_dir() {
+                app.open_project(project_root);
+            }
+        }
 
-        (app, command)
+        let mut tasks = Vec::new();
+        for location in flags {
+            let Some(entity) = app.open_tab(location.path) else {
+                continue;
+            };
+            if let (Some(offset), Some(Tab::Editor(tab))) = (location.offset, app.tab_model.data_mut::<Tab>(entity)) {
+                let task = tab.hex_view.update(Message::MoveCaret(offset * 2));
+                tasks.push(task.map(|msg| cosmic::app::Message::App(Action::HexAction(msg))));
+            }
+        }
+
+        // Create a startup command that sets the window title.
+        tasks.push(Task::perform(accessibility::system_reduce_motion(), |reduce| {
+            cosmic::app::Message::App(Action::SystemReduceMotion(reduce))
+        }));
+        tasks.push(app.update_title());
+        tasks.push(app.focus_hex_view());
+        tasks.push(app.update(Action::CheckClipboard));
+
+        (app, Task::batch(tasks))
     }
 
     /// Elements to pack at the start of the header bar.
@@ -177,7 +442,11 @@ impl Application for AppModel {
     }
 
     fn nav_model(&self) -> Option<&widget::nav_bar::Model> {
-        None
+        if self.config_state.project_root.is_some() {
+            Some(&self.nav_model)
+        } else {
+            None
+        }
     }
 
     /// Display a context drawer if the context page is requested.
@@ -188,6 +457,21 @@ impl Application for AppModel {
         Some(match self.context_page {
             ContextPage::About => context_drawer::context_drawer(self.about(), Action::ToggleContextPage(ContextPage::About)).title(fl!("about")),
             ContextPage::Settings => context_drawer::context_drawer(self.settings(), Action::ToggleContextPage(ContextPage::Settings)).title(fl!("settings")),
+            ContextPage::Protobuf => {
+                context_drawer::context_drawer(self.protobuf_view(), Action::ToggleContextPage(ContextPage::Protobuf)).title(fl!("protobuf"))
+            }
+            ContextPage::ImagePreview => {
+                context_drawer::context_drawer(self.image_preview_view(), Action::ToggleContextPage(ContextPage::ImagePreview)).title(fl!("image-preview"))
+            }
+            ContextPage::AudioPreview => {
+                context_drawer::context_drawer(self.audio_preview_view(), Action::ToggleContextPage(ContextPage::AudioPreview)).title(fl!("audio-preview"))
+            }
+            ContextPage::Yara => context_drawer::context_drawer(self.yara_view(), Action::ToggleContextPage(ContextPage::Yara)).title(fl!("yara-results")),
+            ContextPage::Symbols => context_drawer::context_drawer(self.symbols_view(), Action::ToggleContextPage(ContextPage::Symbols)).title(fl!("symbols")),
+            ContextPage::Watches => context_drawer::context_drawer(self.watches_view(), Action::ToggleContextPage(ContextPage::Watches)).title(fl!("watches")),
+            ContextPage::Inspector => {
+                context_drawer::context_drawer(self.inspector(), Action::ToggleContextPage(ContextPage::Inspector)).title(fl!("data-inspector"))
+            }
         })
     }
 
@@ -240,7 +524,155 @@ impl Application for AppModel {
                     .tertiary_action(cancel_button);
                 Some(dialog.into())
             }
+
+            DialogPage::PromptRevert(entity) => {
+                let revert_button = widget::button::destructive(fl!("revert")).on_press(Action::RevertForce(*entity));
+                let cancel_button = widget::button::text(fl!("cancel")).on_press(Action::DialogCancel);
+                let dialog = widget::dialog::Dialog::new()
+                    .title(fl!("prompt-revert-title"))
+                    .body(fl!("prompt-revert"))
+                    .icon(widget::icon::from_name("dialog-warning-symbolic").size(64))
+                    .primary_action(revert_button)
+                    .secondary_action(cancel_button);
+                Some(dialog.into())
+            }
+
+            DialogPage::ReplaceAll(_, _, tabs) => {
+                let cosmic_theme::Spacing { space_xxs, .. } = self.core().system_theme().cosmic().spacing;
+                let mut column = widget::column::with_capacity(tabs.len()).spacing(space_xxs);
+                for (i, (entity, count, enabled)) in tabs.iter().enumerate() {
+                    if let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(*entity) {
+                        let count = count.map_or_else(|| "…".to_string(), |count| fl!("find-match-count", count = count));
+                        let label = format!("{} — {}", tab.title(), count);
+                        column = column.push(widget::checkbox(label, *enabled).on_toggle(move |enabled| Action::ReplaceAllToggle(i, enabled)));
+                    }
+                }
+                let replace_button = widget::button::suggested(fl!("replace-all")).on_press(Action::ReplaceAllConfirm);
+                let cancel_button = widget::button::text(fl!("cancel")).on_press(Action::DialogCancel);
+                let dialog = widget::dialog::Dialog::new()
+                    .title(fl!("replace-all-tabs"))
+                    .body(fl!("replace-all-tabs-body"))
+                    .control(column)
+                    .primary_action(replace_button)
+                    .secondary_action(cancel_button);
+                Some(dialog.into())
+            }
+
+            DialogPage::Properties(properties) => Some(self.properties_dialog(properties)),
+
+            DialogPage::CompareResult(count, first) => {
+                let body = match first {
+                    Some(first) => fl!("compare-differences", count = format_number(*count as u64), offset = format!("{:08X}", first)),
+                    None => fl!("compare-identical"),
+                };
+                let dialog = widget::dialog::Dialog::new()
+                    .title(fl!("compare-with-clipboard"))
+                    .body(body)
+                    .primary_action(widget::button::suggested(fl!("close")).on_press(Action::DialogCancel));
+                Some(dialog.into())
+            }
+
+            DialogPage::ExportDump(export) => Some(self.export_dump_dialog(export)),
+
+            DialogPage::Records(records) => Some(self.records_dialog(records)),
+            DialogPage::Resize(resize) => Some(self.resize_dialog(resize)),
+
+            DialogPage::XorSearch(search) => Some(self.xor_search_dialog(search)),
+            DialogPage::CompareRanges(compare) => Some(self.compare_ranges_dialog(compare)),
+
+            DialogPage::PromptOverwrite(entity, path) => {
+                let dialog = widget::dialog::Dialog::new()
+                    .title(fl!("prompt-overwrite-title"))
+                    .body(fl!("prompt-overwrite", file = path.display().to_string()))
+                    .icon(widget::icon::from_name("dialog-warning-symbolic").size(64))
+                    .primary_action(widget::button::destructive(fl!("overwrite")).on_press(Action::SaveAsPath(*entity, path.clone())))
+                    .secondary_action(widget::button::text(fl!("cancel")).on_press(Action::DialogCancel));
+                Some(dialog.into())
+            }
+
+            DialogPage::SaveFailed(path, error) => {
+                let dialog = widget::dialog::Dialog::new()
+                    .title(fl!("save-failed-title"))
+                    .body(fl!("save-failed", file = path.display().to_string(), error = error.as_str()))
+                    .icon(widget::icon::from_name("dialog-error-symbolic").size(64))
+                    .primary_action(widget::button::suggested(fl!("close")).on_press(Action::DialogCancel));
+                Some(dialog.into())
+            }
+
+            DialogPage::ChecksumResult(verification) => {
+                let body = match &verification.expected {
+                    None => fl!("checksum-unreadable", file = verification.sidecar.display().to_string()),
+                    Some(_) if verification.matches() => fl!("checksum-match", kind = verification.kind.name()),
+                    Some(expected) => fl!(
+                        "checksum-mismatch",
+                        kind = verification.kind.name(),
+                        expected = expected.as_str(),
+                        actual = verification.actual.as_str()
+                    ),
+                };
+                let icon = if verification.matches() {
+                    "emblem-ok-symbolic"
+                } else {
+                    "dialog-warning-symbolic"
+                };
+                let dialog = widget::dialog::Dialog::new()
+                    .title(fl!("verify-checksum-title"))
+                    .body(body)
+                    .icon(widget::icon::from_name(icon).size(64))
+                    .primary_action(widget::button::suggested(fl!("close")).on_press(Action::DialogCancel));
+                Some(dialog.into())
+            }
+
+            DialogPage::PromptRegenerateSidecar(entity) => {
+                let dialog = widget::dialog::Dialog::new()
+                    .title(fl!("regenerate-checksum-title"))
+                    .body(fl!("regenerate-checksum"))
+                    .primary_action(widget::button::suggested(fl!("regenerate-checksum-update")).on_press(Action::RegenerateSidecar(*entity)))
+                    .secondary_action(widget::button::text(fl!("cancel")).on_press(Action::DialogCancel));
+                Some(dialog.into())
+            }
+
+            DialogPage::Caesar(shift) => {
+                let shift_input = widget::text_input::text_input("", shift)
+                    .on_input(Action::CaesarShift)
+                    .on_submit(Action::CaesarConfirm);
+                let mut apply_button = widget::button::suggested(fl!("apply"));
+                if shift.trim().parse::<i32>().is_ok() {
+                    apply_button = apply_button.on_press(Action::CaesarConfirm);
+                }
+                let dialog = widget::dialog::Dialog::new()
+                    .title(fl!("transform-caesar-title"))
+                    .body(fl!("transform-caesar-body"))
+                    .control(widget::settings::item::builder(fl!("transform-caesar-shift")).control(shift_input))
+                    .primary_action(apply_button)
+                    .secondary_action(widget::button::text(fl!("cancel")).on_press(Action::DialogCancel));
+                Some(dialog.into())
+            }
+
+            DialogPage::JumpList(filter) => match self.tab_model.data::<Tab>(self.tab_model.active()) {
+                Some(Tab::Editor(tab)) => Some(self.jump_list_dialog(&tab.hex_view, filter)),
+                _ => None,
+            },
+        }
+    }
+
+    /// Recolors the hex views when the desktop theme changes, e.g. a new accent color.
+    fn system_theme_update(&mut self, _keys: &[&'static str], new_theme: &cosmic_theme::Theme) -> Task<Self::Message> {
+        if self.config.app_theme != AppTheme::System {
+            return self.update_config();
+        }
+        let entities: Vec<_> = self.tab_model.iter().collect();
+        for entity in entities {
+            if let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(entity) {
+                tab.set_palette(new_theme, &self.config);
+            }
         }
+        Task::none()
+    }
+
+    /// Switches between the dark and light syntax theme when the desktop does.
+    fn system_theme_mode_update(&mut self, _keys: &[&'static str], _new_theme: &cosmic_theme::ThemeMode) -> Task<Self::Message> {
+        self.update_config()
     }
 
     /// Describes the interface based on the current state of the application model.
@@ -269,26 +701,25 @@ impl Application for AppModel {
         match self.tab_model.data::<Tab>(tab_id) {
             Some(Tab::Editor(tab)) => {
                 //tab_column = tab_column.push(tab.hex_view.view());
-                let widget = HexViewWidget::show(&tab.hex_view);
-                let find_widget = widget.map(|msg| Action::HexAction(msg));
-
-                let data_u32 = if let Some(buffer) = tab.hex_view.buffer.as_ref() {
-                    buffer.get_u32(tab.hex_view.cursor.position)
+                let find_widget = if tab.text_view {
+                    self.text_view(tab)
                 } else {
-                    0
+                    let hex_view = widget::context_menu(
+                        HexViewWidget::show(&tab.hex_view).map(|msg| Action::HexAction(msg)),
+                        Some(menu_bar::context_menu(&self.key_binds)),
+                    );
+                    let mut popover = widget::popover(hex_view);
+                    if let Some(point) = tab.context_menu {
+                        popover = popover.popup(menu_bar::touch_context_menu()).position(widget::popover::Position::Point(point));
+                    } else if tab.options_open {
+                        popover = popover.popup(self.tab_options_view(tab)).position(widget::popover::Position::Center);
+                    }
+                    popover.into()
                 };
 
                 tab_column = tab_column.push(column::with_children(vec![
                     widget::row::with_children(vec![find_widget]).height(Length::Fill).into(),
-                    widget::row::with_children(vec![
-                        widget::text::body("Offset:").into(),
-                        widget::text::body(format!("{:08X}", tab.hex_view.cursor.position)).into(),
-                        widget::text::body("\t").into(),
-                        widget::text::body("uint:").into(),
-                        widget::text::body(format!("{}", data_u32)).into(),
-                    ])
-                    .height(Length::Shrink)
-                    .into(),
+                    self.status_bar(&tab.hex_view),
                 ]));
             }
             _ => {}
@@ -310,8 +741,17 @@ impl Application for AppModel {
                         .class(style::Button::Icon)
                         .into(),
                 );
+            let mode_selected = SearchMode::ALL.iter().position(|mode| *mode == self.search_mode);
+            let history = self.config_state.search_history.get(&self.search_mode).map(Vec::as_slice).unwrap_or_default();
             let find_widget = widget::row::with_children(vec![
+                widget::dropdown(&search_mode_names, mode_selected, Action::SearchModeChanged).into(),
                 find_input.into(),
+                widget::tooltip(
+                    widget::dropdown(history, None, Action::SearchHistorySelected),
+                    widget::text::body(fl!("find-history")),
+                    widget::tooltip::Position::Top,
+                )
+                .into(),
                 widget::tooltip(
                     button::custom(widget::icon::from_name("go-up-symbolic").size(16).handle().icon())
                         .on_press(Action::FindPrevious)
@@ -330,6 +770,8 @@ impl Application for AppModel {
                     widget::tooltip::Position::Top,
                 )
                 .into(),
+                widget::button::standard(fl!("find-count")).on_press(Action::CountMatches).into(),
+                widget::text::body(self.match_count.map(|count| fl!("find-match-count", count = count)).unwrap_or_default()).into(),
                 widget::horizontal_space().into(),
                 button::custom(widget::icon::from_name("window-close-symbolic").size(16).handle().icon())
                     .on_press(Action::Find)
@@ -341,7 +783,18 @@ impl Application for AppModel {
             .padding(space_xxs)
             .spacing(space_xxs);
 
-            let column = widget::column::with_capacity(3).push(find_widget);
+            let replace_widget = widget::row::with_children(vec![
+                widget::text_input::text_input(fl!("replace-placeholder"), &self.replace_pattern)
+                    .on_input(Action::ReplacePatternChanged)
+                    .width(Length::Fixed(320.0))
+                    .into(),
+                widget::button::standard(fl!("replace-all-tabs")).on_press(Action::ReplaceAllPreview).into(),
+            ])
+            .align_y(Alignment::Center)
+            .padding(space_xxs)
+            .spacing(space_xxs);
+
+            let column = widget::column::with_capacity(3).push(find_widget).push(replace_widget);
             tab_column = tab_column.push(widget::layer_container(column).layer(cosmic_theme::Layer::Primary));
         }
 
@@ -360,8 +813,20 @@ impl Application for AppModel {
     fn subscription(&self) -> Subscription<Self::Message> {
         struct MySubscription;
 
-        Subscription::batch(vec![
+        let mut subscriptions = vec![
             event::listen_with(|event, status, _window_id| match event {
+                // Escape is also handled when a text input captured it, so the find bar can be closed.
+                event::Event::Keyboard(keyboard::Event::KeyPressed {
+                    key: keyboard::Key::Named(keyboard::key::Named::Escape),
+                    ..
+                }) => Some(Action::Escape),
+                // The hex view copies by itself, so look at the clipboard again for Paste.
+                event::Event::Keyboard(keyboard::Event::KeyPressed {
+                    key: keyboard::Key::Character(ref c),
+                    modifiers,
+                    ..
+                }) if modifiers.command() && c.as_str() == "c" && status == event::Status::Captured => Some(Action::CheckClipboard),
+                event::Event::Window(window::Event::Focused) => Some(Action::CheckClipboard),
                 event::Event::Keyboard(keyboard::Event::KeyPressed { modifiers, key, .. }) => match status {
                     event::Status::Ignored => Some(Action::KeyPressed(modifiers, key)),
                     event::Status::Captured => None,
@@ -382,7 +847,31 @@ impl Application for AppModel {
             self.core()
                 .watch_config::<Config>(Self::APP_ID)
                 .map(|update| Action::UpdateConfig(update.config)),
-        ])
+        ];
+
+        // Keep scrolling while a drag selection is held outside the viewport.
+        if let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(self.tab_model.active()) {
+            if tab.hex_view.drag_scroll != 0.0 {
+                subscriptions.push(cosmic::iced::time::every(std::time::Duration::from_millis(30)).map(|_| Action::HexAction(Message::AutoScroll)));
+            }
+            if tab.hex_view.scroll_animation.is_some() {
+                subscriptions.push(cosmic::iced::time::every(std::time::Duration::from_millis(16)).map(|_| Action::HexAction(Message::AnimateScroll)));
+            }
+        }
+
+        // Blink the caret of the focused hex view.
+        if let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(self.tab_model.active()) {
+            if tab.hex_view.cursor.focus && !accessibility::reduce_motion(&self.config) {
+                subscriptions.push(cosmic::iced::time::every(std::time::Duration::from_millis(530)).map(|_| Action::HexAction(Message::BlinkCaret)));
+            }
+        }
+
+        // Refresh the progress in the status bar while a selection is written.
+        if self.save_selection.is_some() {
+            subscriptions.push(cosmic::iced::time::every(std::time::Duration::from_millis(100)).map(|_| Action::SaveSelectionProgress));
+        }
+
+        Subscription::batch(subscriptions)
     }
 
     /// Handles messages emitted by the application and its widgets.
@@ -391,21 +880,232 @@ impl Application for AppModel {
     /// on the application's async runtime.
     fn update(&mut self, message: Self::Message) -> Task<Self::Message> {
         match message {
-            Action::OpenFile(path) => {
-                self.open_tab(path);
+            Action::OpenFile(paths) => {
+                let mut tasks = Vec::new();
+                for path in paths {
+                    let entity = self.open_tab(path);
+                    if let (true, Some(Tab::Editor(tab))) = (sandbox::is_sandboxed(), entity.and_then(|entity| self.tab_model.data::<Tab>(entity))) {
+                        let path = tab.hex_view.path.clone();
+                        tasks.push(Task::perform(sandbox::persist(path.clone()), |document| match document {
+                            Some(document) => cosmic::app::Message::App(Action::RecentDocument(path, document)),
+                            None => cosmic::app::Message::None,
+                        }));
+                    }
+                }
+                tasks.push(self.update_title());
+                tasks.push(self.focus_hex_view());
+                return Task::batch(tasks);
+            }
+
+            Action::RecentDocument(path, document) => {
+                for file in self.config_state.recent_files.iter_mut().chain(self.config_state.pinned_files.iter_mut()) {
+                    if *file == path {
+                        *file = document.clone();
+                    }
+                }
+                self.save_config_state();
             }
 
             Action::ChooseOpenFile => {
-                if let Some(path) = rfd::FileDialog::new().pick_file() {
-                    return self.update(Action::OpenFile(path));
+                return file_chooser::open_many(rfd::AsyncFileDialog::new(), Action::OpenFile);
+            }
+            Action::ChooseOpenFolder => {
+                return file_chooser::folder(rfd::AsyncFileDialog::new(), Action::OpenFolder);
+            }
+            Action::OpenFolder(path) => {
+                self.open_project(path);
+            }
+
+            Action::WriteFile(path, data) => {
+                if let Err(err) = fs::write(&path, data) {
+                    log::error!("failed to write {:?}: {}", path, err);
                 }
             }
+
             Action::OpenRecentFile(i) => {
                 if let Some(path) = self.config_state.recent_files.get(i).cloned() {
-                    return self.update(Action::OpenFile(path));
+                    return self.update(Action::OpenFile(vec![path]));
+                }
+            }
+
+            Action::OpenPinnedFile(i) => {
+                if let Some(path) = self.config_state.pinned_files.get(i).cloned() {
+                    return self.update(Action::OpenFile(vec![path]));
+                }
+            }
+
+            Action::TogglePinFile => {
+                if let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(self.tab_model.active()) {
+                    let path = tab.hex_view.path.clone();
+                    if self.config_state.pinned_files.contains(&path) {
+                        self.config_state.pinned_files.retain(|x| x != &path);
+                        self.add_recent_file(path);
+                    } else {
+                        self.config_state.recent_files.retain(|x| x != &path);
+                        self.config_state.pinned_files.push(path);
+                    }
+                    self.save_config_state();
+                }
+            }
+
+            Action::ClearRecentFiles => {
+                self.config_state.recent_files.clear();
+                self.save_config_state();
+            }
+
+            Action::ChangeRecentFilesMax(index) => {
+                if let Some(max) = recent_files_max_values.get(index) {
+                    self.config.recent_files_max = *max;
+                    self.config_state.recent_files.truncate(*max);
+                    self.save_config_state();
+                    return self.save_config();
                 }
             }
 
+            Action::ChangeScrollLines(index) => {
+                if let Some(lines) = scroll_lines_values.get(index) {
+                    self.config.scroll_lines = *lines;
+                    return self.save_config();
+                }
+            }
+
+            Action::ChangeEndian(index) => {
+                self.config.endian = if index == 1 { Endian::Big } else { Endian::Little };
+                return self.save_config();
+            }
+
+            Action::SetTabEndian(endian) => {
+                if let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(self.tab_model.active()) {
+                    tab.options.endian = endian;
+                }
+                let task = self.change_tab_options();
+                self.needle = self.get_pattern_needle();
+                return task;
+            }
+
+            Action::SetTabArchitecture(architecture) => {
+                if let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(self.tab_model.active()) {
+                    tab.options.architecture = architecture;
+                }
+                return self.change_tab_options();
+            }
+
+            Action::NopFill => {
+                if let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(self.tab_model.active()) {
+                    if let Some(architecture) = tab.options.architecture {
+                        return self.update(Action::HexAction(Message::FillSelection(architecture.nop().to_vec())));
+                    }
+                }
+            }
+
+            Action::ToggleTabOptions => {
+                if let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(self.tab_model.active()) {
+                    tab.options_open = !tab.options_open;
+                    tab.base_address_input = format!("0x{:X}", tab.options.base_address);
+                }
+            }
+
+            Action::TabBytesPerRow(index) => {
+                if let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(self.tab_model.active()) {
+                    tab.options.bytes_per_row = index.checked_sub(1).and_then(|index| tab_options::bytes_per_row_values.get(index)).copied();
+                }
+                return self.change_tab_options();
+            }
+
+            Action::TabGroupSize(index) => {
+                if let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(self.tab_model.active()) {
+                    tab.options.group_size = index.checked_sub(1).and_then(|index| tab_options::tab_group_size_values.get(index)).copied();
+                }
+                return self.change_tab_options();
+            }
+
+            Action::TabBaseAddress(value) => {
+                if let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(self.tab_model.active()) {
+                    let base_address = if value.trim().is_empty() {
+                        Some(0)
+                    } else {
+                        crate::hex_view::parse_offset(&value).map(|address| address as u64)
+                    };
+                    tab.base_address_input = value;
+                    match base_address {
+                        Some(base_address) => tab.options.base_address = base_address,
+                        None => return Task::none(),
+                    }
+                }
+                return self.change_tab_options();
+            }
+
+            Action::SetTabFont(index) => {
+                if let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(self.tab_model.active()) {
+                    tab.font_override = index.and_then(|index| font_names.get(index)).map(String::as_str);
+                    tab.set_config(&self.config);
+                }
+            }
+
+            Action::ChangeGroupSize(index) => {
+                if let Some(group_size) = group_size_values.get(index) {
+                    self.config.group_size = *group_size;
+                    return self.save_config();
+                }
+            }
+
+            Action::ChangeRowChecksum(index) => {
+                if let Some(row_checksum) = RowChecksum::ALL.get(index) {
+                    self.config.row_checksum = *row_checksum;
+                    return self.save_config();
+                }
+            }
+
+            Action::ChangeNumericColumn(index) => {
+                if let Some(numeric_column) = NumericColumn::ALL.get(index) {
+                    self.config.numeric_column = *numeric_column;
+                    return self.save_config();
+                }
+            }
+
+            Action::ToggleReduceMotion(reduce_motion) => {
+                self.config.reduce_motion = reduce_motion;
+                return self.save_config();
+            }
+
+            Action::ToggleDoubleWidthText(double_width_text) => {
+                self.config.double_width_text = double_width_text;
+                return self.save_config();
+            }
+
+            Action::ToggleGroupGaps(group_gaps) => {
+                self.config.group_gaps = group_gaps;
+                return self.save_config();
+            }
+
+            // One of the panes always stays, hiding the last one shows the other.
+            Action::ToggleHexPane(show) => {
+                self.config.show_hex_pane = show;
+                self.config.show_text_pane |= !show;
+                return self.save_config();
+            }
+
+            Action::ToggleTextPane(show) => {
+                self.config.show_text_pane = show;
+                self.config.show_hex_pane |= !show;
+                return self.save_config();
+            }
+
+            Action::ToggleHighContrast(high_contrast) => {
+                self.config.high_contrast = high_contrast;
+                return self.save_config();
+            }
+
+            Action::SystemReduceMotion(reduce) => {
+                accessibility::set_system_reduce_motion(reduce);
+                return self.update_config();
+            }
+
+            Action::ToggleSmoothScrolling(smooth_scrolling) => {
+                self.config.smooth_scrolling = smooth_scrolling;
+                return self.save_config();
+            }
+
             Action::OpenRepositoryUrl => {
                 _ = open::that_detached(REPOSITORY);
             }
@@ -431,17 +1131,63 @@ impl Application for AppModel {
                 self.config = config;
             }
 
+            Action::ExportSettings => {
+                let dialog = rfd::AsyncFileDialog::new()
+                    .add_filter(fl!("settings-file"), &["json"])
+                    .set_file_name("cosmic-hex-settings.json");
+                return file_chooser::save(dialog, Action::ExportSettingsTo);
+            }
+
+            Action::ExportSettingsTo(path) => {
+                let result = serde_json::to_string_pretty(&self.config)
+                    .map_err(|err| err.to_string())
+                    .and_then(|json| fs::write(&path, json).map_err(|err| err.to_string()));
+                if let Err(err) = result {
+                    log::error!("failed to export settings to {:?}: {}", path, err);
+                }
+            }
+
+            Action::ImportSettings => {
+                let dialog = rfd::AsyncFileDialog::new().add_filter(fl!("settings-file"), &["json"]);
+                return file_chooser::open(dialog, Action::ImportSettingsFrom);
+            }
+
+            Action::ImportSettingsFrom(path) => {
+                let config = fs::read_to_string(&path)
+                    .map_err(|err| err.to_string())
+                    .and_then(|json| serde_json::from_str::<Config>(&json).map_err(|err| err.to_string()));
+                match config {
+                    Ok(config) => {
+                        set_monospace_font(&config.font_name);
+                        self.config = config;
+                        self.config_state.recent_files.truncate(self.config.recent_files_max);
+                        return self.save_config();
+                    }
+                    Err(err) => log::error!("failed to import settings from {:?}: {}", path, err),
+                }
+            }
+
             Action::QuitForce => {
                 process::exit(0);
             }
 
             Action::TabActivate(entity) => {
                 self.tab_model.activate(entity);
-                return self.update_tab();
+                let restore = match self.tab_model.data_mut::<Tab>(entity) {
+                    Some(Tab::Editor(tab)) => tab.hex_view.restore_scroll().map(|msg| cosmic::app::Message::App(Action::HexAction(msg))),
+                    _ => Task::none(),
+                };
+                return Task::batch([self.update_tab(), restore, self.focus_hex_view()]);
             }
 
             Action::TabClose(entity_opt) => {
                 let entity = entity_opt.unwrap_or(self.tab_model.active());
+                // Edits stay in the remaining views of a duplicated view, nothing needs saving. Only
+                // the view holding the data hands it over, the others are just closed.
+                if let Some(view) = self.linked_views(entity).first() {
+                    self.move_document(entity, *view);
+                    return self.update(Action::TabCloseForce(entity));
+                }
                 let tab = self.tab_model.data_mut::<Tab>(entity);
                 if let Some(tab) = tab {
                     if tab.is_dirty() {
@@ -460,6 +1206,30 @@ impl Application for AppModel {
                     }
                 }
                 self.tab_model.remove(entity);
+                return Task::batch([self.update_tab(), self.focus_hex_view()]);
+            }
+
+            Action::DuplicateView => {
+                let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(self.tab_model.active()) else {
+                    return Task::none();
+                };
+                let mut view = tab::EditorTab::new(tab.hex_view.path.clone(), DataBuffer::new(Vec::new()));
+                view.hex_view.buffer = None;
+                view.hex_view.cursor.position = tab.hex_view.cursor.position;
+                view.hex_view.encoding = tab.hex_view.encoding;
+                view.hex_view.record_size = tab.hex_view.record_size;
+                view.options = tab.options.clone();
+                view.font_override = tab.font_override;
+                view.set_config(&self.config);
+                let text = self.tab_model.text(self.tab_model.active()).unwrap_or_default().to_string();
+                let icon = view.icon(16);
+                self.tab_model
+                    .insert()
+                    .text(text)
+                    .icon(icon)
+                    .data::<Tab>(Tab::Editor(view))
+                    .closable()
+                    .activate();
                 return self.update_tab();
             }
 
@@ -469,85 +1239,243 @@ impl Application for AppModel {
 
             Action::HexAction(msg) => {
                 let tab_id = self.tab_model.active();
+                match msg {
+                    Message::DirtyChanged(dirty) => {
+                        self.set_tab_dirty(tab_id, dirty);
+                        return self.update_title();
+                    }
+                    Message::Zoom(steps) => {
+                        let min = *font_sizes.first().unwrap();
+                        let max = *font_sizes.last().unwrap();
+                        let font_size = (self.config.font_size as i32 + steps).clamp(min as i32, max as i32) as usize;
+                        return self.update(Action::ChangeFontSize(font_size));
+                    }
+                    Message::LongPress(point) => {
+                        if let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(tab_id) {
+                            tab.context_menu = Some(point);
+                        }
+                        return Task::none();
+                    }
+                    _ => {}
+                }
                 match self.tab_model.data_mut::<Tab>(tab_id) {
                     Some(Tab::Editor(tab)) => {
-                        return tab.hex_view.update(msg).map(|t| cosmic::app::Message::App(Action::HexAction(t)));
+                        tab.context_menu = None;
+                        let task = tab.hex_view.update(msg).map(|t| cosmic::app::Message::App(Action::HexAction(t)));
+                        return Task::batch([task, self.update_title(), self.update_selection_stats()]);
                     }
                     _ => {}
                 }
             }
 
+            Action::TabHexAction(entity, msg) => {
+                if entity == self.tab_model.active() {
+                    return self.update(Action::HexAction(msg));
+                }
+                if let Message::DirtyChanged(dirty) = msg {
+                    self.set_tab_dirty(entity, dirty);
+                    return self.update_title();
+                }
+                if let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(entity) {
+                    return tab
+                        .hex_view
+                        .update(msg)
+                        .map(move |msg| cosmic::app::Message::App(Action::TabHexAction(entity, msg)));
+                }
+            }
+
+            Action::ContextMenuAction(action) => {
+                if let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(self.tab_model.active()) {
+                    tab.context_menu = None;
+                }
+                return self.update(action.message());
+            }
+
             Action::Save(entity_opt) => {
                 let tab_id = entity_opt.unwrap_or(self.tab_model.active());
+                let mut check = Task::none();
                 match self.tab_model.data_mut::<Tab>(tab_id) {
                     Some(Tab::Editor(tab)) => {
+                        let was_dirty = tab.hex_view.is_dirty();
                         if let Err(err) = tab.hex_view.save() {
                             log::error!("failed to save tab: {}", err);
+                        } else {
+                            tab.sidecar = sidecar::find(&tab.hex_view.path);
+                            if was_dirty {
+                                check = Self::verify_sidecar(tab, move |verification| Action::SidecarChecked(tab_id, verification));
+                            }
                         }
                     }
                     _ => {}
                 }
+                return Task::batch([self.sync_dirty(tab_id), check]);
             }
 
-            Action::SaveAs => {
-                let tab_id = self.tab_model.active();
-                match self.tab_model.data_mut::<Tab>(tab_id) {
-                    Some(Tab::Editor(tab)) => {
-                        if let Some(file) = rfd::FileDialog::new().save_file() {
-                            tab.hex_view.path = file.clone();
-                            if let Err(err) = tab.hex_view.save() {
-                                log::error!("failed to save tab: {}", err);
-                            }
-                        }
-                    }
-                    _ => {}
+            Action::VerifyChecksum => {
+                let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(self.tab_model.active()) else {
+                    return Task::none();
+                };
+                return Self::verify_sidecar(tab, Action::ChecksumVerified);
+            }
+
+            Action::ChecksumVerified(verification) => {
+                self.dialog_page_opt = Some(DialogPage::ChecksumResult(verification));
+            }
+
+            Action::SidecarChecked(entity, verification) => {
+                // Offers to update the checksum file only if the saved data doesn't match it anymore.
+                if !verification.matches() && self.dialog_page_opt.is_none() {
+                    self.dialog_page_opt = Some(DialogPage::PromptRegenerateSidecar(entity));
                 }
             }
 
-            Action::SaveAll => {
-                let entities: Vec<_> = self.tab_model.iter().collect();
-                for entity in entities {
-                    if let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(entity) {
-                        if tab.hex_view.is_dirty() {
-                            let _ = tab.hex_view.save();
-                        }
+            Action::RegenerateSidecar(entity) => {
+                self.dialog_page_opt = None;
+                if let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(entity) {
+                    if let (Some(buffer), Some((sidecar, kind))) = (&tab.hex_view.buffer, tab.sidecar.clone()) {
+                        let path = tab.hex_view.path.clone();
+                        let data = buffer.data.clone();
+                        return Task::perform(
+                            async move {
+                                tokio::task::spawn_blocking(move || {
+                                    sidecar::regenerate(&sidecar, kind, &path, &data).map_err(|err| format!("{:?}: {}", sidecar, err))
+                                })
+                                .await
+                                .unwrap_or_else(|err| Err(err.to_string()))
+                            },
+                            |result| cosmic::app::Message::App(Action::SidecarRegenerated(result)),
+                        );
                     }
                 }
             }
 
-            Action::DialogCancel => {
-                self.dialog_page_opt = None;
+            Action::SidecarRegenerated(result) => {
+                if let Err(err) = result {
+                    log::error!("failed to update {}", err);
+                }
             }
 
-            Action::ChangeTheme(app_theme) => {
-                self.config.app_theme = app_theme;
-                return self.save_config();
+            Action::SaveAs => {
+                let tab_id = self.tab_model.active();
+                let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(tab_id) else {
+                    return Task::none();
+                };
+                let mut dialog = rfd::AsyncFileDialog::new();
+                if let Some(parent) = tab.hex_view.path.parent() {
+                    dialog = dialog.set_directory(parent);
+                }
+                if let Some(name) = tab.hex_view.path.file_name() {
+                    dialog = dialog.set_file_name(name.to_string_lossy());
+                }
+                return file_chooser::save(dialog, move |file| Action::SaveAsChosen(tab_id, file));
             }
 
-            Action::ChangeFont(index) => {
-                match font_names.get(index) {
-                    Some(font_name) => {
-                        if font_name != &self.config.font_name {
-                            // Update font name from config
-                            {
-                                let mut font_system = font_system().write().unwrap();
-                                font_system.raw().db_mut().set_monospace_family(font_name);
-                            }
-                            self.config.font_name = font_name.to_string();
-                            return self.save_config();
-                        }
+            Action::SaveAsChosen(tab_id, file) => {
+                let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(tab_id) else {
+                    return Task::none();
+                };
+                if file.exists() && file != tab.hex_view.path {
+                    self.dialog_page_opt = Some(DialogPage::PromptOverwrite(tab_id, file));
+                } else {
+                    return self.update(Action::SaveAsPath(tab_id, file));
+                }
+            }
+
+            Action::SaveAsPath(tab_id, file) => {
+                self.dialog_page_opt = None;
+                let views = self.linked_views(tab_id);
+                let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(tab_id) else {
+                    return Task::none();
+                };
+                if let Err(err) = tab.hex_view.save_as(file.clone()) {
+                    log::error!("failed to save tab: {}", err);
+                    self.dialog_page_opt = Some(DialogPage::SaveFailed(file, err.to_string()));
+                    return Task::none();
+                }
+                tab.sidecar = sidecar::find(&tab.hex_view.path);
+                let title = tab.title();
+                let path = tab.hex_view.path.clone();
+                let sidecar = tab.sidecar.clone();
+                self.tab_model.text_set(tab_id, title.clone());
+                for view in views {
+                    if let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(view) {
+                        tab.hex_view.path = path.clone();
+                        tab.sidecar = sidecar.clone();
                     }
-                    None => {
-                        log::warn!("failed to find font with index {}", index);
+                    self.tab_model.text_set(view, title.clone());
+                }
+                self.add_recent_file(path);
+                self.save_config_state();
+                return self.sync_dirty(tab_id);
+            }
+
+            Action::SaveAll => {
+                let entities: Vec<_> = self.tab_model.iter().collect();
+                for entity in entities {
+                    if let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(entity) {
+                        if tab.hex_view.is_dirty() {
+                            let _ = tab.hex_view.save();
+                        }
                     }
+                    let _ = self.sync_dirty(entity);
                 }
+                return self.update_title();
+            }
+
+            Action::DialogCancel => {
+                self.dialog_page_opt = None;
             }
 
+            Action::ChangeTheme(app_theme) => {
+                self.config.app_theme = app_theme;
+                return self.save_config();
+            }
+
+            Action::ChangeFont(index) => match font_names.get(index) {
+                Some(font_name) => {
+                    if font_name != &self.config.font_name {
+                        set_monospace_font(font_name);
+                        self.config.font_name = font_name.to_string();
+                        return self.save_config();
+                    }
+                }
+                None => {
+                    log::warn!("failed to find font with index {}", index);
+                }
+            },
+
             Action::ChangeFontSize(font_size) => {
                 self.config.font_size = font_size;
                 return self.save_config();
             }
 
+            Action::ToggleLayer(layer) => {
+                if let Some(index) = self.config.hidden_layers.iter().position(|hidden| *hidden == layer) {
+                    self.config.hidden_layers.remove(index);
+                } else {
+                    self.config.hidden_layers.push(layer);
+                }
+                return self.save_config();
+            }
+
+            Action::ChangeLayerColor(layer, color) => {
+                if color.trim().is_empty() {
+                    self.config.layer_colors.remove(&layer);
+                } else {
+                    self.config.layer_colors.insert(layer, color);
+                }
+                return self.save_config();
+            }
+
+            Action::ToggleStatusField(field, enabled) => {
+                self.config.status_fields.retain(|f| *f != field);
+                if enabled {
+                    self.config.status_fields.push(field);
+                    self.config.status_fields.sort_by_key(|f| StatusField::ALL.iter().position(|a| a == f));
+                }
+                return self.save_config();
+            }
+
             Action::ChangeSyntaxTheme(index, dark) => match theme_names.get(index) {
                 Some(theme_name) => {
                     if dark {
@@ -564,6 +1492,34 @@ impl Application for AppModel {
 
             Action::Find => {
                 self.find = !self.find;
+                if !self.find {
+                    return self.focus_hex_view();
+                }
+                if let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(self.tab_model.active()) {
+                    // Large and block selections are left out, they make no useful pattern.
+                    let selected = tab
+                        .hex_view
+                        .selection
+                        .filter(|selection| selection.row_width.is_none() && selection.len() <= MAX_FIND_PREFILL)
+                        .zip(tab.hex_view.buffer.as_ref());
+                    if let Some((selection, buffer)) = selected {
+                        let pattern: String = buffer.data[selection.range()].iter().map(|b| format!("{:02X}", b)).collect();
+                        self.search_mode = SearchMode::Hex;
+                        self.search_pattern = pattern;
+                        self.needle = self.get_pattern_needle();
+                        self.match_count = None;
+                    }
+                }
+                return Task::batch([
+                    widget::text_input::focus(self.find_search_id.clone()),
+                    widget::text_input::select_all(self.find_search_id.clone()),
+                ]);
+            }
+
+            Action::Escape => {
+                if self.find {
+                    return self.update(Action::Find);
+                }
             }
 
             Action::Undo => {
@@ -571,7 +1527,7 @@ impl Application for AppModel {
                 match self.tab_model.data_mut::<Tab>(tab_id) {
                     Some(Tab::Editor(tab)) => {
                         let _ = tab.hex_view.undo();
-                        return self.update_tab();
+                        return Task::batch([self.update_tab(), self.sync_dirty(tab_id)]);
                     }
                     _ => {}
                 }
@@ -582,7 +1538,7 @@ impl Application for AppModel {
                 match self.tab_model.data_mut::<Tab>(tab_id) {
                     Some(Tab::Editor(tab)) => {
                         let _ = tab.hex_view.redo();
-                        return self.update_tab();
+                        return Task::batch([self.update_tab(), self.sync_dirty(tab_id)]);
                     }
                     _ => {}
                 }
@@ -591,31 +1547,837 @@ impl Application for AppModel {
             Action::SearchPatternChanged(value) => {
                 self.search_pattern = value;
                 self.needle = self.get_pattern_needle();
+                self.match_count = None;
+            }
+
+            Action::SearchModeChanged(index) => {
+                if let Some(mode) = SearchMode::ALL.get(index) {
+                    self.search_mode = *mode;
+                    self.needle = self.get_pattern_needle();
+                    self.match_count = None;
+                }
+            }
+
+            Action::SearchHistorySelected(index) => {
+                if let Some(pattern) = self
+                    .config_state
+                    .search_history
+                    .get(&self.search_mode)
+                    .and_then(|history| history.get(index))
+                    .cloned()
+                {
+                    return self.update(Action::SearchPatternChanged(pattern));
+                }
             }
 
             Action::FindNext => {
+                self.add_search_history();
                 let tab_id = self.tab_model.active();
 
                 match self.tab_model.data_mut::<Tab>(tab_id) {
                     Some(Tab::Editor(tab)) => {
                         tab.hex_view.find_next(&self.needle);
-                        return self.update_tab();
+                        return Task::batch([self.update_tab(), self.find_all_matches()]);
                     }
                     _ => {}
                 }
             }
 
             Action::FindPrevious => {
+                self.add_search_history();
                 let tab_id = self.tab_model.active();
                 match self.tab_model.data_mut::<Tab>(tab_id) {
                     Some(Tab::Editor(tab)) => {
                         tab.hex_view.find_previous(&self.needle);
-                        return self.update_tab();
+                        return Task::batch([self.update_tab(), self.find_all_matches()]);
                     }
                     _ => {}
                 }
             }
 
+            Action::CopyProtoField(path, as_json) => {
+                let (Some(fields), Some(Tab::Editor(tab))) = (&self.protobuf_fields, self.tab_model.data::<Tab>(self.tab_model.active())) else {
+                    return Task::none();
+                };
+                let Some(buffer) = &tab.hex_view.buffer else {
+                    return Task::none();
+                };
+                let text = if path.is_empty() {
+                    serde_json::to_string_pretty(&protobuf::fields_json(fields, &buffer.data)).unwrap_or_default()
+                } else if let Some(field) = protobuf::find_field(fields, &path) {
+                    if as_json {
+                        serde_json::to_string_pretty(&protobuf::field_json(field, &buffer.data)).unwrap_or_default()
+                    } else {
+                        protobuf::field_value(field, &buffer.data)
+                    }
+                } else {
+                    return Task::none();
+                };
+                self.clipboard_has_data = true;
+                return cosmic::iced::clipboard::write(text);
+            }
+
+            Action::CompareWithClipboard => {
+                return cosmic::iced::clipboard::read().map(|text| cosmic::app::Message::App(Action::CompareClipboardRead(text)));
+            }
+
+            Action::Paste => {
+                return cosmic::iced::clipboard::read().map(|text| cosmic::app::Message::App(Action::PasteRead(text)));
+            }
+
+            Action::PasteRead(text) => {
+                self.clipboard_has_data = text.as_ref().is_some_and(|text| !text.is_empty());
+                if let Some(text) = text {
+                    return self.update(Action::HexAction(Message::Paste(text)));
+                }
+            }
+
+            Action::CheckClipboard => {
+                return cosmic::iced::clipboard::read()
+                    .map(|text| cosmic::app::Message::App(Action::ClipboardChecked(text.is_some_and(|text| !text.is_empty()))));
+            }
+
+            Action::ClipboardChecked(has_data) => {
+                self.clipboard_has_data = has_data;
+            }
+
+            Action::CompareClipboardRead(text) => {
+                let Some(text) = text else {
+                    return Task::none();
+                };
+                if let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(self.tab_model.active()) {
+                    let (Some(selection), Some(buffer)) = (tab.hex_view.selection, &tab.hex_view.buffer) else {
+                        return Task::none();
+                    };
+                    let clipboard = crate::hex_view::parse_hex(&text).unwrap_or_else(|| text.into_bytes());
+                    let differences = crate::hex_view::diff_offsets(&buffer.data[selection.range()], &clipboard);
+                    let len = buffer.len();
+                    tab.hex_view.matches_for = None;
+                    tab.hex_view.matches = differences
+                        .iter()
+                        .map(|offset| selection.start() + offset)
+                        .filter(|offset| *offset < len)
+                        .collect();
+                    let diff = tab.hex_view.matches.iter().map(|offset| *offset..offset + 1).collect();
+                    tab.hex_view.highlights.set(Layer::Diff, diff);
+                    tab.hex_view.redraw();
+                    let first = differences.first().map(|offset| selection.start() + offset);
+                    self.dialog_page_opt = Some(DialogPage::CompareResult(differences.len(), first));
+                    if let Some(first) = first {
+                        let task = tab
+                            .hex_view
+                            .update(Message::MoveCaret(first * 2))
+                            .map(|msg| cosmic::app::Message::App(Action::HexAction(msg)));
+                        return Task::batch([task, self.update_tab()]);
+                    }
+                }
+            }
+
+            Action::DecodeProtobuf => {
+                if let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(self.tab_model.active()) {
+                    if let Some(buffer) = &tab.hex_view.buffer {
+                        let range = tab.hex_view.selection.map_or(0..buffer.len(), |selection| selection.range());
+                        let base = range.start;
+                        self.protobuf_fields = crate::hex_view::protobuf::decode(&buffer.data[range], base);
+                    }
+                    let fields = self.protobuf_fields.as_deref().map(crate::hex_view::protobuf::leaf_ranges);
+                    tab.hex_view.highlights.set(Layer::Template, fields.unwrap_or_default());
+                    tab.hex_view.redraw();
+                }
+                self.context_page = ContextPage::Protobuf;
+                self.core.window.show_context = true;
+            }
+
+            Action::ColorInputChanged(value) => {
+                self.color_input = value;
+            }
+
+            Action::WatchInput(value) => {
+                self.watch_input = value;
+            }
+
+            Action::AddWatch => {
+                if let Some(watch) = watches::Watch::parse(&self.watch_input) {
+                    self.watches.push(watch);
+                    self.watch_input.clear();
+                }
+            }
+
+            Action::ShowCaesar => {
+                self.dialog_page_opt = Some(DialogPage::Caesar("3".to_string()));
+            }
+
+            Action::CaesarShift(value) => {
+                if let Some(DialogPage::Caesar(shift)) = &mut self.dialog_page_opt {
+                    *shift = value;
+                }
+            }
+
+            Action::CaesarConfirm => {
+                let Some(DialogPage::Caesar(shift)) = &self.dialog_page_opt else {
+                    return Task::none();
+                };
+                let Ok(shift) = shift.trim().parse::<i32>() else {
+                    return Task::none();
+                };
+                self.dialog_page_opt = None;
+                // Negative shifts undo positive ones, so map them onto the forward shift that does the same.
+                let transform = crate::hex_view::transform::TextTransform::Caesar(shift.rem_euclid(26) as u8);
+                return Task::batch([self.update(Action::HexAction(Message::TransformSelection(transform))), self.focus_hex_view()]);
+            }
+
+            Action::ShowCompareRanges => {
+                if let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(self.tab_model.active()) {
+                    self.dialog_page_opt = Some(DialogPage::CompareRanges(compare_ranges::CompareRanges::new(&tab.hex_view)));
+                }
+            }
+
+            Action::CompareFirst(value) => {
+                if let Some(DialogPage::CompareRanges(compare)) = &mut self.dialog_page_opt {
+                    compare.first = value;
+                }
+            }
+
+            Action::CompareSecond(value) => {
+                if let Some(DialogPage::CompareRanges(compare)) = &mut self.dialog_page_opt {
+                    compare.second = value;
+                }
+            }
+
+            Action::CompareLength(value) => {
+                if let Some(DialogPage::CompareRanges(compare)) = &mut self.dialog_page_opt {
+                    compare.length = value;
+                }
+            }
+
+            Action::CompareRun => {
+                let (Some(DialogPage::CompareRanges(compare)), Some(Tab::Editor(tab))) =
+                    (&mut self.dialog_page_opt, self.tab_model.data_mut::<Tab>(self.tab_model.active()))
+                else {
+                    return Task::none();
+                };
+                if let Some(buffer) = &tab.hex_view.buffer {
+                    compare.compare(&buffer.data);
+                }
+                if let (Some(mismatches), Some((first, second, _))) = (&compare.mismatches, compare.ranges()) {
+                    let diff = mismatches
+                        .iter()
+                        .flat_map(|(i, _, _)| [first + i..first + i + 1, second + i..second + i + 1])
+                        .collect();
+                    tab.hex_view.highlights.set(Layer::Diff, diff);
+                    tab.hex_view.redraw();
+                }
+            }
+
+            Action::ShowXorSearch => {
+                self.dialog_page_opt = Some(DialogPage::XorSearch(xor_search::XorSearch::new()));
+            }
+
+            Action::XorCrib(value) => {
+                if let Some(DialogPage::XorSearch(search)) = &mut self.dialog_page_opt {
+                    search.crib = value;
+                }
+            }
+
+            Action::XorMaxKeyLen(value) => {
+                if let Some(DialogPage::XorSearch(search)) = &mut self.dialog_page_opt {
+                    search.max_key_len = value;
+                }
+            }
+
+            Action::XorSearchRun => {
+                let Some(DialogPage::XorSearch(search)) = &mut self.dialog_page_opt else {
+                    return Task::none();
+                };
+                let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(self.tab_model.active()) else {
+                    return Task::none();
+                };
+                let (Some(buffer), Some(max_key_len)) = (&tab.hex_view.buffer, search.max_key_len()) else {
+                    return Task::none();
+                };
+                let Some(crib) = crate::hex_view::encode_str(tab.hex_view.encoding, &search.crib) else {
+                    return Task::none();
+                };
+                let data = buffer.data.clone();
+                search.searching = true;
+                search.hits = None;
+                return Task::perform(
+                    async move {
+                        tokio::task::spawn_blocking(move || crate::hex_view::xor::find_crib(&data, &crib, max_key_len, xor_search::MAX_HITS))
+                            .await
+                            .unwrap_or_default()
+                    },
+                    |hits| cosmic::app::Message::App(Action::XorSearchDone(hits)),
+                );
+            }
+
+            Action::XorSearchDone(hits) => {
+                if let Some(DialogPage::XorSearch(search)) = &mut self.dialog_page_opt {
+                    search.searching = false;
+                    search.hits = Some(hits);
+                }
+            }
+
+            Action::ScanYara => {
+                let dialog = rfd::AsyncFileDialog::new().add_filter(fl!("yara-rules"), &["yar", "yara"]);
+                return file_chooser::open(dialog, Action::ScanYaraWith);
+            }
+
+            Action::ScanYaraWith(path) => {
+                let entity = self.tab_model.active();
+                let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(entity) else {
+                    return Task::none();
+                };
+                let Some(buffer) = &tab.hex_view.buffer else {
+                    return Task::none();
+                };
+                let source = match fs::read_to_string(&path) {
+                    Ok(source) => source,
+                    Err(err) => {
+                        log::error!("failed to read YARA rules {:?}: {}", path, err);
+                        return Task::none();
+                    }
+                };
+                let data = buffer.data.clone();
+                self.yara_results = None;
+                self.context_page = ContextPage::Yara;
+                self.core.window.show_context = true;
+                return Task::perform(
+                    async move {
+                        tokio::task::spawn_blocking(move || yara_scan::scan(&source, &data))
+                            .await
+                            .unwrap_or_else(|err| Err(err.to_string()))
+                    },
+                    move |results| cosmic::app::Message::App(Action::YaraScanned(entity, results)),
+                );
+            }
+
+            Action::SaveSelectionAs => {
+                if self.save_selection.is_some() {
+                    return Task::none();
+                }
+                let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(self.tab_model.active()) else {
+                    return Task::none();
+                };
+                if tab.hex_view.selection.is_none() {
+                    return Task::none();
+                }
+                let mut dialog = rfd::AsyncFileDialog::new();
+                if let Some(parent) = tab.hex_view.path.parent() {
+                    dialog = dialog.set_directory(parent);
+                }
+                return file_chooser::save(dialog, Action::SaveSelectionTo);
+            }
+
+            Action::SaveSelectionTo(path) => {
+                if self.save_selection.is_some() {
+                    return Task::none();
+                }
+                let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(self.tab_model.active()) else {
+                    return Task::none();
+                };
+                let Some(data) = tab.hex_view.selected_bytes() else {
+                    return Task::none();
+                };
+                let save = save_selection::SaveSelection::new(path.clone(), data.len());
+                let written = save.written.clone();
+                self.save_selection = Some(save);
+                return Task::perform(
+                    async move {
+                        tokio::task::spawn_blocking(move || save_selection::write(&path, &data, &written).map_err(|err| format!("{:?}: {}", path, err)))
+                            .await
+                            .unwrap_or_else(|err| Err(err.to_string()))
+                    },
+                    |result| cosmic::app::Message::App(Action::SelectionSaved(result)),
+                );
+            }
+
+            // Only redraws the status bar.
+            Action::SaveSelectionProgress => {}
+
+            Action::SelectionSaved(result) => {
+                self.save_selection = None;
+                if let Err(err) = result {
+                    log::error!("failed to save selection to {}", err);
+                }
+            }
+
+            Action::YaraScanned(entity, results) => {
+                if let (Ok(matches), Some(Tab::Editor(tab))) = (&results, self.tab_model.data_mut::<Tab>(entity)) {
+                    tab.hex_view.matches = matches.iter().map(|found| found.range.start).collect();
+                    tab.hex_view.matches_for = None;
+                    tab.hex_view
+                        .highlights
+                        .set(Layer::Search, matches.iter().map(|found| found.range.clone()).collect());
+                    tab.hex_view.redraw();
+                }
+                self.yara_results = Some(results);
+            }
+
+            Action::SymbolFilter(filter) => {
+                self.symbol_filter = filter;
+            }
+
+            Action::RemoveWatch(index) => {
+                if index < self.watches.len() {
+                    self.watches.remove(index);
+                }
+            }
+
+            Action::WriteColor(format) => {
+                if let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(self.tab_model.active()) {
+                    if let Some(bytes) = format.encode(&self.color_input) {
+                        let offset = tab.hex_view.cursor.position / 2;
+                        return self.update(Action::HexAction(Message::SetBytes(offset, bytes)));
+                    }
+                }
+            }
+
+            Action::ShowImagePreview => {
+                if let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(self.tab_model.active()) {
+                    self.image_preview.offset = format!("0x{:X}", tab.hex_view.cursor.position / 2);
+                }
+                self.context_page = ContextPage::ImagePreview;
+                self.core.window.show_context = true;
+            }
+
+            Action::ImagePreviewOffset(offset) => {
+                self.image_preview.offset = offset;
+            }
+
+            Action::ImagePreviewWidth(width) => {
+                self.image_preview.width = width;
+            }
+
+            Action::ImagePreviewFormat(index) => {
+                if let Some(format) = crate::hex_view::bitmap::PixelFormat::ALL.get(index) {
+                    self.image_preview.format = *format;
+                }
+            }
+
+            Action::ImagePreviewZoom(index) => {
+                self.image_preview.zoom = 1 << index.min(3);
+            }
+
+            Action::ShowAudioPreview => {
+                self.context_page = ContextPage::AudioPreview;
+                self.core.window.show_context = true;
+            }
+
+            Action::AudioSampleRate(index) => {
+                if let Some(rate) = audio_preview::sample_rates.get(index) {
+                    self.pcm_format.sample_rate = *rate;
+                }
+            }
+
+            Action::AudioBitDepth(index) => {
+                if let Some(bits) = audio_preview::bit_depths.get(index) {
+                    self.pcm_format.bits = *bits;
+                }
+            }
+
+            Action::AudioChannels(index) => {
+                self.pcm_format.channels = index as u16 + 1;
+            }
+
+            Action::PlayAudio => {
+                audio_preview::play(self.audio_samples().to_vec(), self.pcm_format);
+            }
+
+            Action::ToggleTextView => {
+                if let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(self.tab_model.active()) {
+                    tab.text_view = !tab.text_view;
+                }
+                return self.update_tab();
+            }
+
+            Action::ChangeEncoding(index) => {
+                if let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(self.tab_model.active()) {
+                    if let Some(encoding) = crate::hex_view::text_encodings().get(index) {
+                        tab.hex_view.encoding = *encoding;
+                        tab.options.encoding = Some(encoding.name().to_string());
+                    }
+                }
+                return self.change_tab_options();
+            }
+
+            Action::SelectRun => {
+                return self.update(Action::HexAction(Message::SelectRun));
+            }
+
+            Action::CountMatches => {
+                self.add_search_history();
+                if let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(self.tab_model.active()) {
+                    if let Some(buffer) = &tab.hex_view.buffer {
+                        // Block selections are counted per row, matches don't span the gaps.
+                        let runs = tab
+                            .hex_view
+                            .selection
+                            .map_or(vec![0..buffer.len()], |selection| selection.runs(0..buffer.len()));
+                        let count = runs
+                            .into_iter()
+                            .map(|run| crate::hex_view::count_occurrences(&buffer.data[run], &self.needle))
+                            .sum();
+                        self.match_count = Some(count);
+                    }
+                }
+            }
+
+            Action::ReplacePatternChanged(value) => {
+                self.replace_pattern = value;
+            }
+
+            Action::ReplaceAllPreview => {
+                self.add_search_history();
+                let Some(replacement) = self.parse_pattern(&self.replace_pattern) else {
+                    return Task::none();
+                };
+                if self.needle.is_empty() {
+                    return Task::none();
+                }
+                let tabs = self
+                    .tab_model
+                    .iter()
+                    .filter(|entity| matches!(self.tab_model.data::<Tab>(*entity), Some(Tab::Editor(tab)) if tab.hex_view.buffer.is_some()))
+                    .map(|entity| (entity, None, true))
+                    .collect();
+                self.dialog_page_opt = Some(DialogPage::ReplaceAll(self.needle.clone(), replacement, tabs));
+                return self.update(Action::ReplaceAllCount(0));
+            }
+
+            Action::ReplaceAllCount(index) => {
+                let Some(DialogPage::ReplaceAll(needle, _, tabs)) = &mut self.dialog_page_opt else {
+                    return Task::none();
+                };
+                let Some((entity, count, enabled)) = tabs.get_mut(index) else {
+                    return Task::none();
+                };
+                if let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(*entity) {
+                    let found = tab
+                        .hex_view
+                        .buffer
+                        .as_ref()
+                        .map_or(0, |buffer| crate::hex_view::count_occurrences(&buffer.data, needle));
+                    *count = Some(found);
+                    *enabled = found > 0;
+                }
+                // The next tab is counted by its own message, so the dialog stays responsive.
+                return Task::done(cosmic::app::Message::App(Action::ReplaceAllCount(index + 1)));
+            }
+
+            Action::ReplaceAllToggle(index, enabled) => {
+                if let Some(DialogPage::ReplaceAll(_, _, tabs)) = &mut self.dialog_page_opt {
+                    if let Some(tab) = tabs.get_mut(index) {
+                        tab.2 = enabled;
+                    }
+                }
+            }
+
+            Action::ReplaceAllConfirm => {
+                let mut tasks = vec![self.update_tab()];
+                if let Some(DialogPage::ReplaceAll(needle, replacement, tabs)) = self.dialog_page_opt.take() {
+                    for (entity, _count, enabled) in tabs {
+                        if !enabled {
+                            continue;
+                        }
+                        if let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(entity) {
+                            let task = tab.hex_view.replace_all(&needle, &replacement);
+                            tasks.push(task.map(move |msg| cosmic::app::Message::App(Action::TabHexAction(entity, msg))));
+                        }
+                    }
+                }
+                return Task::batch(tasks);
+            }
+
+            Action::SearchMatches(entity, key, matches) => {
+                if let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(entity) {
+                    // Dropped if another search or an edit started since.
+                    let version = tab.hex_view.buffer.as_ref().map(|buffer| buffer.version());
+                    if tab.hex_view.matches_for.as_ref() == Some(&key) && version == Some(key.1) {
+                        tab.hex_view.matches = matches;
+                        tab.hex_view.highlight_matches(key.0.len());
+                    }
+                }
+            }
+
+            Action::SelectionStats(entity, key, stats) => {
+                if let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(entity) {
+                    tab.hex_view.selection_stats = Some((key, stats));
+                }
+            }
+
+            Action::CopyOffset(relative) => {
+                if let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(self.tab_model.active()) {
+                    let offset = tab.hex_view.cursor.position / 2;
+                    let text = if relative {
+                        tab.hex_view.relative_offset(offset)
+                    } else {
+                        format!("0x{:08X}", offset)
+                    };
+                    self.clipboard_has_data = true;
+                    return cosmic::iced::clipboard::write(text);
+                }
+            }
+
+            Action::SetOffsetBase => {
+                return self.update(Action::HexAction(Message::SetOffsetBase));
+            }
+
+            Action::CopyAsHtml => {
+                if let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(self.tab_model.active()) {
+                    if let (Some(selection), Some(buffer)) = (tab.hex_view.selection, &tab.hex_view.buffer) {
+                        let html = crate::hex_view::export::to_html(&buffer.data[selection.range()], selection.start(), &tab.hex_view.theme);
+                        self.clipboard_has_data = true;
+                        return cosmic::iced::clipboard::write(html);
+                    }
+                }
+            }
+
+            Action::Revert => {
+                let entity = self.tab_model.active();
+                if let Some(tab) = self.tab_model.data::<Tab>(entity) {
+                    if tab.is_dirty() {
+                        self.dialog_page_opt = Some(DialogPage::PromptRevert(entity));
+                    } else {
+                        return self.update(Action::RevertForce(entity));
+                    }
+                }
+            }
+
+            Action::RevertForce(entity) => {
+                self.dialog_page_opt = None;
+                if let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(entity) {
+                    if let Err(err) = tab.hex_view.reload() {
+                        log::error!("failed to reload {:?}: {}", tab.hex_view.path, err);
+                    }
+                }
+                return Task::batch([self.update_tab(), self.sync_dirty(entity)]);
+            }
+
+            Action::ExportIps => {
+                let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(self.tab_model.active()) else {
+                    return Task::none();
+                };
+                let Some(buffer) = &tab.hex_view.buffer else {
+                    return Task::none();
+                };
+                let patch = match crate::hex_view::ips::create(buffer.original(), &buffer.data) {
+                    Ok(patch) => patch,
+                    Err(err) => {
+                        log::error!("failed to create IPS patch: {}", err);
+                        return Task::none();
+                    }
+                };
+                let mut dialog = rfd::AsyncFileDialog::new().add_filter(fl!("ips-patch"), &["ips"]);
+                if let Some(name) = tab.hex_view.path.file_stem() {
+                    dialog = dialog.set_file_name(format!("{}.ips", name.to_string_lossy()));
+                }
+                return file_chooser::save(dialog, move |path| Action::WriteFile(path, patch));
+            }
+
+            Action::ExportBookmarks => {
+                let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(self.tab_model.active()) else {
+                    return Task::none();
+                };
+                let list = tab.hex_view.bookmark_list();
+                let mut dialog = rfd::AsyncFileDialog::new().add_filter("JSON", &["json"]).add_filter("CSV", &["csv"]);
+                if let Some(name) = tab.hex_view.path.file_name() {
+                    dialog = dialog.set_file_name(format!("{}.bookmarks.json", name.to_string_lossy()));
+                }
+                return file_chooser::save(dialog, move |path| {
+                    let text = if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("csv")) {
+                        bookmarks::to_csv(&list)
+                    } else {
+                        bookmarks::to_json(&list)
+                    };
+                    Action::WriteFile(path, text.into_bytes())
+                });
+            }
+
+            Action::ImportBookmarks => {
+                let dialog = rfd::AsyncFileDialog::new().add_filter(fl!("bookmark-list"), &["json", "csv"]);
+                return file_chooser::open(dialog, Action::ImportBookmarksFrom);
+            }
+
+            Action::ImportBookmarksFrom(path) => {
+                let list = fs::read_to_string(&path)
+                    .map_err(|err| err.to_string())
+                    .and_then(|text| bookmarks::parse(&text));
+                match list {
+                    Ok(list) => {
+                        if let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(self.tab_model.active()) {
+                            tab.hex_view.add_bookmarks(list);
+                        }
+                    }
+                    Err(err) => log::error!("failed to import bookmarks from {:?}: {}", path, err),
+                }
+            }
+
+            Action::ImportListing => {
+                let dialog = rfd::AsyncFileDialog::new().add_filter(fl!("disassembler-listing"), &["csv", "txt", "tsv", "lst"]);
+                return file_chooser::open(dialog, Action::ImportListingFrom);
+            }
+
+            Action::ImportListingFrom(path) => {
+                let entries = fs::read_to_string(&path)
+                    .map_err(|err| err.to_string())
+                    .and_then(|text| crate::hex_view::listing::parse(&text));
+                match entries {
+                    Ok(entries) => {
+                        if let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(self.tab_model.active()) {
+                            tab.hex_view.import_listing(entries);
+                        }
+                    }
+                    Err(err) => log::error!("failed to import listing from {:?}: {}", path, err),
+                }
+            }
+
+            Action::ShowExportDump => {
+                if let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(self.tab_model.active()) {
+                    self.dialog_page_opt = Some(DialogPage::ExportDump(export_dump::ExportDump::new(&tab.hex_view)));
+                }
+            }
+
+            Action::ExportDumpStart(value) => {
+                if let Some(DialogPage::ExportDump(export)) = &mut self.dialog_page_opt {
+                    export.start = value;
+                }
+            }
+
+            Action::ExportDumpEnd(value) => {
+                if let Some(DialogPage::ExportDump(export)) = &mut self.dialog_page_opt {
+                    export.end = value;
+                }
+            }
+
+            Action::ExportDumpPdf(pdf) => {
+                if let Some(DialogPage::ExportDump(export)) = &mut self.dialog_page_opt {
+                    export.pdf = pdf;
+                }
+            }
+
+            Action::ExportDumpConfirm => {
+                let Some(DialogPage::ExportDump(export)) = &self.dialog_page_opt else {
+                    return Task::none();
+                };
+                let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(self.tab_model.active()) else {
+                    return Task::none();
+                };
+                let Some(data) = export.render(&tab.hex_view) else {
+                    log::error!("invalid export range {}..{}", export.start, export.end);
+                    return Task::none();
+                };
+                let (name, extension) = if export.pdf {
+                    (fl!("export-pdf"), "pdf")
+                } else {
+                    (fl!("export-text"), "txt")
+                };
+                self.dialog_page_opt = None;
+                let dialog = rfd::AsyncFileDialog::new().add_filter(name, &[extension]);
+                return file_chooser::save(dialog, move |path| Action::WriteFile(path, data));
+            }
+
+            Action::ShowRecords => {
+                if let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(self.tab_model.active()) {
+                    self.dialog_page_opt = Some(DialogPage::Records(records::Records::new(&tab.hex_view)));
+                }
+            }
+
+            Action::RecordsSize(value) => {
+                if let Some(DialogPage::Records(records)) = &mut self.dialog_page_opt {
+                    records.size = value;
+                }
+            }
+
+            Action::RecordsGoTo(value) => {
+                if let Some(DialogPage::Records(records)) = &mut self.dialog_page_opt {
+                    records.record = value;
+                }
+            }
+
+            Action::RecordsConfirm => {
+                let Some(DialogPage::Records(records)) = self.dialog_page_opt.take() else {
+                    return Task::none();
+                };
+                let mut tasks = vec![self.update(Action::HexAction(Message::SetRecordSize(records.size())))];
+                if let Some(record) = crate::hex_view::parse_offset(&records.record) {
+                    tasks.push(self.update(Action::HexAction(Message::GoToRecord(record))));
+                }
+                tasks.push(self.focus_hex_view());
+                return Task::batch(tasks);
+            }
+
+            Action::ShowResize => {
+                if let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(self.tab_model.active()) {
+                    self.dialog_page_opt = Some(DialogPage::Resize(resize::Resize::new(&tab.hex_view)));
+                }
+            }
+
+            Action::ResizeLength(value) => {
+                if let Some(DialogPage::Resize(resize)) = &mut self.dialog_page_opt {
+                    resize.length = value;
+                }
+            }
+
+            Action::ResizeFill(value) => {
+                if let Some(DialogPage::Resize(resize)) = &mut self.dialog_page_opt {
+                    resize.fill = value;
+                }
+            }
+
+            Action::ResizeConfirm => {
+                let Some(DialogPage::Resize(resize)) = self.dialog_page_opt.take() else {
+                    return Task::none();
+                };
+                let (Some(length), Some(fill)) = (resize.length(), resize.fill()) else {
+                    return Task::none();
+                };
+                return Task::batch([self.update(Action::HexAction(Message::Resize(length, fill))), self.focus_hex_view()]);
+            }
+
+            Action::ShowJumpList => {
+                self.dialog_page_opt = Some(DialogPage::JumpList(String::new()));
+                return widget::text_input::focus(self.jump_filter_id.clone());
+            }
+
+            Action::JumpListFilter(filter) => {
+                if let Some(DialogPage::JumpList(current)) = &mut self.dialog_page_opt {
+                    *current = filter;
+                }
+            }
+
+            Action::JumpTo(offset) => {
+                self.dialog_page_opt = None;
+                return Task::batch([self.update(Action::HexAction(Message::MoveCaret(offset * 2))), self.focus_hex_view()]);
+            }
+
+            Action::ShowProperties => {
+                if let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(self.tab_model.active()) {
+                    if let Some(buffer) = &tab.hex_view.buffer {
+                        let properties = properties::FileProperties::new(&tab.hex_view.path, &buffer.data);
+                        self.dialog_page_opt = Some(DialogPage::Properties(properties));
+                    }
+                }
+            }
+
+            Action::ComputeHashes => {
+                if let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(self.tab_model.active()) {
+                    if let Some(buffer) = &tab.hex_view.buffer {
+                        let data = buffer.data.clone();
+                        return Task::perform(
+                            async move { tokio::task::spawn_blocking(move || properties::compute_hashes(&data)).await.unwrap_or_default() },
+                            |hashes| cosmic::app::Message::App(Action::HashesComputed(hashes)),
+                        );
+                    }
+                }
+            }
+
+            Action::HashesComputed(hashes) => {
+                if let Some(DialogPage::Properties(properties)) = &mut self.dialog_page_opt {
+                    properties.hashes = Some(hashes);
+                }
+            }
+
             Action::KeyPressed(modifiers, key) => {
                 for (key_bind, action) in self.key_binds.iter() {
                     if key_bind.matches(modifiers, &key) {
@@ -631,8 +2393,11 @@ impl Application for AppModel {
         Task::none()
     }
 
-    fn on_nav_select(&mut self, _id: widget::nav_bar::Id) -> Task<Self::Message> {
-        Task::none()
+    fn on_nav_select(&mut self, id: widget::nav_bar::Id) -> Task<Self::Message> {
+        match self.select_project_node(id) {
+            Some(path) => self.update(Action::OpenFile(vec![path])),
+            None => Task::none(),
+        }
     }
 }
 
@@ -652,9 +2417,22 @@ impl AppModel {
             .into()
     }
 
-    /// Updates the header and window titles.
+    /// Updates the header and window titles with the active file and whether it has unsaved changes.
     pub fn update_title(&mut self) -> Task<Action> {
-        let window_title = fl!("app-title");
+        let window_title = match self.tab_model.data::<Tab>(self.tab_model.active()) {
+            Some(Tab::Editor(tab)) => {
+                let dirty = if tab.hex_view.is_dirty() { "• " } else { "" };
+                match tab.hex_view.path.parent() {
+                    Some(parent) => format!("{dirty}{} ({}) — {}", tab.title(), menu_bar::format_path(parent), fl!("app-title")),
+                    None => format!("{dirty}{} — {}", tab.title(), fl!("app-title")),
+                }
+            }
+            None => fl!("app-title"),
+        };
+        if window_title == self.window_title {
+            return Task::none();
+        }
+        self.window_title = window_title.clone();
         if let Some(id) = self.core.main_window_id() {
             self.set_window_title(window_title, id)
         } else {
@@ -662,6 +2440,7 @@ impl AppModel {
         }
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
     fn open_tab(&mut self, path: std::path::PathBuf) -> Option<segmented_button::Entity> {
         let canonical = match fs::canonicalize(&path) {
             Ok(path) => path,
@@ -682,25 +2461,23 @@ impl AppModel {
         }
         if let Some(entity) = activate_opt {
             self.tab_model.activate(entity);
+            self.claim_document(entity);
             return Some(entity);
         }
 
-        let buf = DataBuffer {
-            data: match fs::read(&canonical) {
-                Ok(data) => data,
-                Err(err) => {
-                    log::error!("failed to read {:?}: {}", canonical, err);
-                    return None;
-                }
-            },
-        };
+        let buf = DataBuffer::new(match fs::read(&canonical) {
+            Ok(data) => data,
+            Err(err) => {
+                log::error!("failed to read {:?}: {}", canonical, err);
+                return None;
+            }
+        });
 
-        self.config_state.recent_files.retain(|x| x != &canonical);
-        self.config_state.recent_files.push_front(canonical.to_path_buf());
-        self.config_state.recent_files.truncate(10);
+        self.add_recent_file(canonical.clone());
         self.save_config_state();
 
-        let mut tab = tab::EditorTab::new(canonical, buf);
+        let mut tab = tab::EditorTab::new(canonical.clone(), buf);
+        tab.options = self.config_state.tab_options.get(&canonical).cloned().unwrap_or_default();
         tab.set_config(&self.config);
         Some(
             self.tab_model
@@ -714,15 +2491,109 @@ impl AppModel {
         )
     }
 
+    /// Moves keyboard focus to the hex view of the active tab.
+    fn focus_hex_view(&self) -> Task<Action> {
+        match self.tab_model.data::<Tab>(self.tab_model.active()) {
+            Some(Tab::Editor(tab)) => tab.hex_view.focus().map(|msg| cosmic::app::Message::App(Action::HexAction(msg))),
+            _ => Task::none(),
+        }
+    }
+
+    /// Moves `path` to the front of the recent files unless it is pinned.
+    fn add_recent_file(&mut self, path: PathBuf) {
+        if self.config_state.pinned_files.contains(&path) {
+            return;
+        }
+        self.config_state.recent_files.retain(|x| x != &path);
+        self.config_state.recent_files.push_front(path);
+        self.config_state.recent_files.truncate(self.config.recent_files_max);
+    }
+
+    /// Prefixes the tab text with a dot while the tab has unsaved changes, in all views of the file.
+    fn set_tab_dirty(&mut self, entity: Entity, dirty: bool) {
+        for entity in std::iter::once(entity).chain(self.linked_views(entity)) {
+            if let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(entity) {
+                let title = if dirty { format!("• {}", tab.title()) } else { tab.title() };
+                self.tab_model.text_set(entity, title);
+            }
+        }
+    }
+
+    /// Other tabs showing the same file as `entity`, created with Duplicate View. Only one of them
+    /// holds the data and edit history at a time, the active one claims it.
+    fn linked_views(&self, entity: Entity) -> Vec<Entity> {
+        let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(entity) else {
+            return Vec::new();
+        };
+        self.tab_model
+            .iter()
+            .filter(|other| *other != entity)
+            .filter(|other| matches!(self.tab_model.data::<Tab>(*other), Some(Tab::Editor(other)) if other.hex_view.path == tab.hex_view.path))
+            .collect()
+    }
+
+    fn move_document(&mut self, from: Entity, to: Entity) {
+        let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(from) else {
+            return;
+        };
+        let Some(document) = tab.hex_view.take_document() else {
+            return;
+        };
+        if let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(to) {
+            tab.hex_view.set_document(document);
+        }
+    }
+
+    /// Moves the shared data of a duplicated view into `entity` so it can be shown and edited there.
+    fn claim_document(&mut self, entity: Entity) {
+        if matches!(self.tab_model.data::<Tab>(entity), Some(Tab::Editor(tab)) if tab.hex_view.buffer.is_some()) {
+            return;
+        }
+        let holder = self
+            .linked_views(entity)
+            .into_iter()
+            .find(|view| matches!(self.tab_model.data::<Tab>(*view), Some(Tab::Editor(tab)) if tab.hex_view.buffer.is_some()));
+        if let Some(holder) = holder {
+            self.move_document(holder, entity);
+        }
+    }
+
+    /// Applies the edited options of the active tab and remembers them for its file.
+    fn change_tab_options(&mut self) -> Task<Action> {
+        let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(self.tab_model.active()) else {
+            return Task::none();
+        };
+        tab.set_config(&self.config);
+        let (path, options) = (tab.hex_view.path.clone(), tab.options.clone());
+        if options == tab::TabOptions::default() {
+            self.config_state.tab_options.remove(&path);
+        } else {
+            self.config_state.tab_options.insert(path, options);
+        }
+        self.save_config_state();
+        self.update_tab()
+    }
+
+    /// Picks up dirty state changes made outside of the hex view's own messages (undo, save, revert, ...).
+    fn sync_dirty(&mut self, entity: Entity) -> Task<Action> {
+        if let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(entity) {
+            if let Some(dirty) = tab.hex_view.take_dirty_change() {
+                self.set_tab_dirty(entity, dirty);
+            }
+        }
+        Task::batch([self.update_title(), self.update_selection_stats()])
+    }
+
     fn update_tab(&mut self) -> cosmic::Task<cosmic::app::Message<Action>> {
         let tab_id = self.tab_model.active();
+        self.claim_document(tab_id);
         match self.tab_model.data_mut::<Tab>(tab_id) {
             Some(Tab::Editor(tab)) => {
                 tab.hex_view.redraw();
             }
             _ => {}
         }
-        Task::none()
+        Task::batch([self.update_title(), self.update_selection_stats()])
     }
 
     fn settings(&self) -> Element<Action> {
@@ -741,34 +2612,134 @@ impl AppModel {
 
         let font_size_selected = font_sizes.iter().position(|font_size| font_size == &self.config.font_size);
 
-        widget::settings::view_column(vec![widget::settings::section()
-            .title(fl!("appearance"))
-            .add(
-                widget::settings::item::builder(fl!("theme")).control(widget::dropdown(&app_themes, Some(app_theme_selected), move |index| {
-                    Action::ChangeTheme(match index {
-                        1 => AppTheme::Dark,
-                        2 => AppTheme::Light,
-                        _ => AppTheme::System,
-                    })
-                })),
-            )
-            .add(
-                widget::settings::item::builder(fl!("syntax-dark")).control(widget::dropdown(&theme_names, dark_selected, move |index| {
-                    Action::ChangeSyntaxTheme(index, true)
-                })),
-            )
-            .add(
-                widget::settings::item::builder(fl!("syntax-light")).control(widget::dropdown(&theme_names, light_selected, move |index| {
-                    Action::ChangeSyntaxTheme(index, false)
-                })),
-            )
-            .add(widget::settings::item::builder(fl!("default-font")).control(widget::dropdown(&font_names, font_selected, Action::ChangeFont)))
-            .add(
-                widget::settings::item::builder(fl!("default-font-size")).control(widget::dropdown(&font_size_names, font_size_selected, move |index| {
-                    Action::ChangeFontSize(font_sizes[index])
-                })),
-            )
-            .into()])
+        let cosmic_theme::Spacing { space_xxs, .. } = theme::active().cosmic().spacing;
+        let mut status_section = widget::settings::section().title(fl!("status-bar"));
+        for field in StatusField::ALL {
+            let enabled = self.config.status_fields.contains(&field);
+            status_section = status_section
+                .add(widget::settings::item::builder(status_bar::field_name(field)).toggler(enabled, move |enabled| Action::ToggleStatusField(field, enabled)));
+        }
+
+        let hex_theme = match self.tab_model.data::<Tab>(self.tab_model.active()) {
+            Some(Tab::Editor(tab)) => Some(&tab.hex_view.theme),
+            _ => None,
+        };
+        let mut layer_section = widget::settings::section().title(fl!("highlight-colors"));
+        for layer in Layer::ALL {
+            let color = self.config.layer_colors.get(&layer).map_or("", String::as_str);
+            let input = widget::text_input::text_input(fl!("highlight-color-default"), color).on_input(move |color| Action::ChangeLayerColor(layer, color));
+            let mut item = widget::settings::item::builder(layer.name());
+            // Warn about colors the hex digits are hard to read on.
+            if let (Some(hex_theme), Some(color)) = (hex_theme, highlight::parse_color(color)) {
+                let ratio = contrast_ratio(hex_theme.hex, blend(color, hex_theme.background));
+                if ratio < accessibility::MIN_CONTRAST {
+                    item = item.description(fl!("highlight-color-low-contrast", ratio = format!("{:.1}", ratio)));
+                }
+            }
+            layer_section = layer_section.add(item.control(input));
+        }
+
+        widget::settings::view_column(vec![
+            widget::settings::section()
+                .title(fl!("appearance"))
+                .add(
+                    widget::settings::item::builder(fl!("theme")).control(widget::dropdown(&app_themes, Some(app_theme_selected), move |index| {
+                        Action::ChangeTheme(match index {
+                            1 => AppTheme::Dark,
+                            2 => AppTheme::Light,
+                            _ => AppTheme::System,
+                        })
+                    })),
+                )
+                .add(
+                    widget::settings::item::builder(fl!("syntax-dark")).control(widget::dropdown(&theme_names, dark_selected, move |index| {
+                        Action::ChangeSyntaxTheme(index, true)
+                    })),
+                )
+                .add(
+                    widget::settings::item::builder(fl!("syntax-light")).control(widget::dropdown(&theme_names, light_selected, move |index| {
+                        Action::ChangeSyntaxTheme(index, false)
+                    })),
+                )
+                .add(widget::settings::item::builder(fl!("high-contrast")).toggler(self.config.high_contrast, Action::ToggleHighContrast))
+                .add(widget::settings::item::builder(fl!("default-font")).control(widget::dropdown(&font_names, font_selected, Action::ChangeFont)))
+                .add(
+                    widget::settings::item::builder(fl!("default-font-size")).control(widget::dropdown(&font_size_names, font_size_selected, move |index| {
+                        Action::ChangeFontSize(font_sizes[index])
+                    })),
+                )
+                .into(),
+            widget::settings::section()
+                .title(fl!("files"))
+                .add(widget::settings::item::builder(fl!("recent-files-max")).control(widget::dropdown(
+                    &recent_files_max_names,
+                    recent_files_max_values.iter().position(|max| *max == self.config.recent_files_max),
+                    Action::ChangeRecentFilesMax,
+                )))
+                .into(),
+            widget::settings::section()
+                .title(fl!("scrolling"))
+                .add(widget::settings::item::builder(fl!("smooth-scrolling")).toggler(self.config.smooth_scrolling, Action::ToggleSmoothScrolling))
+                .add(
+                    widget::settings::item::builder(fl!("reduce-motion"))
+                        .description(fl!("reduce-motion-description"))
+                        .toggler(self.config.reduce_motion, Action::ToggleReduceMotion),
+                )
+                .add(widget::settings::item::builder(fl!("scroll-lines")).control(widget::dropdown(
+                    &scroll_lines_names,
+                    scroll_lines_values.iter().position(|lines| *lines == self.config.scroll_lines),
+                    Action::ChangeScrollLines,
+                )))
+                .add(widget::settings::item::builder(fl!("default-endian")).control(widget::dropdown(
+                    &endian_names,
+                    Some(if self.config.endian == Endian::Big { 1 } else { 0 }),
+                    Action::ChangeEndian,
+                )))
+                .add(widget::settings::item::builder(fl!("group-size")).control(widget::dropdown(
+                    &group_size_names,
+                    group_size_values.iter().position(|group_size| *group_size == self.config.group_size),
+                    Action::ChangeGroupSize,
+                )))
+                .into(),
+            widget::settings::section()
+                .title(fl!("view"))
+                .add(
+                    widget::settings::item::builder(fl!("numeric-column")).control(widget::dropdown(
+                        &numeric_column_names,
+                        NumericColumn::ALL
+                            .iter()
+                            .position(|numeric_column| *numeric_column == self.config.numeric_column),
+                        Action::ChangeNumericColumn,
+                    )),
+                )
+                .add(widget::settings::item::builder(fl!("row-checksum")).control(widget::dropdown(
+                    &row_checksum_names,
+                    RowChecksum::ALL.iter().position(|row_checksum| *row_checksum == self.config.row_checksum),
+                    Action::ChangeRowChecksum,
+                )))
+                .add(
+                    widget::settings::item::builder(fl!("double-width-text"))
+                        .description(fl!("double-width-text-description"))
+                        .toggler(self.config.double_width_text, Action::ToggleDoubleWidthText),
+                )
+                .add(widget::settings::item::builder(fl!("group-gaps")).toggler(self.config.group_gaps, Action::ToggleGroupGaps))
+                .add(widget::settings::item::builder(fl!("show-hex-pane")).toggler(self.config.show_hex_pane, Action::ToggleHexPane))
+                .add(widget::settings::item::builder(fl!("show-text-pane")).toggler(self.config.show_text_pane, Action::ToggleTextPane))
+                .into(),
+            layer_section.into(),
+            status_section.into(),
+            widget::settings::section()
+                .title(fl!("backup"))
+                .add(
+                    widget::settings::item::builder(fl!("backup-settings")).control(
+                        widget::row::with_capacity(2)
+                            .spacing(space_xxs)
+                            .push(widget::button::standard(fl!("import-settings")).on_press(Action::ImportSettings))
+                            .push(widget::button::standard(fl!("export-settings")).on_press(Action::ExportSettings)),
+                    ),
+                )
+                .into(),
+        ])
         .into()
     }
 
@@ -799,21 +2770,124 @@ impl AppModel {
     }
 
     fn get_pattern_needle(&self) -> Vec<u8> {
-        let mut res = Vec::new();
+        self.parse_pattern(&self.search_pattern).unwrap_or_default()
+    }
 
-        for (i, c) in self.search_pattern.chars().enumerate() {
-            let d = c.to_digit(16);
-            if let Some(d) = d {
-                if i % 2 == 0 {
-                    res.push((d as u8) << 4);
-                } else {
-                    let a = res.pop().unwrap();
-                    res.push(a | (d as u8));
+    /// Converts a pattern typed into the find bar into bytes according to the search mode, `None`
+    /// if it isn't valid in that mode.
+    fn parse_pattern(&self, pattern: &str) -> Option<Vec<u8>> {
+        match self.search_mode {
+            // An empty pattern is valid, as a replacement it deletes the matches.
+            SearchMode::Hex => crate::hex_view::parse_hex(pattern).or_else(|| pattern.trim().is_empty().then(Vec::new)),
+            SearchMode::Text => {
+                let encoding = match self.tab_model.data::<Tab>(self.tab_model.active()) {
+                    Some(Tab::Editor(tab)) => tab.hex_view.encoding,
+                    _ => encoding_rs::UTF_8,
+                };
+                crate::hex_view::encode_str(encoding, pattern)
+            }
+            SearchMode::Number => {
+                let endian = match self.tab_model.data::<Tab>(self.tab_model.active()) {
+                    Some(Tab::Editor(tab)) => tab.hex_view.endian,
+                    _ => self.config.endian,
+                };
+                match crate::hex_view::parse_offset(pattern)? {
+                    value if value <= u32::MAX as usize => Some(endian.normalize((value as u32).to_le_bytes()).to_vec()),
+                    value => Some(endian.normalize((value as u64).to_le_bytes()).to_vec()),
                 }
             }
         }
+    }
 
-        res
+    /// Searches the active tab for all matches in the background, for the scrollbar markers and
+    /// the highlights. Matches already found for the needle and data are kept.
+    fn find_all_matches(&mut self) -> Task<Action> {
+        let entity = self.tab_model.active();
+        let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(entity) else {
+            return Task::none();
+        };
+        let Some(buffer) = &tab.hex_view.buffer else {
+            return Task::none();
+        };
+        let key = (self.needle.clone(), buffer.version());
+        if tab.hex_view.matches_for.as_ref() == Some(&key) {
+            return Task::none();
+        }
+        let haystack = buffer.data.clone();
+        let needle = self.needle.clone();
+        tab.hex_view.matches_for = Some(key.clone());
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || crate::hex_view::find_occurrences(&haystack, &needle))
+                    .await
+                    .unwrap_or_default()
+            },
+            move |matches| cosmic::app::Message::App(Action::SearchMatches(entity, key.clone(), matches)),
+        )
+    }
+
+    /// Sums up the selected bytes of the active tab in the background for the status bar. Stats
+    /// already requested for the selection and data are kept.
+    fn update_selection_stats(&mut self) -> Task<Action> {
+        if !self.config.status_fields.contains(&StatusField::Selection) {
+            return Task::none();
+        }
+        let entity = self.tab_model.active();
+        let Some(Tab::Editor(tab)) = self.tab_model.data_mut::<Tab>(entity) else {
+            return Task::none();
+        };
+        let (Some(key), Some(buffer)) = (tab.hex_view.stats_key(), &tab.hex_view.buffer) else {
+            return Task::none();
+        };
+        if tab.hex_view.stats_for == Some(key) {
+            return Task::none();
+        }
+        let bytes: Vec<u8> = key
+            .0
+            .runs(0..buffer.len())
+            .into_iter()
+            .flat_map(|run| buffer.data[run].iter().copied())
+            .collect();
+        tab.hex_view.stats_for = Some(key);
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || crate::hex_view::buffer::byte_stats(&[&bytes]))
+                    .await
+                    .unwrap_or_default()
+            },
+            move |stats| cosmic::app::Message::App(Action::SelectionStats(entity, key, stats)),
+        )
+    }
+
+    /// Hashes the data of `tab` in the background and compares it with its checksum file.
+    fn verify_sidecar(tab: &tab::EditorTab, done: impl Fn(sidecar::Verification) -> Action + Send + 'static) -> Task<Action> {
+        let (Some(buffer), Some((sidecar, kind))) = (&tab.hex_view.buffer, tab.sidecar.clone()) else {
+            return Task::none();
+        };
+        let path = tab.hex_view.path.clone();
+        let data = buffer.data.clone();
+        Task::perform(
+            async move { tokio::task::spawn_blocking(move || sidecar::verify(sidecar, kind, &path, &data)).await },
+            move |verification| match verification {
+                Ok(verification) => cosmic::app::Message::App(done(verification)),
+                Err(err) => {
+                    log::error!("failed to verify checksum: {}", err);
+                    cosmic::app::Message::App(Action::DialogCancel)
+                }
+            },
+        )
+    }
+
+    /// Remembers the current pattern in the search history of the current mode.
+    fn add_search_history(&mut self) {
+        if self.search_pattern.is_empty() {
+            return;
+        }
+        let history = self.config_state.search_history.entry(self.search_mode).or_default();
+        history.retain(|pattern| pattern != &self.search_pattern);
+        history.insert(0, self.search_pattern.clone());
+        history.truncate(SEARCH_HISTORY_LEN);
+        self.save_config_state();
     }
 }
 
@@ -823,6 +2897,13 @@ pub enum ContextPage {
     #[default]
     About,
     Settings,
+    Inspector,
+    Protobuf,
+    ImagePreview,
+    AudioPreview,
+    Watches,
+    Symbols,
+    Yara,
 }
 
 impl ContextPage {
@@ -830,6 +2911,13 @@ impl ContextPage {
         match self {
             Self::About => String::new(),
             Self::Settings => fl!("settings"),
+            Self::Inspector => fl!("data-inspector"),
+            Self::Protobuf => fl!("protobuf"),
+            Self::ImagePreview => fl!("image-preview"),
+            Self::AudioPreview => fl!("audio-preview"),
+            Self::Watches => fl!("watches"),
+            Self::Symbols => fl!("symbols"),
+            Self::Yara => fl!("yara-results"),
         }
     }
 }
@@ -837,6 +2925,22 @@ impl ContextPage {
 lazy_static::lazy_static! {
     static ref font_size_names: Vec<String> = (4..=32).map(|font_size| format!("{}px", font_size)).collect();
     static ref font_sizes: Vec<usize> = (4..=32).collect();
+    static ref search_mode_names: Vec<String> = vec![fl!("search-hex"), fl!("search-text"), fl!("search-number")];
+    static ref recent_files_max_values: Vec<usize> = vec![5, 10, 20, 50];
+    static ref recent_files_max_names: Vec<String> = recent_files_max_values.iter().map(|max| max.to_string()).collect();
+    static ref scroll_lines_values: Vec<usize> = vec![1, 3, 5, 10];
+    static ref scroll_lines_names: Vec<String> = scroll_lines_values.iter().map(|lines| lines.to_string()).collect();
+    static ref endian_names: Vec<String> = vec![fl!("little-endian"), fl!("big-endian")];
+    static ref numeric_column_names: Vec<String> = vec![
+        fl!("numeric-column-none"),
+        fl!("numeric-column-u8"),
+        fl!("numeric-column-i8"),
+        fl!("numeric-column-u16"),
+        fl!("numeric-column-i16"),
+    ];
+    static ref row_checksum_names: Vec<String> = vec![fl!("row-checksum-none"), fl!("row-checksum-sum"), fl!("row-checksum-crc8")];
+    static ref group_size_values: Vec<usize> = vec![4, 8];
+    static ref group_size_names: Vec<String> = group_size_values.iter().map(|group_size| fl!("group-size-bytes", bytes = *group_size)).collect();
     static ref app_themes: Vec<String> = vec![fl!("match-desktop"), fl!("dark"), fl!("light")];
     static ref theme_names: Vec<String> = SYNTAX_SYSTEM.get().unwrap().theme_set.themes.iter().map(|(theme_name, _theme)| theme_name.to_string()).collect();
     static ref font_names: Vec<String> = {
@@ -859,13 +2963,67 @@ lazy_static::lazy_static! {
     };
 }
 
+/// Tried in order when the configured font is not installed.
+const FALLBACK_FONTS: &[&str] = &["Fira Mono", "DejaVu Sans Mono", "Noto Sans Mono", "Liberation Mono"];
+
+/// Makes `font_name` the monospace family, falling back to another installed monospace font if it is missing.
+fn set_monospace_font(font_name: &str) {
+    let installed = |name: &str| font_names.iter().any(|installed| installed == name);
+    let family = if installed(font_name) {
+        font_name
+    } else {
+        let fallback = FALLBACK_FONTS
+            .iter()
+            .copied()
+            .find(|name| installed(name))
+            .or_else(|| font_names.first().map(String::as_str));
+        let Some(fallback) = fallback else {
+            log::warn!("no monospace font installed");
+            return;
+        };
+        log::warn!("font {:?} is not installed, using {:?}", font_name, fallback);
+        fallback
+    };
+    font_system().write().unwrap().raw().db_mut().set_monospace_family(family);
+}
+
+/// How the find pattern is interpreted.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum SearchMode {
+    Hex,
+    Text,
+    Number,
+}
+
+impl SearchMode {
+    const ALL: [SearchMode; 3] = [SearchMode::Hex, SearchMode::Text, SearchMode::Number];
+}
+
+const SEARCH_HISTORY_LEN: usize = 20;
+
+/// Longest selection in bytes that Find fills into the search field.
+const MAX_FIND_PREFILL: usize = 256;
+
 #[derive(Clone, CosmicConfigEntry, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct ConfigState {
     pub recent_files: VecDeque<PathBuf>,
+    /// Pinned files are listed first and never evicted from the recent files.
+    pub pinned_files: Vec<PathBuf>,
+    pub search_history: HashMap<SearchMode, Vec<String>>,
+    /// Folder shown in the sidebar.
+    pub project_root: Option<PathBuf>,
+    /// View settings of files that override the config.
+    pub tab_options: HashMap<PathBuf, tab::TabOptions>,
 }
 
 impl Default for ConfigState {
     fn default() -> Self {
-        Self { recent_files: VecDeque::new() }
+        Self {
+            recent_files: VecDeque::new(),
+            pinned_files: Vec::new(),
+            search_history: HashMap::new(),
+            project_root: None,
+            tab_options: HashMap::new(),
+        }
     }
 }
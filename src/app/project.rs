@@ -0,0 +1,116 @@
+use std::{fs, path::PathBuf};
+
+use cosmic::widget::{icon, nav_bar, segmented_button};
+
+use super::AppModel;
+
+/// An entry of the folder sidebar.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProjectNode {
+    Folder { path: PathBuf, open: bool },
+    File { path: PathBuf },
+}
+
+impl ProjectNode {
+    fn new(path: PathBuf) -> Self {
+        if path.is_dir() {
+            ProjectNode::Folder { path, open: false }
+        } else {
+            ProjectNode::File { path }
+        }
+    }
+
+    fn name(&self) -> String {
+        let path = match self {
+            ProjectNode::Folder { path, .. } | ProjectNode::File { path } => path,
+        };
+        path.file_name()
+            .map_or_else(|| path.display().to_string(), |name| name.to_string_lossy().to_string())
+    }
+
+    fn icon(&self) -> icon::Icon {
+        let name = match self {
+            ProjectNode::Folder { open: true, .. } => "go-down-symbolic",
+            ProjectNode::Folder { open: false, .. } => "go-next-symbolic",
+            ProjectNode::File { .. } => "text-x-generic-symbolic",
+        };
+        icon::from_name(name).size(16).handle().icon()
+    }
+}
+
+/// Lists a folder with sub folders first, hidden entries are skipped.
+fn read_folder(path: &PathBuf) -> Vec<ProjectNode> {
+    let mut nodes = match fs::read_dir(path) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+            .map(|entry| ProjectNode::new(entry.path()))
+            .collect::<Vec<_>>(),
+        Err(err) => {
+            log::error!("failed to read folder {:?}: {}", path, err);
+            Vec::new()
+        }
+    };
+    nodes.sort_by(|a, b| match (a, b) {
+        (ProjectNode::Folder { .. }, ProjectNode::File { .. }) => std::cmp::Ordering::Less,
+        (ProjectNode::File { .. }, ProjectNode::Folder { .. }) => std::cmp::Ordering::Greater,
+        _ => a.name().to_lowercase().cmp(&b.name().to_lowercase()),
+    });
+    nodes
+}
+
+impl AppModel {
+    /// Replaces the sidebar contents with the given folder.
+    pub(crate) fn open_project(&mut self, path: PathBuf) {
+        self.nav_model = nav_bar::Model::default();
+        let root = ProjectNode::Folder {
+            path: path.clone(),
+            open: true,
+        };
+        let root_id = self.nav_model.insert().icon(root.icon()).text(root.name()).data(root).id();
+        self.insert_children(root_id, &path);
+        self.config_state.project_root = Some(path);
+        self.save_config_state();
+        self.core.nav_bar_set_toggled(true);
+    }
+
+    fn insert_children(&mut self, parent: segmented_button::Entity, path: &PathBuf) {
+        let mut position = self.nav_model.position(parent).unwrap_or(0);
+        let indent = self.nav_model.indent(parent).unwrap_or(0) + 1;
+        for node in read_folder(path) {
+            position += 1;
+            self.nav_model
+                .insert()
+                .position(position)
+                .indent(indent)
+                .icon(node.icon())
+                .text(node.name())
+                .data(node);
+        }
+    }
+
+    /// Expands or collapses a folder, or returns the path of a selected file.
+    pub(crate) fn select_project_node(&mut self, id: segmented_button::Entity) -> Option<PathBuf> {
+        match self.nav_model.data::<ProjectNode>(id).cloned()? {
+            ProjectNode::File { path } => Some(path),
+            ProjectNode::Folder { path, open } => {
+                if open {
+                    let position = self.nav_model.position(id).unwrap_or(0);
+                    let indent = self.nav_model.indent(id).unwrap_or(0);
+                    while let Some(child) = self.nav_model.entity_at(position + 1) {
+                        if self.nav_model.indent(child).unwrap_or(0) <= indent {
+                            break;
+                        }
+                        self.nav_model.remove(child);
+                    }
+                } else {
+                    self.insert_children(id, &path);
+                }
+                let node = ProjectNode::Folder { path, open: !open };
+                self.nav_model.icon_set(id, node.icon());
+                self.nav_model.data_set(id, node);
+                None
+            }
+        }
+    }
+}
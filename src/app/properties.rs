@@ -0,0 +1,138 @@
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use chrono::{DateTime, Local};
+use cosmic::{cosmic_theme, widget, Element};
+use md5::Md5;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    fl,
+    hex_view::magic,
+    i18n::{format_number, format_size},
+};
+
+use super::{Action, AppModel};
+
+/// Information shown in the File > Properties dialog. Hashes are only
+/// computed on request since they need a full pass over the data.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FileProperties {
+    pub path: PathBuf,
+    pub size: usize,
+    pub permissions: Option<String>,
+    pub owner: Option<String>,
+    pub modified: Option<String>,
+    pub accessed: Option<String>,
+    pub created: Option<String>,
+    pub file_type: Option<String>,
+    pub hashes: Option<Vec<(String, String)>>,
+}
+
+impl FileProperties {
+    pub fn new(path: &Path, data: &[u8]) -> Self {
+        let metadata = std::fs::metadata(path).ok();
+        let timestamp = |time: std::io::Result<SystemTime>| time.ok().map(|time| DateTime::<Local>::from(time).format("%Y-%m-%d %H:%M:%S").to_string());
+        Self {
+            path: path.to_path_buf(),
+            size: data.len(),
+            permissions: metadata.as_ref().map(format_permissions),
+            owner: metadata.as_ref().and_then(format_owner),
+            modified: metadata.as_ref().and_then(|metadata| timestamp(metadata.modified())),
+            accessed: metadata.as_ref().and_then(|metadata| timestamp(metadata.accessed())),
+            created: metadata.as_ref().and_then(|metadata| timestamp(metadata.created())),
+            file_type: magic::detect(data).map(|(mime, description)| format!("{description} ({mime})")),
+            hashes: None,
+        }
+    }
+}
+
+#[cfg(unix)]
+fn format_permissions(metadata: &std::fs::Metadata) -> String {
+    use std::os::unix::fs::PermissionsExt;
+    let mode = metadata.permissions().mode();
+    let mut flags = String::with_capacity(9);
+    for shift in [6, 3, 0] {
+        let bits = (mode >> shift) & 0o7;
+        flags.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+        flags.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+        flags.push(if bits & 0o1 != 0 { 'x' } else { '-' });
+    }
+    format!("{flags} ({:04o})", mode & 0o7777)
+}
+
+#[cfg(not(unix))]
+fn format_permissions(metadata: &std::fs::Metadata) -> String {
+    if metadata.permissions().readonly() {
+        fl!("properties-read-only")
+    } else {
+        fl!("properties-read-write")
+    }
+}
+
+#[cfg(unix)]
+fn format_owner(metadata: &std::fs::Metadata) -> Option<String> {
+    use std::os::unix::fs::MetadataExt;
+    Some(format!("{}:{}", metadata.uid(), metadata.gid()))
+}
+
+#[cfg(not(unix))]
+fn format_owner(_metadata: &std::fs::Metadata) -> Option<String> {
+    None
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+pub fn compute_hashes(data: &[u8]) -> Vec<(String, String)> {
+    vec![
+        ("CRC-32".to_string(), format!("{:08x}", crc32fast::hash(data))),
+        ("MD5".to_string(), to_hex(&Md5::digest(data))),
+        ("SHA-1".to_string(), to_hex(&Sha1::digest(data))),
+        ("SHA-256".to_string(), to_hex(&Sha256::digest(data))),
+    ]
+}
+
+impl AppModel {
+    pub(crate) fn properties_dialog<'a>(&'a self, properties: &'a FileProperties) -> Element<'a, Action> {
+        let cosmic_theme::Spacing { space_xxs, .. } = self.core().system_theme().cosmic().spacing;
+        let unknown = fl!("properties-unknown");
+        let mut section = widget::settings::section()
+            .add(widget::settings::item::builder(fl!("properties-location")).control(widget::text(properties.path.display().to_string())))
+            .add(widget::settings::item::builder(fl!("properties-size")).control(widget::text(fl!(
+                "properties-bytes",
+                size = format_size(properties.size as u64),
+                bytes = format_number(properties.size as u64)
+            ))))
+            .add(widget::settings::item::builder(fl!("properties-type")).control(widget::text(properties.file_type.clone().unwrap_or(unknown.clone()))))
+            .add(
+                widget::settings::item::builder(fl!("properties-permissions")).control(widget::text(properties.permissions.clone().unwrap_or(unknown.clone()))),
+            )
+            .add(widget::settings::item::builder(fl!("properties-owner")).control(widget::text(properties.owner.clone().unwrap_or(unknown.clone()))))
+            .add(widget::settings::item::builder(fl!("properties-modified")).control(widget::text(properties.modified.clone().unwrap_or(unknown.clone()))))
+            .add(widget::settings::item::builder(fl!("properties-accessed")).control(widget::text(properties.accessed.clone().unwrap_or(unknown.clone()))))
+            .add(widget::settings::item::builder(fl!("properties-created")).control(widget::text(properties.created.clone().unwrap_or(unknown))));
+
+        let mut column = widget::column::with_capacity(2).spacing(space_xxs);
+        match &properties.hashes {
+            Some(hashes) => {
+                for (name, value) in hashes {
+                    section = section.add(widget::settings::item::builder(name.clone()).control(widget::text(value.clone()).font(cosmic::font::mono())));
+                }
+                column = column.push(section);
+            }
+            None => {
+                column = column.push(section);
+                column = column.push(widget::button::standard(fl!("properties-compute-hashes")).on_press(Action::ComputeHashes));
+            }
+        }
+
+        widget::dialog::Dialog::new()
+            .title(fl!("properties-title"))
+            .control(widget::scrollable(column))
+            .primary_action(widget::button::suggested(fl!("close")).on_press(Action::DialogCancel))
+            .into()
+    }
+}
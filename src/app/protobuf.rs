@@ -0,0 +1,128 @@
+use crate::{
+    fl,
+    hex_view::{
+        export,
+        protobuf::{ProtoField, ProtoValue},
+        selection::Selection,
+        Message,
+    },
+};
+use cosmic::{
+    iced::{Alignment, Padding},
+    widget, Element,
+};
+use serde_json::json;
+
+use super::{Action, AppModel};
+
+fn wire_type_name(wire_type: u8) -> &'static str {
+    match wire_type {
+        0 => "varint",
+        1 => "i64",
+        2 => "len",
+        5 => "i32",
+        _ => "?",
+    }
+}
+
+/// The payload as hex pairs, empty if the data changed since decoding.
+fn payload_hex(field: &ProtoField, data: &[u8]) -> String {
+    data.get(field.payload.clone()).map(export::to_hex).unwrap_or_default()
+}
+
+/// The value shown for a field, bytes as hex pairs.
+pub(super) fn field_value(field: &ProtoField, data: &[u8]) -> String {
+    match &field.value {
+        ProtoValue::Varint(v) => v.to_string(),
+        ProtoValue::Fixed64(v) => format!("0x{:016X}", v),
+        ProtoValue::Fixed32(v) => format!("0x{:08X}", v),
+        ProtoValue::Bytes | ProtoValue::Message(_) => payload_hex(field, data),
+    }
+}
+
+pub(super) fn field_json(field: &ProtoField, data: &[u8]) -> serde_json::Value {
+    let value = match &field.value {
+        ProtoValue::Varint(v) | ProtoValue::Fixed64(v) => json!(v),
+        ProtoValue::Fixed32(v) => json!(v),
+        ProtoValue::Bytes => json!(payload_hex(field, data)),
+        ProtoValue::Message(children) => fields_json(children, data),
+    };
+    json!({
+        "number": field.number,
+        "wire_type": wire_type_name(field.wire_type),
+        "offset": field.range.start,
+        "length": field.range.len(),
+        "value": value,
+    })
+}
+
+pub(super) fn fields_json(fields: &[ProtoField], data: &[u8]) -> serde_json::Value {
+    serde_json::Value::Array(fields.iter().map(|field| field_json(field, data)).collect())
+}
+
+/// The field at `path`, a list of child indices starting at the top level.
+pub(super) fn find_field<'a>(fields: &'a [ProtoField], path: &[usize]) -> Option<&'a ProtoField> {
+    let (first, rest) = path.split_first()?;
+    let field = fields.get(*first)?;
+    if rest.is_empty() {
+        return Some(field);
+    }
+    match &field.value {
+        ProtoValue::Message(children) => find_field(children, rest),
+        _ => None,
+    }
+}
+
+impl AppModel {
+    /// Tree of the fields decoded by the last "Decode as Protobuf" run.
+    pub(crate) fn protobuf_view(&self) -> Element<Action> {
+        let Some(fields) = &self.protobuf_fields else {
+            return widget::text::body(fl!("protobuf-invalid")).into();
+        };
+        let mut column = widget::column::with_capacity(fields.len() + 1);
+        column = column.push(widget::button::text(fl!("copy-all-as-json")).on_press(Action::CopyProtoField(Vec::new(), true)));
+        for (i, field) in fields.iter().enumerate() {
+            column = Self::push_proto_field(column, field, vec![i]);
+        }
+        widget::scrollable(column).into()
+    }
+
+    fn push_proto_field<'a>(mut column: widget::Column<'a, Action>, field: &ProtoField, path: Vec<usize>) -> widget::Column<'a, Action> {
+        let depth = path.len() - 1;
+        let value = match &field.value {
+            ProtoValue::Varint(v) => v.to_string(),
+            ProtoValue::Fixed64(v) => format!("0x{:016X}", v),
+            ProtoValue::Fixed32(v) => format!("0x{:08X}", v),
+            ProtoValue::Bytes => fl!("protobuf-bytes", len = field.payload.len()),
+            ProtoValue::Message(children) => fl!("protobuf-message", count = children.len()),
+        };
+        let label = format!(
+            "#{} {}: {}  [{:08X}–{:08X}]",
+            field.number,
+            wire_type_name(field.wire_type),
+            value,
+            field.range.start,
+            field.range.end - 1
+        );
+        let selection = Selection::new(field.range.start, field.range.end - 1);
+        let row = widget::row::with_children(vec![
+            widget::button::text(label).on_press(Action::HexAction(Message::Select(selection))).into(),
+            widget::button::text(fl!("copy-value"))
+                .on_press(Action::CopyProtoField(path.clone(), false))
+                .into(),
+            widget::button::text(fl!("copy-as-json"))
+                .on_press(Action::CopyProtoField(path.clone(), true))
+                .into(),
+        ])
+        .align_y(Alignment::Center);
+        column = column.push(widget::container(row).padding(Padding::ZERO.left(depth as f32 * 16.0)));
+        if let ProtoValue::Message(children) = &field.value {
+            for (i, child) in children.iter().enumerate() {
+                let mut child_path = path.clone();
+                child_path.push(i);
+                column = Self::push_proto_field(column, child, child_path);
+            }
+        }
+        column
+    }
+}
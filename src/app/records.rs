@@ -0,0 +1,51 @@
+use cosmic::{cosmic_theme, widget, Element};
+
+use crate::{
+    fl,
+    hex_view::{parse_offset, HexView},
+};
+
+use super::{Action, AppModel};
+
+/// Record size and record to jump to, as typed in the records dialog.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Records {
+    pub size: String,
+    pub record: String,
+}
+
+impl Records {
+    /// Starts with the tab's record size and the record under the caret.
+    pub fn new(hex_view: &HexView) -> Self {
+        let offset = hex_view.cursor.position / 2;
+        Self {
+            size: hex_view.record_size.map_or_else(String::new, |size| size.to_string()),
+            record: hex_view.record_size.map_or_else(String::new, |size| (offset / size).to_string()),
+        }
+    }
+
+    /// The entered record size, `None` for an empty field or zero.
+    pub fn size(&self) -> Option<usize> {
+        parse_offset(&self.size).filter(|size| *size > 0)
+    }
+}
+
+impl AppModel {
+    pub(crate) fn records_dialog<'a>(&'a self, records: &'a Records) -> Element<'a, Action> {
+        let cosmic_theme::Spacing { space_xxs, .. } = self.core().system_theme().cosmic().spacing;
+        let control = widget::column::with_capacity(2)
+            .spacing(space_xxs)
+            .push(widget::settings::item::builder(fl!("record-size")).control(widget::text_input::text_input("", &records.size).on_input(Action::RecordsSize)))
+            .push(
+                widget::settings::item::builder(fl!("go-to-record")).control(widget::text_input::text_input("", &records.record).on_input(Action::RecordsGoTo)),
+            );
+
+        widget::dialog::Dialog::new()
+            .title(fl!("records-title"))
+            .body(fl!("records-body"))
+            .control(control)
+            .primary_action(widget::button::suggested(fl!("apply")).on_press(Action::RecordsConfirm))
+            .secondary_action(widget::button::text(fl!("cancel")).on_press(Action::DialogCancel))
+            .into()
+    }
+}
@@ -0,0 +1,61 @@
+use cosmic::{cosmic_theme, widget, Element};
+
+use crate::{
+    fl,
+    hex_view::{parse_offset, HexView},
+};
+
+use super::{Action, AppModel};
+
+/// New length and fill byte, as typed in the resize dialog.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Resize {
+    pub length: String,
+    pub fill: String,
+}
+
+impl Resize {
+    /// Starts with the current length and zero fill.
+    pub fn new(hex_view: &HexView) -> Self {
+        Self {
+            length: hex_view.buffer.as_ref().map_or(0, |buffer| buffer.len()).to_string(),
+            fill: "00".to_string(),
+        }
+    }
+
+    pub fn length(&self) -> Option<usize> {
+        parse_offset(&self.length)
+    }
+
+    /// The fill byte in hex, an empty field fills with zero.
+    pub fn fill(&self) -> Option<u8> {
+        let fill = self.fill.trim();
+        let fill = fill.strip_prefix("0x").unwrap_or(fill);
+        if fill.is_empty() {
+            return Some(0);
+        }
+        u8::from_str_radix(fill, 16).ok()
+    }
+}
+
+impl AppModel {
+    pub(crate) fn resize_dialog<'a>(&'a self, resize: &'a Resize) -> Element<'a, Action> {
+        let cosmic_theme::Spacing { space_xxs, .. } = self.core().system_theme().cosmic().spacing;
+        let control = widget::column::with_capacity(2)
+            .spacing(space_xxs)
+            .push(
+                widget::settings::item::builder(fl!("resize-length"))
+                    .control(widget::text_input::text_input("", &resize.length).on_input(Action::ResizeLength)),
+            )
+            .push(widget::settings::item::builder(fl!("resize-fill")).control(widget::text_input::text_input("00", &resize.fill).on_input(Action::ResizeFill)));
+
+        let valid = resize.length().is_some() && resize.fill().is_some();
+        widget::dialog::Dialog::new()
+            .title(fl!("resize-title"))
+            .body(fl!("resize-body"))
+            .control(control)
+            .primary_action(widget::button::suggested(fl!("apply")).on_press_maybe(valid.then_some(Action::ResizeConfirm)))
+            .secondary_action(widget::button::text(fl!("cancel")).on_press(Action::DialogCancel))
+            .into()
+    }
+}
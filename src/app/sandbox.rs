@@ -0,0 +1,35 @@
+//! Keeps files opened inside a Flatpak sandbox reachable in later sessions.
+
+use std::path::{Path, PathBuf};
+
+use ashpd::documents::Documents;
+
+/// Inside a Flatpak only files the user picked are accessible, through the document portal.
+pub fn is_sandboxed() -> bool {
+    Path::new("/.flatpak-info").exists()
+}
+
+/// Adds `path` to the document store with a persistent grant for this app and returns its path
+/// below the document portal mount, which stays valid across sessions.
+pub async fn persist(path: PathBuf) -> Option<PathBuf> {
+    let name = path.file_name()?.to_owned();
+    let file = match std::fs::File::open(&path) {
+        Ok(file) => file,
+        Err(err) => {
+            log::warn!("failed to open {:?} for the document portal: {}", path, err);
+            return None;
+        }
+    };
+    let result = async {
+        let documents = Documents::new().await?;
+        documents.add(&file, true, true).await
+    }
+    .await;
+    match result {
+        Ok(id) => Some(dirs::runtime_dir()?.join("doc").join(id.to_string()).join(name)),
+        Err(err) => {
+            log::warn!("failed to add {:?} to the document portal: {}", path, err);
+            None
+        }
+    }
+}
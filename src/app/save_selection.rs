@@ -0,0 +1,47 @@
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+/// Bytes written between progress updates.
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+/// A selection being written to a file in the background.
+#[derive(Clone, Debug)]
+pub struct SaveSelection {
+    pub path: PathBuf,
+    pub total: usize,
+    pub written: Arc<AtomicUsize>,
+}
+
+impl SaveSelection {
+    pub fn new(path: PathBuf, total: usize) -> Self {
+        Self {
+            path,
+            total,
+            written: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Percentage written so far.
+    pub fn percent(&self) -> usize {
+        if self.total == 0 {
+            return 100;
+        }
+        self.written.load(Ordering::Relaxed) * 100 / self.total
+    }
+}
+
+/// Writes `data` to `path` in chunks, counting the written bytes in `written`.
+pub fn write(path: &Path, data: &[u8], written: &AtomicUsize) -> std::io::Result<()> {
+    let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+    for chunk in data.chunks(CHUNK_SIZE) {
+        file.write_all(chunk)?;
+        written.fetch_add(chunk.len(), Ordering::Relaxed);
+    }
+    file.flush()
+}
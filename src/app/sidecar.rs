@@ -0,0 +1,146 @@
+use std::path::{Path, PathBuf};
+
+use md5::Md5;
+use sha2::{Digest, Sha256};
+
+/// Checksum files written by `sha256sum`/`md5sum` next to the file they describe.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HashKind {
+    Sha256,
+    Md5,
+}
+
+impl HashKind {
+    const EXTENSIONS: [(&'static str, HashKind); 4] = [
+        ("sha256", HashKind::Sha256),
+        ("sha256sum", HashKind::Sha256),
+        ("md5", HashKind::Md5),
+        ("md5sum", HashKind::Md5),
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            HashKind::Sha256 => "SHA-256",
+            HashKind::Md5 => "MD5",
+        }
+    }
+
+    fn compute(self, data: &[u8]) -> String {
+        let digest = match self {
+            HashKind::Sha256 => Sha256::digest(data).to_vec(),
+            HashKind::Md5 => Md5::digest(data).to_vec(),
+        };
+        digest.iter().map(|b| format!("{b:02x}")).collect()
+    }
+}
+
+/// Result of checking data against its sidecar file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Verification {
+    pub sidecar: PathBuf,
+    pub kind: HashKind,
+    /// The hash from the sidecar, `None` if it couldn't be read.
+    pub expected: Option<String>,
+    pub actual: String,
+}
+
+impl Verification {
+    pub fn matches(&self) -> bool {
+        self.expected.as_deref().is_some_and(|expected| expected.eq_ignore_ascii_case(&self.actual))
+    }
+}
+
+/// The first existing sidecar of `path`, e.g. `image.iso.sha256`.
+pub fn find(path: &Path) -> Option<(PathBuf, HashKind)> {
+    HashKind::EXTENSIONS.iter().find_map(|(extension, kind)| {
+        let mut sidecar = path.as_os_str().to_owned();
+        sidecar.push(".");
+        sidecar.push(extension);
+        let sidecar = PathBuf::from(sidecar);
+        sidecar.is_file().then_some((sidecar, *kind))
+    })
+}
+
+/// Hash and file name of every entry of a checksum file, with the index of its line.
+fn entries(text: &str) -> Vec<(usize, &str, &str)> {
+    text.lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let mut parts = line.split_whitespace();
+            Some((i, parts.next()?, parts.next().unwrap_or_default().trim_start_matches('*')))
+        })
+        .collect()
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default()
+}
+
+/// Hashes `data` and compares it with the hash in `sidecar`. The sidecar may list several files,
+/// the line naming `path` wins, otherwise the first hash is used.
+pub fn verify(sidecar: PathBuf, kind: HashKind, path: &Path, data: &[u8]) -> Verification {
+    let name = file_name(path);
+    let expected = std::fs::read_to_string(&sidecar).ok().and_then(|text| {
+        let entries = entries(&text);
+        let entry = entries.iter().find(|(_, _, file)| *file == name).or(entries.first())?;
+        Some(entry.1.to_string())
+    });
+    Verification {
+        sidecar,
+        kind,
+        expected,
+        actual: kind.compute(data),
+    }
+}
+
+/// Writes the hash of `data` into `sidecar`, keeping the entries of other files.
+pub fn regenerate(sidecar: &Path, kind: HashKind, path: &Path, data: &[u8]) -> std::io::Result<()> {
+    let text = match std::fs::read_to_string(sidecar) {
+        Ok(text) => text,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err),
+    };
+    std::fs::write(sidecar, update_entry(&text, &file_name(path), &kind.compute(data)))
+}
+
+/// Replaces the hash of the entry for `name`, or of the only entry of a file listing a single hash.
+/// Other lines stay as they are, without an entry one is added in the `sha256sum` format.
+fn update_entry(text: &str, name: &str, hash: &str) -> String {
+    let entries = entries(text);
+    let entry = entries
+        .iter()
+        .find(|(_, _, file)| *file == name)
+        .or_else(|| entries.first().filter(|_| entries.len() == 1));
+    let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+    match entry {
+        Some((i, old, _)) => lines[*i] = lines[*i].replacen(old, hash, 1),
+        None => lines.push(format!("{}  {}", hash, name)),
+    }
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update_entry_keeps_other_files() {
+        let text = "aaaa  first.bin\nbbbb *second.bin\ncccc  third.bin\n";
+        assert_eq!(update_entry(text, "second.bin", "ffff"), "aaaa  first.bin\nffff *second.bin\ncccc  third.bin\n");
+    }
+
+    #[test]
+    fn update_entry_replaces_a_single_hash() {
+        assert_eq!(update_entry("aaaa  old-name.bin\n", "file.bin", "ffff"), "ffff  old-name.bin\n");
+        assert_eq!(update_entry("aaaa\n", "file.bin", "ffff"), "ffff\n");
+    }
+
+    #[test]
+    fn update_entry_adds_missing_entries() {
+        assert_eq!(
+            update_entry("aaaa  a.bin\nbbbb  b.bin\n", "c.bin", "ffff"),
+            "aaaa  a.bin\nbbbb  b.bin\nffff  c.bin\n"
+        );
+        assert_eq!(update_entry("", "c.bin", "ffff"), "ffff  c.bin\n");
+    }
+}
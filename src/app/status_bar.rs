@@ -0,0 +1,189 @@
+use crate::{
+    config::StatusField,
+    fl,
+    hex_view::{executable::Image, EditMode, HexView, Message},
+    i18n::{format_float, format_number, format_signed, format_size},
+};
+use cosmic::{iced::Length, widget, Element};
+
+use super::{Action, AppModel};
+
+/// The label shown for a status field in the settings.
+pub(crate) fn field_name(field: StatusField) -> String {
+    match field {
+        StatusField::OffsetHex => fl!("field-offset-hex"),
+        StatusField::OffsetDec => fl!("field-offset-dec"),
+        StatusField::U8 => fl!("field-u8"),
+        StatusField::U16 => fl!("field-u16"),
+        StatusField::U32 => fl!("field-u32"),
+        StatusField::U64 => fl!("field-u64"),
+        StatusField::I32 => fl!("field-i32"),
+        StatusField::F32 => fl!("field-f32"),
+        StatusField::Binary => fl!("field-binary"),
+        StatusField::Selection => fl!("field-selection"),
+        StatusField::SelectionLength => fl!("field-selection-length"),
+        StatusField::FileSize => fl!("field-file-size"),
+        StatusField::Encoding => fl!("field-encoding"),
+        StatusField::EditMode => fl!("field-edit-mode"),
+    }
+}
+
+impl AppModel {
+    /// The status row below the hex view showing the fields enabled in the config.
+    pub(crate) fn status_bar<'a>(&'a self, hex_view: &'a HexView) -> Element<'a, Action> {
+        let offset = hex_view.cursor.position / 2;
+        let mut items: Vec<Element<Action>> = Vec::new();
+
+        for field in &self.config.status_fields {
+            if *field == StatusField::EditMode {
+                if !items.is_empty() {
+                    items.push(widget::text::body("\t").into());
+                }
+                items.extend(Self::edit_mode_segments(hex_view));
+            } else if let Some(text) = Self::status_field(hex_view, *field, offset) {
+                if !items.is_empty() {
+                    items.push(widget::text::body("\t").into());
+                }
+                items.push(widget::text::body(text).into());
+            }
+        }
+
+        if let Some(image) = &hex_view.image {
+            if let Some(text) = Self::symbol_location(image, offset) {
+                if !items.is_empty() {
+                    items.push(widget::text::body("\t").into());
+                }
+                items.push(widget::text::body(text).into());
+            }
+        }
+
+        if let Some(region) = hex_view.region_at(offset) {
+            if !items.is_empty() {
+                items.push(widget::text::body("\t").into());
+            }
+            let name = format!("{}+0x{:X}", region.name, offset - region.range.start);
+            items.push(widget::text::body(fl!("status-region", name = name)).into());
+        }
+
+        if let Some(record_size) = hex_view.record_size {
+            if !items.is_empty() {
+                items.push(widget::text::body("\t").into());
+            }
+            let text = fl!(
+                "status-record",
+                record = format_number((offset / record_size) as u64),
+                offset = format!("{:X}", offset % record_size)
+            );
+            items.push(widget::text::body(text).into());
+        }
+
+        if let Some(mark) = hex_view.mark {
+            if !items.is_empty() {
+                items.push(widget::text::body("\t").into());
+            }
+            items.push(widget::text::body(Self::mark_distance(mark, offset)).into());
+        }
+
+        if let Some(save) = &self.save_selection {
+            if !items.is_empty() {
+                items.push(widget::text::body("\t").into());
+            }
+            items.push(widget::text::body(fl!("status-save-selection", percent = save.percent().to_string())).into());
+        }
+
+        widget::row::with_children(items).height(Length::Shrink).into()
+    }
+
+    /// Section and symbol containing `offset`, for ELF and PE files.
+    fn symbol_location(image: &Image, offset: usize) -> Option<String> {
+        let section = image.section_at(offset).map(|section| fl!("status-section", name = section.name.as_str()));
+        let symbol = image
+            .symbol_at(offset)
+            .map(|symbol| fl!("status-symbol", name = format!("{}+0x{:X}", symbol.name, offset - symbol.range.start)));
+        match (section, symbol) {
+            (Some(section), Some(symbol)) => Some(format!("{}\t{}", section, symbol)),
+            (section, symbol) => section.or(symbol),
+        }
+    }
+
+    /// The mark and the signed distance from it to the caret.
+    fn mark_distance(mark: usize, offset: usize) -> String {
+        let (sign, distance) = if offset >= mark { ("", offset - mark) } else { ("-", mark - offset) };
+        fl!(
+            "status-mark",
+            mark = format!("{:08X}", mark),
+            hex = format!("{}0x{:X}", sign, distance),
+            dec = format!("{}{}", sign, format_number(distance as u64))
+        )
+    }
+
+    /// Pane, insert/overwrite and read-only state as buttons that toggle the respective mode.
+    fn edit_mode_segments(hex_view: &HexView) -> [Element<'static, Action>; 3] {
+        let pane = match hex_view.cursor.in_hex {
+            EditMode::Hex => "HEX",
+            EditMode::Ascii => "ASCII",
+        };
+        let insert = if hex_view.insert_mode {
+            fl!("status-insert")
+        } else {
+            fl!("status-overwrite")
+        };
+        let read_only = if hex_view.read_only {
+            fl!("status-read-only")
+        } else {
+            fl!("status-read-write")
+        };
+        [
+            widget::button::text(pane).on_press(Action::HexAction(Message::SwitchMode)).into(),
+            widget::button::text(insert).on_press(Action::HexAction(Message::ToggleInsertMode)).into(),
+            widget::button::text(read_only).on_press(Action::HexAction(Message::ToggleReadOnly)).into(),
+        ]
+    }
+
+    fn status_field(hex_view: &HexView, field: StatusField, offset: usize) -> Option<String> {
+        let buffer = hex_view.buffer.as_ref()?;
+        let endian = hex_view.endian;
+        let text = match field {
+            StatusField::OffsetHex => fl!("status-offset", offset = format!("{:08X}", offset)),
+            StatusField::OffsetDec => fl!("status-offset", offset = format_number(offset as u64)),
+            StatusField::U8 => fl!("status-u8", value = format_number(buffer.get_uint(offset, 1, endian)?)),
+            StatusField::U16 => fl!("status-u16", value = format_number(buffer.get_uint(offset, 2, endian)?)),
+            StatusField::U32 => fl!("status-uint", value = format_number(buffer.get_uint(offset, 4, endian)?)),
+            StatusField::U64 => fl!("status-u64", value = format_number(buffer.get_uint(offset, 8, endian)?)),
+            StatusField::I32 => fl!("status-i32", value = format_signed(buffer.get_int(offset, 4, endian)?)),
+            StatusField::F32 => fl!("status-f32", value = format_float(buffer.get_f32(offset, endian)?)),
+            StatusField::Binary => fl!("status-binary", value = format!("{:08b}", buffer.get_uint(offset, 1, endian)?)),
+            StatusField::Selection => {
+                let selection = hex_view.selection?;
+                // Computed in the background, see `AppModel::update_selection_stats`.
+                let stats = match hex_view.current_stats() {
+                    Some((sum, min, max)) => fl!(
+                        "status-stats",
+                        sum = format_number(sum),
+                        min = format!("{:02X}", min),
+                        max = format!("{:02X}", max)
+                    ),
+                    None => "…".to_string(),
+                };
+                format!(
+                    "{}\t{}",
+                    fl!(
+                        "status-selection",
+                        start = format!("{:08X}", selection.start()),
+                        end = format!("{:08X}", selection.end())
+                    ),
+                    stats
+                )
+            }
+            StatusField::SelectionLength => {
+                let len = hex_view.selection?.len();
+                fl!("status-length", hex = format!("{:X}", len), dec = format_number(len as u64))
+            }
+            StatusField::FileSize => fl!("status-file-size", size = format_size(buffer.len() as u64)),
+            StatusField::Encoding => fl!("status-encoding", encoding = hex_view.encoding.name()),
+            // Rendered as buttons by `edit_mode_segments`.
+            StatusField::EditMode => return None,
+        };
+        Some(text)
+    }
+}
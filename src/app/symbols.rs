@@ -0,0 +1,59 @@
+use cosmic::{iced::Length, widget, Element};
+
+use crate::{fl, hex_view::executable::Region};
+
+use super::{tab::Tab, Action, AppModel};
+
+/// Symbols listed at most, narrow the list down with the filter.
+const MAX_SYMBOLS: usize = 500;
+
+fn region_button(region: &Region) -> Element<'static, Action> {
+    widget::button::text(format!("{:08X}  {}", region.range.start, region.name))
+        .on_press(Action::JumpTo(region.range.start))
+        .width(Length::Fill)
+        .into()
+}
+
+impl AppModel {
+    /// Sections and symbols of an ELF or PE file and imported regions, clicking one jumps to it.
+    pub(crate) fn symbols_view(&self) -> Element<Action> {
+        let Some(Tab::Editor(tab)) = self.tab_model.data::<Tab>(self.tab_model.active()) else {
+            return widget::text::body(fl!("symbols-none")).into();
+        };
+        let (image, regions) = (tab.hex_view.image.as_ref(), &tab.hex_view.regions);
+        if image.is_none() && regions.is_empty() {
+            return widget::text::body(fl!("symbols-none")).into();
+        }
+
+        let filter = self.symbol_filter.to_lowercase();
+        let matching = |region: &&Region| region.name.to_lowercase().contains(&filter);
+        let mut column = Vec::new();
+        if let Some(image) = image {
+            let mut sections = widget::settings::section().title(fl!("symbols-sections"));
+            for section in &image.sections {
+                sections = sections.add(region_button(section));
+            }
+            column.push(sections.into());
+        }
+        column.push(
+            widget::text_input::text_input(fl!("symbols-filter"), &self.symbol_filter)
+                .on_input(Action::SymbolFilter)
+                .into(),
+        );
+        if let Some(image) = image {
+            let mut symbols = widget::settings::section().title(fl!("symbols"));
+            for symbol in image.symbols.iter().filter(matching).take(MAX_SYMBOLS) {
+                symbols = symbols.add(region_button(symbol));
+            }
+            column.push(symbols.into());
+        }
+        if !regions.is_empty() {
+            let mut imported = widget::settings::section().title(fl!("symbols-regions"));
+            for region in regions.iter().filter(matching).take(MAX_SYMBOLS) {
+                imported = imported.add(region_button(region));
+            }
+            column.push(imported.into());
+        }
+        widget::settings::view_column(column).into()
+    }
+}
@@ -1,5 +1,19 @@
-use crate::{hex_view::HexView, SYNTAX_SYSTEM};
-use cosmic::{iced::Point, widget::Icon};
+use crate::{
+    hex_view::{
+        arch::Architecture,
+        buffer::Endian,
+        highlight::{parse_color, Layer},
+        HexView,
+    },
+    SYNTAX_SYSTEM,
+};
+use cosmic::{
+    iced::{font::Family, Font, Point},
+    widget::Icon,
+};
+
+use super::{accessibility, sidecar, text_view::TextCache};
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 pub enum Tab {
@@ -15,16 +29,52 @@ impl Tab {
     }
 }
 
+/// View settings of one file that override the config, remembered across sessions.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TabOptions {
+    /// Fixed bytes per row, `None` fits as many as the window is wide.
+    pub bytes_per_row: Option<usize>,
+    pub group_size: Option<usize>,
+    /// Label of the text pane encoding.
+    pub encoding: Option<String>,
+    /// Added to the offsets shown in the gutter.
+    pub base_address: u64,
+    pub endian: Option<Endian>,
+    /// Instruction set the NOP fill shortcut writes.
+    pub architecture: Option<Architecture>,
+}
+
 pub struct EditorTab {
     pub hex_view: HexView,
-    pub _context_menu: Option<Point>,
+    /// Position of the context menu opened by a touch long press.
+    pub context_menu: Option<Point>,
+    /// Shows the decoded text instead of the hex view.
+    pub text_view: bool,
+    pub text_cache: std::cell::RefCell<TextCache>,
+    /// Settings overriding the config for this tab.
+    pub options: TabOptions,
+    /// Shows the [`TabOptions`] popover.
+    pub options_open: bool,
+    /// Base address as typed in the options popover.
+    pub base_address_input: String,
+    /// Font family for this tab, `None` uses the configured monospace font.
+    pub font_override: Option<&'static str>,
+    /// The checksum file next to the file, looked up when it is opened or saved.
+    pub sidecar: Option<(PathBuf, sidecar::HashKind)>,
 }
 
 impl EditorTab {
     pub(crate) fn new(path: PathBuf, buf: crate::hex_view::buffer::DataBuffer) -> Self {
         Self {
+            sidecar: sidecar::find(&path),
             hex_view: HexView::new(path, buf),
-            _context_menu: None,
+            context_menu: None,
+            text_view: false,
+            text_cache: Default::default(),
+            options: TabOptions::default(),
+            options_open: false,
+            base_address_input: String::new(),
+            font_override: None,
         }
     }
 
@@ -46,11 +96,63 @@ impl EditorTab {
             self.hex_view.theme.hex = convert_color(theme.settings.foreground);
             self.hex_view.theme.ascii = convert_color(theme.settings.foreground);
         }
+        self.apply_palette(cosmic::theme::active().cosmic(), config);
 
-        self.hex_view.font_size = config.font_size as f32;
+        // A new font size or row width moves the rows, the caret stays on its screen row.
+        let font_size = config.font_size as f32;
+        if self.hex_view.font_size != font_size || self.hex_view.bytes_per_row != self.options.bytes_per_row {
+            self.hex_view.anchor_caret();
+        }
+
+        self.hex_view.scroll_lines = config.scroll_lines;
+        self.hex_view.group_size = self.options.group_size.unwrap_or(config.group_size);
+        self.hex_view.endian = self.options.endian.unwrap_or(config.endian);
+        self.hex_view.bytes_per_row = self.options.bytes_per_row;
+        self.hex_view.base_address = self.options.base_address;
+        if let Some(encoding) = self
+            .options
+            .encoding
+            .as_deref()
+            .and_then(|label| encoding_rs::Encoding::for_label(label.as_bytes()))
+        {
+            self.hex_view.encoding = encoding;
+        }
+        self.hex_view.smooth_scrolling = config.smooth_scrolling && !accessibility::reduce_motion(config);
+        self.hex_view.row_checksum = config.row_checksum;
+        self.hex_view.numeric_column = config.numeric_column;
+        self.hex_view.double_width_text = config.double_width_text;
+        self.hex_view.group_gaps = config.group_gaps;
+        self.hex_view.set_panes(config.show_hex_pane, config.show_text_pane);
+        self.hex_view.font = self.font_override.map_or(Font::MONOSPACE, |name| Font {
+            family: Family::Name(name),
+            ..Font::MONOSPACE
+        });
+        self.hex_view.font_size = font_size;
         self.hex_view.update_font();
         self.hex_view.redraw();
     }
+
+    /// Follows a change of the desktop theme, e.g. a new accent color or dark mode.
+    pub(crate) fn set_palette(&mut self, theme: &cosmic::cosmic_theme::Theme, config: &crate::config::Config) {
+        self.apply_palette(theme, config);
+        self.hex_view.redraw();
+    }
+
+    /// Colors from the desktop theme, or the high contrast theme, and the highlight layer colors.
+    fn apply_palette(&mut self, theme: &cosmic::cosmic_theme::Theme, config: &crate::config::Config) {
+        self.hex_view.theme.apply_palette(theme);
+        if config.high_contrast || theme.is_high_contrast {
+            self.hex_view.theme.apply_high_contrast();
+        }
+        for layer in Layer::ALL {
+            let color = config
+                .layer_colors
+                .get(&layer)
+                .and_then(|color| parse_color(color))
+                .unwrap_or_else(|| layer.default_color(&self.hex_view.theme));
+            self.hex_view.highlights.set_style(layer, color, !config.hidden_layers.contains(&layer));
+        }
+    }
 }
 
 fn convert_color(background: Option<syntect::highlighting::Color>) -> cosmic::iced::Color {
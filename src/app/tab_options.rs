@@ -0,0 +1,91 @@
+use cosmic::{cosmic_theme, iced::Length, widget, Element};
+
+use crate::{
+    fl,
+    hex_view::{arch::Architecture, buffer::Endian},
+};
+
+use super::{tab::EditorTab, text_view::encoding_names, Action, AppModel};
+
+impl AppModel {
+    /// Popover with the view settings that override the config for one tab.
+    pub(crate) fn tab_options_view<'a>(&'a self, tab: &'a EditorTab) -> Element<'a, Action> {
+        let cosmic_theme::Spacing { space_xxs, space_s, .. } = self.core().system_theme().cosmic().spacing;
+        let options = &tab.options;
+        let bytes_per_row_selected = match options.bytes_per_row {
+            None => Some(0),
+            Some(bytes) => bytes_per_row_values.iter().position(|value| *value == bytes).map(|index| index + 1),
+        };
+        let group_size_selected = match options.group_size {
+            None => Some(0),
+            Some(size) => tab_group_size_values.iter().position(|value| *value == size).map(|index| index + 1),
+        };
+        let encoding_selected = crate::hex_view::text_encodings().iter().position(|encoding| *encoding == tab.hex_view.encoding);
+        let endian_selected = match options.endian {
+            None => 0,
+            Some(Endian::Little) => 1,
+            Some(Endian::Big) => 2,
+        };
+        let architecture_selected = options
+            .architecture
+            .and_then(|architecture| Architecture::ALL.iter().position(|value| *value == architecture))
+            .map_or(0, |index| index + 1);
+
+        let section = widget::settings::section()
+            .title(fl!("tab-options-title"))
+            .add(widget::settings::item::builder(fl!("bytes-per-row")).control(widget::dropdown(
+                &bytes_per_row_names,
+                bytes_per_row_selected,
+                Action::TabBytesPerRow,
+            )))
+            .add(widget::settings::item::builder(fl!("group-size")).control(widget::dropdown(&tab_group_size_names, group_size_selected, Action::TabGroupSize)))
+            .add(widget::settings::item::builder(fl!("encoding")).control(widget::dropdown(&encoding_names, encoding_selected, Action::ChangeEncoding)))
+            .add(
+                widget::settings::item::builder(fl!("base-address"))
+                    .control(widget::text_input::text_input("0x0", &tab.base_address_input).on_input(Action::TabBaseAddress)),
+            )
+            .add(
+                widget::settings::item::builder(fl!("byte-order")).control(widget::dropdown(&tab_endian_names, Some(endian_selected), |index| {
+                    Action::SetTabEndian(match index {
+                        1 => Some(Endian::Little),
+                        2 => Some(Endian::Big),
+                        _ => None,
+                    })
+                })),
+            )
+            .add(widget::settings::item::builder(fl!("architecture")).control(widget::dropdown(
+                &tab_architecture_names,
+                Some(architecture_selected),
+                |index| Action::SetTabArchitecture(index.checked_sub(1).and_then(|index| Architecture::ALL.get(index)).copied()),
+            )));
+
+        widget::container(
+            widget::column::with_capacity(2)
+                .spacing(space_xxs)
+                .push(section)
+                .push(widget::row::with_children(vec![
+                    widget::horizontal_space().into(),
+                    widget::button::text(fl!("close")).on_press(Action::ToggleTabOptions).into(),
+                ])),
+        )
+        .padding(space_s)
+        .width(Length::Fixed(420.0))
+        .class(cosmic::theme::Container::Dropdown)
+        .into()
+    }
+}
+
+lazy_static::lazy_static! {
+    pub(super) static ref bytes_per_row_values: Vec<usize> = vec![8, 16, 24, 32, 48, 64];
+    static ref bytes_per_row_names: Vec<String> = std::iter::once(fl!("bytes-per-row-fit"))
+        .chain(bytes_per_row_values.iter().map(|bytes| bytes.to_string()))
+        .collect();
+    pub(super) static ref tab_group_size_values: Vec<usize> = vec![1, 2, 4, 8, 16];
+    static ref tab_group_size_names: Vec<String> = std::iter::once(fl!("tab-option-default"))
+        .chain(tab_group_size_values.iter().map(|group_size| fl!("group-size-bytes", bytes = *group_size)))
+        .collect();
+    static ref tab_endian_names: Vec<String> = vec![fl!("tab-option-default"), fl!("little-endian"), fl!("big-endian")];
+    static ref tab_architecture_names: Vec<String> = std::iter::once(fl!("architecture-none"))
+        .chain(Architecture::ALL.iter().map(|architecture| architecture.name().to_string()))
+        .collect();
+}
@@ -0,0 +1,115 @@
+use std::path::{Path, PathBuf};
+
+use crate::{fl, i18n::format_size, SYNTAX_SYSTEM};
+use cosmic::{
+    iced::{Color, Font, Length},
+    theme, widget, Element,
+};
+use encoding_rs::Encoding;
+use syntect::{easy::HighlightLines, highlighting::Theme as SyntaxTheme};
+
+use super::{tab::EditorTab, Action, AppModel};
+
+/// Only the start of large files is shown in the text view.
+const MAX_TEXT_BYTES: usize = 256 * 1024;
+const MAX_TEXT_LINES: usize = 2000;
+
+/// The parts of a line with their colors, `None` for lines that couldn't be highlighted.
+type StyledLine = Vec<(Option<Color>, String)>;
+
+/// The decoded and highlighted start of the file. Highlighting only runs again when the data, the
+/// encoding, the file name or the syntax theme changed, not on every frame.
+#[derive(Default)]
+pub struct TextCache {
+    key: Option<(u64, &'static Encoding, PathBuf, String)>,
+    lines: Vec<StyledLine>,
+    /// Whether the file goes on after the shown lines.
+    truncated: bool,
+}
+
+fn syntax_color(color: syntect::highlighting::Color) -> Color {
+    Color::from_rgba8(color.r, color.g, color.b, color.a as f32 / 255.0)
+}
+
+/// Decodes and highlights up to [`MAX_TEXT_LINES`] lines from the start of `data`.
+fn highlight(data: &[u8], encoding: &'static Encoding, path: &Path, theme_name: &str) -> (Vec<StyledLine>, bool) {
+    let shown = &data[..data.len().min(MAX_TEXT_BYTES)];
+    let (text, _had_errors) = encoding.decode_without_bom_handling(shown);
+
+    let syntax_system = SYNTAX_SYSTEM.get().unwrap();
+    let syntax = syntax_system
+        .syntax_set
+        .find_syntax_for_file(path)
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| syntax_system.syntax_set.find_syntax_plain_text());
+    let fallback_theme = SyntaxTheme::default();
+    let syntax_theme = syntax_system.theme_set.themes.get(theme_name).unwrap_or(&fallback_theme);
+    let mut highlighter = HighlightLines::new(syntax, syntax_theme);
+
+    let mut text_lines = text.lines();
+    let lines = text_lines
+        .by_ref()
+        .take(MAX_TEXT_LINES)
+        .map(|line| match highlighter.highlight_line(line, &syntax_system.syntax_set) {
+            Ok(ranges) => ranges
+                .into_iter()
+                .map(|(style, part)| (Some(syntax_color(style.foreground)), part.to_string()))
+                .collect(),
+            Err(_) => vec![(None, line.to_string())],
+        })
+        .collect();
+    (lines, shown.len() < data.len() || text_lines.next().is_some())
+}
+
+impl AppModel {
+    /// Shows the buffer decoded with the tab's encoding and highlighted by file type.
+    pub(crate) fn text_view<'a>(&'a self, tab: &'a EditorTab) -> Element<'a, Action> {
+        let encodings = crate::hex_view::text_encodings();
+        let encoding_selected = encodings.iter().position(|e| *e == tab.hex_view.encoding);
+        let header = widget::row::with_children(vec![
+            widget::text::body(fl!("encoding")).into(),
+            widget::dropdown(&encoding_names, encoding_selected, Action::ChangeEncoding).into(),
+        ])
+        .spacing(8)
+        .align_y(cosmic::iced::Alignment::Center);
+
+        let Some(buffer) = &tab.hex_view.buffer else {
+            return header.into();
+        };
+        let theme_name = self.config.syntax_theme();
+        let key = Some((buffer.version(), tab.hex_view.encoding, tab.hex_view.path.clone(), theme_name.to_string()));
+        let mut cache = tab.text_cache.borrow_mut();
+        if cache.key != key {
+            let (lines, truncated) = highlight(&buffer.data, tab.hex_view.encoding, &tab.hex_view.path, theme_name);
+            *cache = TextCache { key, lines, truncated };
+        }
+
+        let mut lines = widget::column::with_capacity(cache.lines.len());
+        for line in cache.lines.iter() {
+            let mut row = widget::row::with_capacity(line.len());
+            for (color, part) in line {
+                let mut text = widget::text(part.clone()).font(Font::MONOSPACE);
+                if let Some(color) = color {
+                    text = text.class(theme::Text::Color(*color));
+                }
+                row = row.push(text);
+            }
+            lines = lines.push(row);
+        }
+
+        let mut column = widget::column::with_capacity(3).spacing(8).push(header);
+        if cache.truncated {
+            column = column.push(widget::text::caption(fl!(
+                "text-view-truncated",
+                lines = MAX_TEXT_LINES,
+                size = format_size(MAX_TEXT_BYTES as u64)
+            )));
+        }
+        column.push(widget::scrollable(lines).width(Length::Fill).height(Length::Fill)).into()
+    }
+}
+
+lazy_static::lazy_static! {
+    pub(super) static ref encoding_names: Vec<String> = crate::hex_view::text_encodings().iter().map(|encoding| encoding.name().to_string()).collect();
+}
@@ -0,0 +1,164 @@
+use cosmic::{cosmic_theme, iced::Alignment, widget, Element};
+
+use crate::{
+    fl,
+    hex_view::{
+        buffer::{DataBuffer, Endian},
+        parse_offset,
+    },
+};
+
+use super::{tab::Tab, Action, AppModel};
+
+/// Value types a watch can read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WatchType {
+    U8,
+    U16,
+    U32,
+    U64,
+    I8,
+    I16,
+    I32,
+    I64,
+    F32,
+    F64,
+}
+
+impl WatchType {
+    const ALL: [WatchType; 10] = [
+        WatchType::U8,
+        WatchType::U16,
+        WatchType::U32,
+        WatchType::U64,
+        WatchType::I8,
+        WatchType::I16,
+        WatchType::I32,
+        WatchType::I64,
+        WatchType::F32,
+        WatchType::F64,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            WatchType::U8 => "u8",
+            WatchType::U16 => "u16",
+            WatchType::U32 => "u32",
+            WatchType::U64 => "u64",
+            WatchType::I8 => "i8",
+            WatchType::I16 => "i16",
+            WatchType::I32 => "i32",
+            WatchType::I64 => "i64",
+            WatchType::F32 => "f32",
+            WatchType::F64 => "f64",
+        }
+    }
+
+    fn width(self) -> usize {
+        match self {
+            WatchType::U8 | WatchType::I8 => 1,
+            WatchType::U16 | WatchType::I16 => 2,
+            WatchType::U32 | WatchType::I32 | WatchType::F32 => 4,
+            WatchType::U64 | WatchType::I64 | WatchType::F64 => 8,
+        }
+    }
+}
+
+/// A value at a fixed offset, shown with its current value in the watch panel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Watch {
+    pub kind: WatchType,
+    pub offset: usize,
+    /// Byte order of the value, the view's byte order if `None`.
+    pub endian: Option<Endian>,
+}
+
+impl Watch {
+    /// Parses watches written as `<type> @ <offset> [LE|BE]`, for example `u32 @ 0x1F4 LE`.
+    pub fn parse(input: &str) -> Option<Self> {
+        let (kind, rest) = input.split_once('@')?;
+        let kind = kind.trim().to_lowercase();
+        let kind = WatchType::ALL.into_iter().find(|watch_type| watch_type.name() == kind)?;
+        let mut parts = rest.split_whitespace();
+        let offset = parse_offset(parts.next()?)?;
+        let endian = match parts.next().map(str::to_uppercase).as_deref() {
+            None => None,
+            Some("LE") => Some(Endian::Little),
+            Some("BE") => Some(Endian::Big),
+            Some(_) => return None,
+        };
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self { kind, offset, endian })
+    }
+
+    pub fn label(&self) -> String {
+        let endian = match self.endian {
+            None => "",
+            Some(Endian::Little) => " LE",
+            Some(Endian::Big) => " BE",
+        };
+        format!("{} @ 0x{:X}{}", self.kind.name(), self.offset, endian)
+    }
+
+    /// The current value, `None` if it reaches past the end of the data.
+    pub fn value(&self, buffer: &DataBuffer, view_endian: Endian) -> Option<String> {
+        let endian = self.endian.unwrap_or(view_endian);
+        let width = self.kind.width();
+        Some(match self.kind {
+            WatchType::F32 => buffer.get_f32(self.offset, endian)?.to_string(),
+            WatchType::F64 => buffer.get_f64(self.offset, endian)?.to_string(),
+            WatchType::I8 | WatchType::I16 | WatchType::I32 | WatchType::I64 => buffer.get_int(self.offset, width, endian)?.to_string(),
+            _ => buffer.get_uint(self.offset, width, endian)?.to_string(),
+        })
+    }
+}
+
+impl AppModel {
+    /// Registered watches with their values in the active tab, clicking one jumps to it.
+    pub(crate) fn watches_view(&self) -> Element<Action> {
+        let cosmic_theme::Spacing { space_xxs, .. } = self.core().system_theme().cosmic().spacing;
+        let tab = match self.tab_model.data::<Tab>(self.tab_model.active()) {
+            Some(Tab::Editor(tab)) => Some(tab),
+            _ => None,
+        };
+
+        let mut section = widget::settings::section().title(fl!("watches"));
+        for (i, watch) in self.watches.iter().enumerate() {
+            let value = tab
+                .and_then(|tab| watch.value(tab.hex_view.buffer.as_ref()?, tab.hex_view.endian))
+                .unwrap_or_else(|| fl!("inspector-invalid"));
+            let remove_button = widget::button::custom(widget::icon::from_name("edit-delete-symbolic").size(16).handle().icon())
+                .padding(space_xxs)
+                .class(cosmic::style::Button::Icon)
+                .on_press(Action::RemoveWatch(i));
+            let jump_button = widget::button::text(watch.label()).on_press(Action::JumpTo(watch.offset));
+            section = section.add(
+                widget::row::with_children(vec![
+                    jump_button.into(),
+                    widget::horizontal_space().into(),
+                    widget::text::body(value).into(),
+                    remove_button.into(),
+                ])
+                .spacing(space_xxs)
+                .align_y(Alignment::Center),
+            );
+        }
+
+        let input = widget::text_input::text_input("u32 @ 0x1F4 LE", &self.watch_input)
+            .on_input(Action::WatchInput)
+            .on_submit(Action::AddWatch);
+        let add_button = widget::button::standard(fl!("add-watch"));
+        let add_button = if Watch::parse(&self.watch_input).is_some() {
+            add_button.on_press(Action::AddWatch)
+        } else {
+            add_button
+        };
+        let input_row = widget::row::with_children(vec![input.into(), add_button.into()])
+            .spacing(space_xxs)
+            .align_y(Alignment::Center);
+
+        widget::settings::view_column(vec![section.into(), input_row.into()]).into()
+    }
+}
@@ -0,0 +1,75 @@
+use cosmic::{cosmic_theme, iced::Length, widget, Element};
+
+use crate::{
+    fl,
+    hex_view::xor::{XorHit, MAX_KEY_LEN},
+    i18n::format_number,
+};
+
+use super::{Action, AppModel};
+
+/// Hits reported at most.
+pub const MAX_HITS: usize = 200;
+
+/// Crib and key length typed in the XOR crib search dialog, with the hits of the last search.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct XorSearch {
+    pub crib: String,
+    pub max_key_len: String,
+    /// `None` before the first search and while searching.
+    pub hits: Option<Vec<XorHit>>,
+    pub searching: bool,
+}
+
+impl XorSearch {
+    pub fn new() -> Self {
+        Self {
+            max_key_len: "4".to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// The entered key length, `None` unless it is between 1 and [`MAX_KEY_LEN`].
+    pub fn max_key_len(&self) -> Option<usize> {
+        self.max_key_len.trim().parse().ok().filter(|len| (1..=MAX_KEY_LEN).contains(len))
+    }
+}
+
+impl AppModel {
+    pub(crate) fn xor_search_dialog<'a>(&'a self, search: &'a XorSearch) -> Element<'a, Action> {
+        let cosmic_theme::Spacing { space_xxs, .. } = self.core().system_theme().cosmic().spacing;
+        let mut control = widget::column::with_capacity(4)
+            .spacing(space_xxs)
+            .push(widget::settings::item::builder(fl!("xor-crib")).control(widget::text_input::text_input("", &search.crib).on_input(Action::XorCrib)))
+            .push(
+                widget::settings::item::builder(fl!("xor-max-key-len"))
+                    .control(widget::text_input::text_input("", &search.max_key_len).on_input(Action::XorMaxKeyLen)),
+            );
+
+        if search.searching {
+            control = control.push(widget::text::body(fl!("xor-searching")));
+        } else if let Some(hits) = &search.hits {
+            let mut list = widget::column::with_capacity(hits.len()).spacing(space_xxs);
+            for hit in hits {
+                let key: String = hit.key.iter().map(|byte| format!("{:02X}", byte)).collect();
+                let text = fl!("xor-hit", offset = format!("{:08X}", hit.offset), key = key);
+                list = list.push(widget::button::text(text).on_press(Action::JumpTo(hit.offset)).width(Length::Fill));
+            }
+            control = control
+                .push(widget::text::body(fl!("xor-hit-count", count = format_number(hits.len() as u64))))
+                .push(widget::scrollable(list).height(Length::Fixed(240.0)));
+        }
+
+        let mut search_button = widget::button::suggested(fl!("search"));
+        if !search.crib.is_empty() && search.max_key_len().is_some() && !search.searching {
+            search_button = search_button.on_press(Action::XorSearchRun);
+        }
+        widget::dialog::Dialog::new()
+            .title(fl!("xor-search-title"))
+            .body(fl!("xor-search-body"))
+            .control(control)
+            .primary_action(search_button)
+            .secondary_action(widget::button::text(fl!("close")).on_press(Action::DialogCancel))
+            .into()
+    }
+}
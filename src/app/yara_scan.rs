@@ -0,0 +1,88 @@
+use std::ops::Range;
+
+use cosmic::{iced::Length, widget, Element};
+
+use crate::{
+    fl,
+    hex_view::{selection::Selection, Message},
+    i18n::format_number,
+};
+
+use super::{Action, AppModel};
+
+/// Matches listed at most in the results panel.
+const MAX_MATCHES: usize = 1000;
+
+/// A string of a YARA rule found in the buffer.
+#[derive(Clone, Debug)]
+pub struct YaraMatch {
+    pub rule: String,
+    pub pattern: String,
+    pub range: Range<usize>,
+    pub data: Vec<u8>,
+}
+
+/// Compiles `source` and scans `data` with it. Errors are returned as text for the results panel.
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(data = data.len())))]
+pub fn scan(source: &str, data: &[u8]) -> Result<Vec<YaraMatch>, String> {
+    let rules = yara_x::compile(source).map_err(|err| err.to_string())?;
+    let mut scanner = yara_x::Scanner::new(&rules);
+    let results = scanner.scan(data).map_err(|err| err.to_string())?;
+    let mut matches = Vec::new();
+    for rule in results.matching_rules() {
+        for pattern in rule.patterns() {
+            for found in pattern.matches() {
+                matches.push(YaraMatch {
+                    rule: rule.identifier().to_string(),
+                    pattern: pattern.identifier().to_string(),
+                    range: found.range(),
+                    data: found.data().to_vec(),
+                });
+            }
+        }
+    }
+    matches.sort_by_key(|found| found.range.start);
+    Ok(matches)
+}
+
+/// Printable ASCII as is, everything else as `\xNN`, cut after 32 bytes.
+fn preview(data: &[u8]) -> String {
+    let mut text: String = data
+        .iter()
+        .take(32)
+        .map(|byte| {
+            if byte.is_ascii_graphic() || *byte == b' ' {
+                (*byte as char).to_string()
+            } else {
+                format!("\\x{:02X}", byte)
+            }
+        })
+        .collect();
+    if data.len() > 32 {
+        text.push('…');
+    }
+    text
+}
+
+impl AppModel {
+    /// Results of the last YARA scan, clicking a match selects it.
+    pub(crate) fn yara_view(&self) -> Element<Action> {
+        let matches = match &self.yara_results {
+            None => return widget::text::body(fl!("yara-scanning")).into(),
+            Some(Err(err)) => return widget::text::body(fl!("yara-error", error = err.as_str())).into(),
+            Some(Ok(matches)) if matches.is_empty() => return widget::text::body(fl!("yara-no-matches")).into(),
+            Some(Ok(matches)) => matches,
+        };
+        let mut section = widget::settings::section().title(fl!("yara-matches", count = format_number(matches.len() as u64)));
+        for found in matches.iter().take(MAX_MATCHES) {
+            let label = format!("{:08X}  {} {}  {}", found.range.start, found.rule, found.pattern, preview(&found.data));
+            let selection = Selection::new(found.range.start, found.range.end.max(found.range.start + 1) - 1);
+            section = section.add(
+                widget::button::text(label)
+                    .on_press(Action::HexAction(Message::Select(selection)))
+                    .width(Length::Fill),
+            );
+        }
+        widget::settings::view_column(vec![section.into()]).into()
+    }
+}
@@ -0,0 +1,372 @@
+use std::{
+    ops::Range,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Byte order used to interpret multi-byte values.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Endian {
+    #[default]
+    Little,
+    Big,
+}
+
+impl Endian {
+    /// Reorders bytes stored in this byte order into little endian order, and back.
+    pub fn normalize<const N: usize>(self, mut bytes: [u8; N]) -> [u8; N] {
+        if self == Endian::Big {
+            bytes.reverse();
+        }
+        bytes
+    }
+}
+
+/// Source of [`DataBuffer::version`], shared so that two buffers never have the same version.
+static NEXT_VERSION: AtomicU64 = AtomicU64::new(0);
+
+fn next_version() -> u64 {
+    NEXT_VERSION.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Bytes compared at once while looking for modified runs, equal chunks are skipped as a whole.
+const COMPARE_CHUNK: usize = 4096;
+
+pub struct DataBuffer {
+    pub data: Vec<u8>,
+    /// The data as it was last loaded or saved. Only copied on the first write after that, so
+    /// unchanged files are held once.
+    original: Option<Vec<u8>>,
+    /// Sorted, disjoint runs of bytes that differ from the original, updated on every write.
+    modified: Vec<Range<usize>>,
+    version: u64,
+}
+
+impl DataBuffer {
+    pub fn new(data: Vec<u8>) -> Self {
+        Self {
+            data,
+            original: None,
+            modified: Vec::new(),
+            version: next_version(),
+        }
+    }
+
+    /// Changes with every write, so views can cache what they derive from the data.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// The data as it was last loaded or saved.
+    pub fn original(&self) -> &[u8] {
+        self.original.as_deref().unwrap_or(&self.data)
+    }
+
+    /// Makes the current data the original, after it was written to disk.
+    pub fn mark_saved(&mut self) {
+        self.original = None;
+        self.modified.clear();
+    }
+
+    /// Returns true if any byte in `range` differs from the last saved state.
+    pub fn is_modified(&self, range: Range<usize>) -> bool {
+        let i = self.modified.partition_point(|run| run.end <= range.start);
+        self.modified.get(i).is_some_and(|run| run.start < range.end)
+    }
+
+    /// Start of the first modified run after `offset`.
+    pub fn next_modified(&self, offset: usize) -> Option<usize> {
+        let i = self.modified.partition_point(|run| run.start <= offset);
+        self.modified.get(i).map(|run| run.start)
+    }
+
+    /// Start of the last modified run before `offset`.
+    pub fn previous_modified(&self, offset: usize) -> Option<usize> {
+        let i = self.modified.partition_point(|run| run.start < offset);
+        i.checked_sub(1).map(|i| self.modified[i].start)
+    }
+
+    /// Keeps the original before the first write and gives the data a new version.
+    fn begin_write(&mut self) {
+        if self.original.is_none() {
+            self.original = Some(self.data.clone());
+        }
+        self.version = next_version();
+    }
+
+    /// Compares the bytes in `range` with the original again after writing them.
+    fn update_modified(&mut self, range: Range<usize>) {
+        let range = range.start.min(self.data.len())..range.end.min(self.data.len());
+        let original = self.original();
+        let mut runs = Vec::new();
+        let mut run_start = None;
+        let mut i = range.start;
+        while i < range.end {
+            let chunk_end = (i + COMPARE_CHUNK).min(range.end);
+            if original.get(i..chunk_end) == Some(&self.data[i..chunk_end]) {
+                if let Some(start) = run_start.take() {
+                    runs.push(start..i);
+                }
+                i = chunk_end;
+                continue;
+            }
+            for j in i..chunk_end {
+                let differs = original.get(j) != Some(&self.data[j]);
+                match (differs, run_start) {
+                    (true, None) => run_start = Some(j),
+                    (false, Some(start)) => {
+                        runs.push(start..j);
+                        run_start = None;
+                    }
+                    _ => {}
+                }
+            }
+            i = chunk_end;
+        }
+        if let Some(start) = run_start {
+            runs.push(start..range.end);
+        }
+        self.set_modified(range, runs);
+    }
+
+    /// Marks everything from `offset` as modified after inserting or removing there, the bytes
+    /// after it moved. Once the length matches the original again they are compared byte by byte.
+    fn update_moved(&mut self, offset: usize) {
+        let len = self.data.len();
+        if len == self.original().len() {
+            self.update_modified(offset..len);
+        } else {
+            let moved = (offset < len).then_some(offset..len);
+            self.set_modified(offset.min(len)..len, moved.into_iter().collect());
+        }
+    }
+
+    /// Replaces the modified runs inside `range` with `runs`, runs past the end of the data are dropped.
+    fn set_modified(&mut self, range: Range<usize>, runs: Vec<Range<usize>>) {
+        let len = self.data.len();
+        self.modified.retain_mut(|run| {
+            run.end = run.end.min(len);
+            run.start < run.end
+        });
+        // Runs touching `range` keep only their parts outside of it and merge with the new runs.
+        let first = self.modified.partition_point(|run| run.end < range.start);
+        let last = self.modified.partition_point(|run| run.start <= range.end);
+        let touching = &self.modified[first..last];
+        let before = touching.first().filter(|run| run.start < range.start).map(|run| run.start..range.start);
+        let after = touching.last().filter(|run| run.end > range.end).map(|run| range.end..run.end);
+        let mut merged: Vec<Range<usize>> = Vec::new();
+        for run in before.into_iter().chain(runs).chain(after) {
+            match merged.last_mut() {
+                Some(last) if last.end >= run.start => last.end = last.end.max(run.end),
+                _ => merged.push(run),
+            }
+        }
+        self.modified.splice(first..last, merged);
+    }
+
+    pub fn set_byte(&mut self, offset: usize, value: u8) {
+        self.begin_write();
+        self.data[offset] = value;
+        self.update_modified(offset..offset + 1);
+    }
+
+    pub fn get_byte(&self, offset: usize) -> u8 {
+        self.data[offset]
+    }
+
+    /// Returns the bytes in `range`, `None` if it reaches past the end.
+    pub fn get_bytes(&self, range: Range<usize>) -> Option<&[u8]> {
+        self.data.get(range)
+    }
+
+    /// Overwrites the bytes at `offset`, `None` if they don't fit into the buffer.
+    pub fn set_bytes(&mut self, offset: usize, bytes: &[u8]) -> Option<()> {
+        let range = offset..offset.checked_add(bytes.len())?;
+        if range.end > self.data.len() {
+            return None;
+        }
+        self.begin_write();
+        self.data[range.clone()].copy_from_slice(bytes);
+        self.update_modified(range);
+        Some(())
+    }
+
+    /// Inserts `bytes` before `offset`.
+    pub fn insert(&mut self, offset: usize, bytes: &[u8]) {
+        self.begin_write();
+        self.data.splice(offset..offset, bytes.iter().copied());
+        self.update_moved(offset);
+    }
+
+    /// Removes the bytes in `range`.
+    pub fn remove(&mut self, range: Range<usize>) {
+        self.begin_write();
+        self.data.drain(range.clone());
+        self.update_moved(range.start);
+    }
+
+    /// Replaces the whole data, e.g. after an edit that changed it in many places.
+    pub fn replace(&mut self, data: Vec<u8>) {
+        self.begin_write();
+        self.data = data;
+        self.update_modified(0..self.data.len());
+    }
+
+    /// Reads an unsigned integer of `width` (1 to 8) bytes.
+    pub fn get_uint(&self, offset: usize, width: usize, endian: Endian) -> Option<u64> {
+        if !(1..=8).contains(&width) {
+            return None;
+        }
+        let bytes = self.get_bytes(offset..offset.checked_add(width)?)?;
+        let mut le = [0; 8];
+        le[..width].copy_from_slice(bytes);
+        if endian == Endian::Big {
+            le[..width].reverse();
+        }
+        Some(u64::from_le_bytes(le))
+    }
+
+    /// Reads a sign extended integer of `width` (1 to 8) bytes.
+    pub fn get_int(&self, offset: usize, width: usize, endian: Endian) -> Option<i64> {
+        let value = self.get_uint(offset, width, endian)?;
+        let shift = 64 - width as u32 * 8;
+        Some(((value << shift) as i64) >> shift)
+    }
+
+    pub fn get_f32(&self, offset: usize, endian: Endian) -> Option<f32> {
+        Some(f32::from_bits(self.get_uint(offset, 4, endian)? as u32))
+    }
+
+    pub fn get_f64(&self, offset: usize, endian: Endian) -> Option<f64> {
+        Some(f64::from_bits(self.get_uint(offset, 8, endian)?))
+    }
+
+    /// Writes the low `width` (1 to 8) bytes of `value`.
+    pub fn set_uint(&mut self, offset: usize, width: usize, endian: Endian, value: u64) -> Option<()> {
+        if !(1..=8).contains(&width) {
+            return None;
+        }
+        let mut bytes = value.to_le_bytes()[..width].to_vec();
+        if endian == Endian::Big {
+            bytes.reverse();
+        }
+        self.set_bytes(offset, &bytes)
+    }
+
+    pub fn set_int(&mut self, offset: usize, width: usize, endian: Endian, value: i64) -> Option<()> {
+        self.set_uint(offset, width, endian, value as u64)
+    }
+
+    pub fn set_f32(&mut self, offset: usize, endian: Endian, value: f32) -> Option<()> {
+        self.set_uint(offset, 4, endian, value.to_bits() as u64)
+    }
+
+    pub fn set_f64(&mut self, offset: usize, endian: Endian, value: f64) -> Option<()> {
+        self.set_uint(offset, 8, endian, value.to_bits())
+    }
+
+    /// Returns the inclusive bounds of the printable text run around `offset`.
+    pub fn text_run(&self, offset: usize) -> (usize, usize) {
+        let is_text = |b: &u8| b.is_ascii_graphic() || *b == b' ';
+        if !is_text(&self.data[offset]) {
+            return (offset, offset);
+        }
+        let start = self.data[..offset].iter().rposition(|b| !is_text(b)).map_or(0, |i| i + 1);
+        let end = self.data[offset..].iter().position(|b| !is_text(b)).map_or(self.data.len(), |i| offset + i) - 1;
+        (start, end)
+    }
+
+    /// Returns the inclusive bounds of the run of identical bytes around `offset`.
+    pub fn run_bounds(&self, offset: usize) -> (usize, usize) {
+        let value = self.data[offset];
+        let start = self.data[..offset].iter().rposition(|b| *b != value).map_or(0, |i| i + 1);
+        let end = self.data[offset..].iter().position(|b| *b != value).map_or(self.data.len(), |i| offset + i) - 1;
+        (start, end)
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+}
+
+/// Returns the sum, minimum and maximum of the bytes in `slices`.
+pub fn byte_stats(slices: &[&[u8]]) -> (u64, u8, u8) {
+    let bytes = || slices.iter().flat_map(|slice| slice.iter().copied());
+    let sum = bytes().map(|b| b as u64).sum();
+    let min = bytes().min().unwrap_or(0);
+    let max = bytes().max().unwrap_or(0);
+    (sum, min, max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_track_modified_runs() {
+        let mut buffer = DataBuffer::new(vec![0; 16]);
+        assert!(!buffer.is_modified(0..16));
+        buffer.set_bytes(4, &[1, 1]);
+        buffer.set_byte(6, 1);
+        buffer.set_byte(10, 1);
+        assert!(buffer.is_modified(6..7));
+        assert!(!buffer.is_modified(7..10));
+        assert_eq!(buffer.next_modified(0), Some(4));
+        assert_eq!(buffer.next_modified(4), Some(10));
+        assert_eq!(buffer.previous_modified(10), Some(4));
+        assert_eq!(buffer.original(), &[0; 16]);
+
+        // Writing the original value back clears the mark.
+        buffer.set_byte(5, 0);
+        assert_eq!(buffer.next_modified(4), Some(6));
+        assert!(!buffer.is_modified(5..6));
+    }
+
+    #[test]
+    fn moved_bytes_are_modified_until_the_length_matches() {
+        let mut buffer = DataBuffer::new((0..16).collect());
+        buffer.insert(8, &[0xFF, 0xFF]);
+        assert!(!buffer.is_modified(0..8));
+        assert!(buffer.is_modified(17..18));
+        buffer.remove(8..10);
+        assert!(!buffer.is_modified(0..16));
+        assert_eq!(buffer.next_modified(0), None);
+
+        buffer.remove(0..4);
+        assert!(buffer.is_modified(11..12));
+        assert!(!buffer.is_modified(12..16));
+    }
+
+    #[test]
+    fn saving_clears_the_marks() {
+        let mut buffer = DataBuffer::new(vec![0; 4]);
+        buffer.set_byte(1, 1);
+        buffer.mark_saved();
+        assert!(!buffer.is_modified(0..4));
+        assert_eq!(buffer.original(), &[0, 1, 0, 0]);
+    }
+
+    #[test]
+    fn byte_stats_span_all_slices() {
+        assert_eq!(byte_stats(&[&[1, 2], &[5, 6]]), (1 + 2 + 5 + 6, 1, 6));
+        assert_eq!(byte_stats(&[&[], &[3]]), (3, 3, 3));
+        assert_eq!(byte_stats(&[]), (0, 0, 0));
+    }
+
+    #[test]
+    fn get_int_sign_extends() {
+        let buffer = DataBuffer::new(vec![0xFE, 0xFF, 0x7F, 0x80]);
+        assert_eq!(buffer.get_int(0, 1, Endian::Little), Some(-2));
+        assert_eq!(buffer.get_int(0, 2, Endian::Little), Some(-2));
+        assert_eq!(buffer.get_int(0, 2, Endian::Big), Some(-257));
+        assert_eq!(buffer.get_int(1, 2, Endian::Little), Some(0x7FFF));
+        assert_eq!(buffer.get_int(0, 4, Endian::Little), Some(0x807FFFFEu32 as i32 as i64));
+        assert_eq!(buffer.get_uint(0, 2, Endian::Little), Some(0xFFFE));
+        assert_eq!(buffer.get_int(2, 4, Endian::Little), None);
+    }
+}
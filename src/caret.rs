@@ -0,0 +1,132 @@
+//! Caret movement and nibble editing as plain functions, so they can be tested without
+//! a window. Caret positions count nibbles, two per byte, offsets count bytes.
+
+use super::selection::Selection;
+
+/// The last caret position. Writable views allow the position past the end, typing there appends.
+pub fn max_position(len: usize, read_only: bool) -> usize {
+    if read_only {
+        len.saturating_sub(1) * 2
+    } else {
+        len * 2
+    }
+}
+
+/// Moves `position` by `rows` rows, keeping the column. Moving up stops at the first row.
+pub fn move_rows(position: usize, rows: isize, numbers_in_row: usize) -> usize {
+    let delta = rows.unsigned_abs() * numbers_in_row * 2;
+    if rows < 0 {
+        position.saturating_sub(delta)
+    } else {
+        position + delta
+    }
+}
+
+/// The offset of the next or, if `offset` is inside a group, the current group start.
+pub fn move_group(offset: usize, group_size: usize, forward: bool) -> usize {
+    let group_size = group_size.max(1);
+    if forward {
+        (offset / group_size + 1) * group_size
+    } else if !offset.is_multiple_of(group_size) {
+        offset - offset % group_size
+    } else {
+        offset.saturating_sub(group_size)
+    }
+}
+
+/// Moves `position` a page of `page_rows` rows up or down, keeping the column and the caret inside
+/// `max_position`.
+pub fn move_page(position: usize, page_rows: usize, numbers_in_row: usize, max_position: usize, down: bool) -> usize {
+    let row_len = numbers_in_row * 2;
+    let row = position / row_len;
+    let new_row = if down {
+        (row + page_rows).min(max_position / row_len)
+    } else {
+        row.saturating_sub(page_rows)
+    };
+    (new_row * row_len + position % row_len).min(max_position)
+}
+
+/// `byte` with the high or low nibble replaced by the hex `digit`.
+pub fn set_nibble(byte: u8, digit: u8, high: bool) -> u8 {
+    if high {
+        (byte & 0x0F) | (digit << 4)
+    } else {
+        (byte & 0xF0) | (digit & 0x0F)
+    }
+}
+
+/// The selection a drag from `anchor` to `offset` makes, `None` while the pointer is still on the
+/// anchor. `block_width` is the row width for a block selection.
+pub fn drag_selection(anchor: usize, offset: usize, block_width: Option<usize>) -> Option<Selection> {
+    if offset == anchor {
+        return None;
+    }
+    Some(match block_width {
+        Some(width) => Selection::block(anchor, offset, width),
+        None => Selection::new(anchor, offset),
+    })
+}
+
+/// The group of `group_width` bytes around `offset` a double click selects, cut off at `len`.
+pub fn group_selection(offset: usize, group_width: usize, len: usize) -> Selection {
+    let group_width = group_width.max(1);
+    let start = offset - offset % group_width;
+    Selection::new(start, (start + group_width).min(len) - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_position_depends_on_read_only() {
+        assert_eq!(max_position(10, false), 20);
+        assert_eq!(max_position(10, true), 18);
+        assert_eq!(max_position(0, true), 0);
+    }
+
+    #[test]
+    fn move_rows_keeps_column() {
+        assert_eq!(move_rows(5, 1, 16), 37);
+        assert_eq!(move_rows(37, -1, 16), 5);
+        assert_eq!(move_rows(5, -1, 16), 0);
+    }
+
+    #[test]
+    fn move_group_steps_to_group_starts() {
+        assert_eq!(move_group(0, 4, true), 4);
+        assert_eq!(move_group(5, 4, true), 8);
+        assert_eq!(move_group(5, 4, false), 4);
+        assert_eq!(move_group(4, 4, false), 0);
+        assert_eq!(move_group(0, 4, false), 0);
+        assert_eq!(move_group(3, 0, true), 4);
+    }
+
+    #[test]
+    fn move_page_clamps() {
+        assert_eq!(move_page(3, 10, 16, 1000, true), 323);
+        assert_eq!(move_page(323, 10, 16, 1000, false), 3);
+        assert_eq!(move_page(3, 10, 16, 100, true), 99);
+        assert_eq!(move_page(3, 10, 16, 100, false), 3);
+    }
+
+    #[test]
+    fn set_nibble_replaces_one_half() {
+        assert_eq!(set_nibble(0x12, 0xA, true), 0xA2);
+        assert_eq!(set_nibble(0x12, 0xA, false), 0x1A);
+    }
+
+    #[test]
+    fn drag_selection_from_anchor() {
+        assert_eq!(drag_selection(4, 4, None), None);
+        assert_eq!(drag_selection(8, 4, None), Some(Selection::new(8, 4)));
+        assert_eq!(drag_selection(4, 40, Some(16)), Some(Selection::block(4, 40, 16)));
+    }
+
+    #[test]
+    fn group_selection_is_cut_at_end() {
+        assert_eq!(group_selection(6, 4, 100), Selection::new(4, 7));
+        assert_eq!(group_selection(9, 4, 10), Selection::new(8, 9));
+    }
+}
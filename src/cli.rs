@@ -0,0 +1,296 @@
+//! Headless command line modes that run without starting the GUI.
+
+use std::{
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::hex_view::{export, ips, parse_offset, pattern::Pattern};
+
+/// Runs the mode selected by `args` (without the program name) and returns its exit code,
+/// `None` if the GUI should start.
+pub fn run(args: &[String]) -> Option<i32> {
+    match args.first().map(String::as_str) {
+        Some("--grep") => Some(grep(&args[1..])),
+        Some("--dump") => Some(dump(&args[1..])),
+        Some("--apply-patch") => Some(apply_patch(&args[1..])),
+        _ => None,
+    }
+}
+
+/// `--dump [--offset N] [--length N] FILE` prints a hex dump in the Export Hex Dump format.
+fn dump(args: &[String]) -> i32 {
+    const USAGE: &str = "usage: cosmic-hex --dump [--offset N] [--length N] FILE";
+    let mut offset = 0;
+    let mut length = None;
+    let mut file = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let value = match arg.as_str() {
+            "--offset" | "--length" => match args.next().and_then(|value| parse_offset(value)) {
+                Some(value) => value,
+                None => {
+                    eprintln!("{} expects a decimal or 0x prefixed hex number", arg);
+                    return 2;
+                }
+            },
+            _ if file.is_none() => {
+                file = Some(arg);
+                continue;
+            }
+            _ => {
+                eprintln!("{}", USAGE);
+                return 2;
+            }
+        };
+        if arg == "--offset" {
+            offset = value;
+        } else {
+            length = Some(value);
+        }
+    }
+    let Some(file) = file else {
+        eprintln!("{}", USAGE);
+        return 2;
+    };
+    let data = match std::fs::read(file) {
+        Ok(data) => data,
+        Err(err) => {
+            eprintln!("{}: {}", file, err);
+            return 2;
+        }
+    };
+    let end = length.map_or(data.len(), |length| offset.saturating_add(length).min(data.len()));
+    let Some(data) = data.get(offset..end) else {
+        eprintln!("offset 0x{:X} is past the end of {} (0x{:X} bytes)", offset, file, data.len());
+        return 2;
+    };
+    let mut out = std::io::stdout().lock();
+    match out.write_all(export::to_rows(data, offset).as_bytes()).and_then(|()| out.flush()) {
+        Ok(()) => 0,
+        Err(err) => write_failed(err),
+    }
+}
+
+/// Exit code after writing to stdout failed. A closed pipe, as in `| head`, ends the output normally.
+fn write_failed(err: std::io::Error) -> i32 {
+    if err.kind() == ErrorKind::BrokenPipe {
+        0
+    } else {
+        eprintln!("stdout: {}", err);
+        2
+    }
+}
+
+/// `--apply-patch PATCH TARGET` applies an IPS patch, as written by Export Changes as IPS Patch, in place.
+fn apply_patch(args: &[String]) -> i32 {
+    let [patch, target] = args else {
+        eprintln!("usage: cosmic-hex --apply-patch PATCH TARGET");
+        return 2;
+    };
+    let (patch_data, mut data) = match (std::fs::read(patch), std::fs::read(target)) {
+        (Ok(patch_data), Ok(data)) => (patch_data, data),
+        (Err(err), _) => {
+            eprintln!("{}: {}", patch, err);
+            return 2;
+        }
+        (_, Err(err)) => {
+            eprintln!("{}: {}", target, err);
+            return 2;
+        }
+    };
+    if let Err(err) = ips::apply(&mut data, &patch_data) {
+        eprintln!("{}: {}", patch, err);
+        return 1;
+    }
+    if let Err(err) = replace_file(Path::new(target), &data) {
+        eprintln!("{}: {}", target, err);
+        return 2;
+    }
+    0
+}
+
+/// Writes `data` to a temporary file next to `target` and renames it over `target`, so an
+/// interrupted write never leaves a half patched file behind.
+fn replace_file(target: &Path, data: &[u8]) -> std::io::Result<()> {
+    let mut name = std::ffi::OsString::from(".");
+    name.push(target.file_name().unwrap_or_default());
+    name.push(".tmp");
+    let temp = target.with_file_name(name);
+    let result = std::fs::write(&temp, data)
+        .and_then(|()| {
+            if let Ok(metadata) = std::fs::metadata(target) {
+                std::fs::set_permissions(&temp, metadata.permissions())?;
+            }
+            Ok(())
+        })
+        .and_then(|()| std::fs::rename(&temp, target));
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp);
+    }
+    result
+}
+
+/// `--grep PATTERN FILE...` prints the offsets of every match. Exits with 0 if something was found,
+/// 1 if not and 2 on errors, like grep.
+fn grep(args: &[String]) -> i32 {
+    let Some((pattern, files)) = args.split_first().filter(|(_, files)| !files.is_empty()) else {
+        eprintln!("usage: cosmic-hex --grep PATTERN FILE...");
+        return 2;
+    };
+    let Some(pattern) = Pattern::parse(pattern) else {
+        eprintln!("invalid pattern {:?}, expected hex pairs and ?? wildcards", pattern);
+        return 2;
+    };
+    let mut out = std::io::stdout().lock();
+    let mut found = false;
+    let mut failed = false;
+    for file in files {
+        let data = match std::fs::read(file) {
+            Ok(data) => data,
+            Err(err) => {
+                eprintln!("{}: {}", file, err);
+                failed = true;
+                continue;
+            }
+        };
+        for offset in pattern.find_all(&data) {
+            found = true;
+            let written = if files.len() > 1 {
+                writeln!(out, "{}:0x{:08X}", file, offset)
+            } else {
+                writeln!(out, "0x{:08X}", offset)
+            };
+            if let Err(err) = written {
+                return write_failed(err);
+            }
+        }
+    }
+    if failed {
+        2
+    } else if found {
+        0
+    } else {
+        1
+    }
+}
+
+/// A file to open with an optional byte offset to put the caret at.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Location {
+    pub path: PathBuf,
+    pub offset: Option<usize>,
+}
+
+/// Files given on the command line when starting the GUI. Besides plain paths this accepts
+/// `file://` URIs and `hex://` URIs, both with an `?offset=` query or a `#offset` fragment,
+/// e.g. `hex:///tmp/core.bin?offset=0x1F40`.
+pub fn locations(args: &[String]) -> Vec<Location> {
+    args.iter()
+        .filter_map(|arg| {
+            let Some(uri) = arg.strip_prefix("hex://").or_else(|| arg.strip_prefix("file://")) else {
+                return Some(Location {
+                    path: PathBuf::from(arg),
+                    offset: None,
+                });
+            };
+            let (path, offset) = match uri.split_once(['?', '#']) {
+                Some((path, query)) => {
+                    let value = query.split('&').find_map(|pair| pair.strip_prefix("offset=")).unwrap_or(query);
+                    (path, percent_decode(value).and_then(|value| parse_offset(&value)))
+                }
+                None => (uri, None),
+            };
+            // Skip the host of `file://localhost/...`.
+            let path = &path[path.find('/')?..];
+            Some(Location {
+                path: PathBuf::from(percent_decode(path)?),
+                offset,
+            })
+        })
+        .collect()
+}
+
+/// Decodes `%XX` escapes, `None` if they don't form valid UTF-8.
+fn percent_decode(text: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.as_bytes();
+    while let Some((byte, tail)) = rest.split_first() {
+        match (byte, tail) {
+            (b'%', [high, low, tail @ ..]) if high.is_ascii_hexdigit() && low.is_ascii_hexdigit() => {
+                bytes.push(u8::from_str_radix(std::str::from_utf8(&[*high, *low]).ok()?, 16).ok()?);
+                rest = tail;
+            }
+            _ => {
+                bytes.push(*byte);
+                rest = tail;
+            }
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location(arg: &str) -> Option<Location> {
+        locations(&[arg.to_string()]).pop()
+    }
+
+    fn at(path: &str, offset: Option<usize>) -> Option<Location> {
+        Some(Location {
+            path: PathBuf::from(path),
+            offset,
+        })
+    }
+
+    #[test]
+    fn plain_paths_are_kept() {
+        assert_eq!(location("some dir/a%20b.bin"), at("some dir/a%20b.bin", None));
+    }
+
+    #[test]
+    fn file_uris() {
+        assert_eq!(location("file:///tmp/a%20b.bin"), at("/tmp/a b.bin", None));
+        assert_eq!(location("file://localhost/tmp/core.bin"), at("/tmp/core.bin", None));
+        assert_eq!(location("file:///tmp/core.bin#0x10"), at("/tmp/core.bin", Some(16)));
+    }
+
+    #[test]
+    fn hex_uris_with_offsets() {
+        assert_eq!(location("hex:///tmp/core.bin?offset=0x1F40"), at("/tmp/core.bin", Some(0x1F40)));
+        assert_eq!(location("hex:///tmp/core.bin?mode=ro&offset=100"), at("/tmp/core.bin", Some(100)));
+        assert_eq!(location("hex:///tmp/core.bin#1F40h"), at("/tmp/core.bin", Some(0x1F40)));
+        assert_eq!(location("hex:///tmp/core.bin?offset=%30x10"), at("/tmp/core.bin", Some(16)));
+        assert_eq!(location("hex:///tmp/core.bin?offset=nope"), at("/tmp/core.bin", None));
+        assert_eq!(location("hex:///tmp/core.bin?offset=%FF"), at("/tmp/core.bin", None));
+    }
+
+    #[test]
+    fn uris_without_a_path_are_skipped() {
+        assert_eq!(location("hex://core.bin"), None);
+        assert_eq!(location("file:///tmp/%FF.bin"), None);
+    }
+
+    #[test]
+    fn percent_decoding() {
+        assert_eq!(percent_decode("%41%2f%42").as_deref(), Some("A/B"));
+        assert_eq!(percent_decode("100%").as_deref(), Some("100%"));
+        assert_eq!(percent_decode("%4G").as_deref(), Some("%4G"));
+        assert_eq!(percent_decode("%C3%A4").as_deref(), Some("ä"));
+        assert_eq!(percent_decode("%C3"), None);
+    }
+
+    #[test]
+    fn replacing_a_file_leaves_no_temporary_file() {
+        let dir = std::env::temp_dir().join(format!("cosmic-hex-cli-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let target = dir.join("patched.bin");
+        std::fs::write(&target, b"old").unwrap();
+        replace_file(&target, b"new").unwrap();
+        assert_eq!(std::fs::read(&target).unwrap(), b"new");
+        assert!(!dir.join(".patched.bin.tmp").exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
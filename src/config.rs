@@ -1,11 +1,15 @@
 // SPDX-License-Identifier: {{LICENSE}}
 
+use std::collections::BTreeMap;
+
 use cosmic::{
     cosmic_config::{self, cosmic_config_derive::CosmicConfigEntry, CosmicConfigEntry},
     theme,
 };
 use serde::{Deserialize, Serialize};
 
+use crate::hex_view::{buffer::Endian, checksum::RowChecksum, highlight::Layer, numeric::NumericColumn};
+
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum AppTheme {
     Dark,
@@ -31,16 +35,81 @@ impl AppTheme {
     }
 }
 
+/// A value that can be shown in the status row below the hex view.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum StatusField {
+    OffsetHex,
+    OffsetDec,
+    U8,
+    U16,
+    U32,
+    U64,
+    I32,
+    F32,
+    Binary,
+    Selection,
+    SelectionLength,
+    FileSize,
+    Encoding,
+    EditMode,
+}
+
+impl StatusField {
+    pub const ALL: [StatusField; 14] = [
+        StatusField::OffsetHex,
+        StatusField::OffsetDec,
+        StatusField::U8,
+        StatusField::U16,
+        StatusField::U32,
+        StatusField::U64,
+        StatusField::I32,
+        StatusField::F32,
+        StatusField::Binary,
+        StatusField::Selection,
+        StatusField::SelectionLength,
+        StatusField::FileSize,
+        StatusField::Encoding,
+        StatusField::EditMode,
+    ];
+}
+
 pub const CONFIG_VERSION: u64 = 1;
 
-#[derive(Debug, Clone, CosmicConfigEntry, Eq, PartialEq)]
+/// Settings, also written to JSON by Export Settings. Missing fields take their defaults on import.
+#[derive(Debug, Clone, CosmicConfigEntry, Deserialize, Eq, PartialEq, Serialize)]
 #[version = 1]
+#[serde(default)]
 pub struct Config {
     pub app_theme: AppTheme,
     pub font_name: String,
     pub font_size: usize,
     pub syntax_theme_dark: String,
     pub syntax_theme_light: String,
+    pub status_fields: Vec<StatusField>,
+    /// Number of unpinned entries kept in the recent files list.
+    pub recent_files_max: usize,
+    /// Rows scrolled per mouse wheel notch.
+    pub scroll_lines: usize,
+    pub smooth_scrolling: bool,
+    /// Turns off caret blinking and smooth scrolling, also done when the desktop asks for it.
+    pub reduce_motion: bool,
+    /// Draws the hex view in black, white and strong colors instead of the syntax theme.
+    pub high_contrast: bool,
+    /// Bytes per group for Ctrl+Left/Right.
+    pub group_size: usize,
+    /// Byte order used unless a tab overrides it.
+    pub endian: Endian,
+    pub row_checksum: RowChecksum,
+    pub numeric_column: NumericColumn,
+    /// Text pane cells two characters wide, so wide CJK glyphs fit.
+    pub double_width_text: bool,
+    /// Leaves a gap between the byte groups of the hex pane.
+    pub group_gaps: bool,
+    pub show_hex_pane: bool,
+    pub show_text_pane: bool,
+    /// `#RRGGBB` or `#RRGGBBAA` per highlight layer, layers without one use their default color.
+    pub layer_colors: BTreeMap<Layer, String>,
+    pub hidden_layers: Vec<Layer>,
 }
 
 impl Config {
@@ -64,6 +133,23 @@ impl Default for Config {
 
             syntax_theme_dark: "COSMIC Dark".to_string(),
             syntax_theme_light: "COSMIC Light".to_string(),
+
+            status_fields: vec![StatusField::OffsetHex, StatusField::U32, StatusField::Selection],
+            recent_files_max: 10,
+            scroll_lines: 3,
+            smooth_scrolling: true,
+            reduce_motion: false,
+            high_contrast: false,
+            group_size: 4,
+            endian: Endian::Little,
+            row_checksum: RowChecksum::None,
+            numeric_column: NumericColumn::None,
+            double_width_text: false,
+            group_gaps: false,
+            show_hex_pane: true,
+            show_text_pane: true,
+            layer_colors: BTreeMap::new(),
+            hidden_layers: Vec::new(),
         }
     }
 }
@@ -0,0 +1,35 @@
+//! Instruction set details used for patching binaries.
+
+use serde::{Deserialize, Serialize};
+
+/// Instruction sets with a fixed no-op encoding.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Architecture {
+    X86,
+    Arm,
+    Arm64,
+    RiscV,
+}
+
+impl Architecture {
+    pub const ALL: [Architecture; 4] = [Architecture::X86, Architecture::Arm, Architecture::Arm64, Architecture::RiscV];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Architecture::X86 => "x86",
+            Architecture::Arm => "ARM",
+            Architecture::Arm64 => "AArch64",
+            Architecture::RiscV => "RISC-V",
+        }
+    }
+
+    /// The no-op instruction in memory order.
+    pub fn nop(self) -> &'static [u8] {
+        match self {
+            Architecture::X86 => &[0x90],
+            Architecture::Arm => &[0x00, 0xF0, 0x20, 0xE3],
+            Architecture::Arm64 => &[0x1F, 0x20, 0x03, 0xD5],
+            Architecture::RiscV => &[0x13, 0x00, 0x00, 0x00],
+        }
+    }
+}
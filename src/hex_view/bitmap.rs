@@ -0,0 +1,128 @@
+//! Converts raw bytes into RGBA pixels for the image preview.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PixelFormat {
+    Mono1,
+    Gray8,
+    Indexed8,
+    Rgb565,
+    Rgb24,
+    Bgr24,
+    Rgba32,
+    Bgra32,
+}
+
+impl PixelFormat {
+    pub const ALL: [PixelFormat; 8] = [
+        PixelFormat::Mono1,
+        PixelFormat::Gray8,
+        PixelFormat::Indexed8,
+        PixelFormat::Rgb565,
+        PixelFormat::Rgb24,
+        PixelFormat::Bgr24,
+        PixelFormat::Rgba32,
+        PixelFormat::Bgra32,
+    ];
+
+    pub fn bits_per_pixel(self) -> usize {
+        match self {
+            PixelFormat::Mono1 => 1,
+            PixelFormat::Gray8 | PixelFormat::Indexed8 => 8,
+            PixelFormat::Rgb565 => 16,
+            PixelFormat::Rgb24 | PixelFormat::Bgr24 => 24,
+            PixelFormat::Rgba32 | PixelFormat::Bgra32 => 32,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PixelFormat::Mono1 => "1 bpp mono",
+            PixelFormat::Gray8 => "8 bpp grayscale",
+            PixelFormat::Indexed8 => "8 bpp indexed",
+            PixelFormat::Rgb565 => "16 bpp RGB565",
+            PixelFormat::Rgb24 => "24 bpp RGB",
+            PixelFormat::Bgr24 => "24 bpp BGR",
+            PixelFormat::Rgba32 => "32 bpp RGBA",
+            PixelFormat::Bgra32 => "32 bpp BGRA",
+        }
+    }
+
+    /// Bytes needed for one row of `width` pixels.
+    pub fn row_bytes(self, width: usize) -> usize {
+        (width * self.bits_per_pixel()).div_ceil(8)
+    }
+}
+
+/// Default palette for indexed images: the 16 VGA colors, a 6x6x6 color cube and a gray ramp.
+fn palette(index: u8) -> [u8; 3] {
+    const VGA: [[u8; 3]; 16] = [
+        [0x00, 0x00, 0x00],
+        [0x00, 0x00, 0xAA],
+        [0x00, 0xAA, 0x00],
+        [0x00, 0xAA, 0xAA],
+        [0xAA, 0x00, 0x00],
+        [0xAA, 0x00, 0xAA],
+        [0xAA, 0x55, 0x00],
+        [0xAA, 0xAA, 0xAA],
+        [0x55, 0x55, 0x55],
+        [0x55, 0x55, 0xFF],
+        [0x55, 0xFF, 0x55],
+        [0x55, 0xFF, 0xFF],
+        [0xFF, 0x55, 0x55],
+        [0xFF, 0x55, 0xFF],
+        [0xFF, 0xFF, 0x55],
+        [0xFF, 0xFF, 0xFF],
+    ];
+    match index {
+        0..=15 => VGA[index as usize],
+        16..=231 => {
+            let i = index - 16;
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            [level(i / 36), level((i / 6) % 6), level(i % 6)]
+        }
+        _ => {
+            let gray = 8 + (index - 232) * 10;
+            [gray, gray, gray]
+        }
+    }
+}
+
+/// Renders up to `max_height` rows of `data` as an RGBA image `width` pixels wide.
+/// Returns the height that was rendered and the pixel data.
+pub fn to_rgba(data: &[u8], width: usize, format: PixelFormat, max_height: usize) -> (usize, Vec<u8>) {
+    let row_bytes = format.row_bytes(width);
+    if width == 0 || row_bytes == 0 {
+        return (0, Vec::new());
+    }
+    let height = (data.len() / row_bytes).min(max_height);
+    let mut pixels = Vec::with_capacity(width * height * 4);
+    for row in data.chunks_exact(row_bytes).take(height) {
+        for x in 0..width {
+            let rgba = match format {
+                PixelFormat::Mono1 => {
+                    let bit = row[x / 8] & (0x80 >> (x % 8)) != 0;
+                    let v = if bit { 0xFF } else { 0x00 };
+                    [v, v, v, 0xFF]
+                }
+                PixelFormat::Gray8 => [row[x], row[x], row[x], 0xFF],
+                PixelFormat::Indexed8 => {
+                    let [r, g, b] = palette(row[x]);
+                    [r, g, b, 0xFF]
+                }
+                PixelFormat::Rgb565 => {
+                    let v = u16::from_le_bytes([row[x * 2], row[x * 2 + 1]]);
+                    let r = ((v >> 11) & 0x1F) as u8;
+                    let g = ((v >> 5) & 0x3F) as u8;
+                    let b = (v & 0x1F) as u8;
+                    [r << 3 | r >> 2, g << 2 | g >> 4, b << 3 | b >> 2, 0xFF]
+                }
+                PixelFormat::Rgb24 => [row[x * 3], row[x * 3 + 1], row[x * 3 + 2], 0xFF],
+                PixelFormat::Bgr24 => [row[x * 3 + 2], row[x * 3 + 1], row[x * 3], 0xFF],
+                PixelFormat::Rgba32 => [row[x * 4], row[x * 4 + 1], row[x * 4 + 2], row[x * 4 + 3]],
+                PixelFormat::Bgra32 => [row[x * 4 + 2], row[x * 4 + 1], row[x * 4], row[x * 4 + 3]],
+            };
+            pixels.extend_from_slice(&rgba);
+        }
+    }
+    (height, pixels)
+}
@@ -0,0 +1,70 @@
+//! Bookmark lists shared as JSON or CSV, e.g. written by analysis scripts.
+//!
+//! JSON is an array of `{"offset": 4096, "note": "header"}` objects, CSV has one `offset,note` line
+//! per bookmark. Offsets are read like the jump dialog reads them, so `0x1000` works as well.
+
+use serde::{Deserialize, Serialize};
+
+use super::parse_offset;
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Bookmark {
+    pub offset: usize,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub note: String,
+}
+
+pub fn to_json(bookmarks: &[Bookmark]) -> String {
+    serde_json::to_string_pretty(bookmarks).unwrap_or_default()
+}
+
+pub fn to_csv(bookmarks: &[Bookmark]) -> String {
+    let mut csv = String::from("offset,note\n");
+    for bookmark in bookmarks {
+        csv.push_str(&format!("0x{:X},{}\n", bookmark.offset, quote(&bookmark.note)));
+    }
+    csv
+}
+
+/// Reads a JSON or CSV bookmark list, telling them apart by the first character.
+pub fn parse(text: &str) -> Result<Vec<Bookmark>, String> {
+    if text.trim_start().starts_with('[') {
+        return serde_json::from_str(text).map_err(|err| err.to_string());
+    }
+    let mut bookmarks = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim_end_matches('\r');
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (offset, note) = line.split_once(',').unwrap_or((line, ""));
+        let Some(offset) = parse_offset(offset) else {
+            // A header line names the columns.
+            if number == 0 {
+                continue;
+            }
+            return Err(format!("line {}: invalid offset {:?}", number + 1, offset.trim()));
+        };
+        bookmarks.push(Bookmark {
+            offset,
+            note: unquote(note.trim()),
+        });
+    }
+    Ok(bookmarks)
+}
+
+fn quote(note: &str) -> String {
+    let note = note.replace('\n', " ");
+    if note.contains([',', '"']) {
+        format!("\"{}\"", note.replace('"', "\"\""))
+    } else {
+        note
+    }
+}
+
+fn unquote(note: &str) -> String {
+    match note.strip_prefix('"').and_then(|note| note.strip_suffix('"')) {
+        Some(note) => note.replace("\"\"", "\""),
+        None => note.to_string(),
+    }
+}
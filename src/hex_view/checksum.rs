@@ -0,0 +1,36 @@
+//! Per row checksums as printed in EPROM listings.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum RowChecksum {
+    #[default]
+    None,
+    /// Sum of the bytes modulo 256.
+    Sum8,
+    /// CRC-8 with polynomial 0x07, initial value 0 (CRC-8/SMBUS).
+    Crc8,
+}
+
+impl RowChecksum {
+    pub const ALL: [RowChecksum; 3] = [RowChecksum::None, RowChecksum::Sum8, RowChecksum::Crc8];
+
+    pub fn compute(self, data: &[u8]) -> Option<u8> {
+        match self {
+            RowChecksum::None => None,
+            RowChecksum::Sum8 => Some(data.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte))),
+            RowChecksum::Crc8 => Some(crc8(data)),
+        }
+    }
+}
+
+fn crc8(data: &[u8]) -> u8 {
+    let mut crc = 0u8;
+    for byte in data {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 { crc << 1 ^ 0x07 } else { crc << 1 };
+        }
+    }
+    crc
+}
@@ -0,0 +1,90 @@
+//! Interpretations of raw bytes used by the data inspector.
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeDelta, Utc};
+
+/// Seconds between 1601-01-01 (FILETIME epoch) and 1970-01-01.
+const FILETIME_UNIX_OFFSET: i64 = 11_644_473_600;
+/// Seconds between 1904-01-01 (HFS+ epoch) and 1970-01-01.
+const HFS_UNIX_OFFSET: i64 = 2_082_844_800;
+/// Seconds between 1970-01-01 and 2001-01-01 (Cocoa epoch).
+const COCOA_UNIX_OFFSET: i64 = 978_307_200;
+
+pub fn unix_time32(bytes: [u8; 4]) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp(i32::from_le_bytes(bytes) as i64, 0)
+}
+
+pub fn unix_time64(bytes: [u8; 8]) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp(i64::from_le_bytes(bytes), 0)
+}
+
+/// Windows FILETIME: 100ns intervals since 1601-01-01.
+pub fn filetime(bytes: [u8; 8]) -> Option<DateTime<Utc>> {
+    let ticks = u64::from_le_bytes(bytes);
+    let secs = (ticks / 10_000_000) as i64 - FILETIME_UNIX_OFFSET;
+    let nanos = (ticks % 10_000_000) as u32 * 100;
+    DateTime::from_timestamp(secs, nanos)
+}
+
+/// MS-DOS date/time as stored in FAT and ZIP: time in the low word, date in the high word.
+pub fn dos_date_time(bytes: [u8; 4]) -> Option<NaiveDateTime> {
+    let value = u32::from_le_bytes(bytes);
+    let time = value & 0xFFFF;
+    let date = value >> 16;
+    let day = date & 0x1F;
+    let month = (date >> 5) & 0x0F;
+    let year = (date >> 9) + 1980;
+    let seconds = (time & 0x1F) * 2;
+    let minutes = (time >> 5) & 0x3F;
+    let hours = time >> 11;
+    NaiveDate::from_ymd_opt(year as i32, month, day)?.and_hms_opt(hours, minutes, seconds)
+}
+
+/// Classic Mac OS / HFS+ time: seconds since 1904-01-01.
+pub fn hfs_time(bytes: [u8; 4]) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp(u32::from_le_bytes(bytes) as i64 - HFS_UNIX_OFFSET, 0)
+}
+
+/// Apple Cocoa / Core Data absolute time: seconds since 2001-01-01 as a double.
+pub fn cocoa_time(bytes: [u8; 8]) -> Option<DateTime<Utc>> {
+    let secs = f64::from_le_bytes(bytes);
+    if !secs.is_finite() {
+        return None;
+    }
+    let epoch = DateTime::from_timestamp(COCOA_UNIX_OFFSET, 0)?;
+    epoch.checked_add_signed(TimeDelta::try_milliseconds((secs * 1000.0) as i64)?)
+}
+
+/// Decodes an unsigned LEB128 value (the protobuf varint encoding).
+/// Returns the value and the number of bytes consumed.
+pub fn uleb128(data: &[u8]) -> Option<(u64, usize)> {
+    let mut result = 0u64;
+    for (i, byte) in data.iter().take(10).enumerate() {
+        result |= ((byte & 0x7F) as u64).checked_shl(7 * i as u32)?;
+        if byte & 0x80 == 0 {
+            return Some((result, i + 1));
+        }
+    }
+    None
+}
+
+/// Decodes a signed LEB128 value as used by DWARF and WebAssembly.
+pub fn sleb128(data: &[u8]) -> Option<(i64, usize)> {
+    let mut result = 0i64;
+    let mut shift = 0;
+    for (i, byte) in data.iter().take(10).enumerate() {
+        result |= ((byte & 0x7F) as i64) << shift;
+        shift += 7;
+        if byte & 0x80 == 0 {
+            if shift < 64 && byte & 0x40 != 0 {
+                result |= -1 << shift;
+            }
+            return Some((result, i + 1));
+        }
+    }
+    None
+}
+
+/// Maps a zig-zag encoded varint (protobuf `sint32`/`sint64`) back to its signed value.
+pub fn zigzag(value: u64) -> i64 {
+    (value >> 1) as i64 ^ -((value & 1) as i64)
+}
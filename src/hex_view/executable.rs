@@ -0,0 +1,181 @@
+//! Section and symbol tables of ELF and PE files.
+
+use std::ops::Range;
+
+use goblin::{elf, Object};
+
+/// A named part of the file.
+#[derive(Clone, Debug)]
+pub struct Region {
+    pub name: String,
+    /// File offsets covered by the region.
+    pub range: Range<usize>,
+}
+
+/// A part of the file that is loaded into memory at `address`.
+#[derive(Clone, Debug)]
+pub struct Mapping {
+    pub file: Range<usize>,
+    pub address: u64,
+}
+
+/// Sections and symbols, all mapped to file offsets.
+#[derive(Clone, Debug, Default)]
+pub struct Image {
+    pub sections: Vec<Region>,
+    /// Sorted by start offset.
+    pub symbols: Vec<Region>,
+    /// Where the loader places the file contents, from the program headers or PE sections.
+    pub mappings: Vec<Mapping>,
+    /// Size of a pointer in bytes.
+    pub pointer_width: usize,
+}
+
+impl Image {
+    /// Parses `data` as an ELF or PE file, `None` for anything else or files without sections.
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        let mut image = match Object::parse(data).ok()? {
+            Object::Elf(elf) => Self::from_elf(&elf),
+            Object::PE(pe) => Self::from_pe(&pe),
+            _ => return None,
+        };
+        if image.sections.is_empty() {
+            return None;
+        }
+        image.symbols.sort_by_key(|symbol| symbol.range.start);
+        image.symbols.dedup_by(|a, b| a.range.start == b.range.start && a.name == b.name);
+        Some(image)
+    }
+
+    fn from_elf(elf: &elf::Elf) -> Self {
+        let mut image = Self {
+            pointer_width: if elf.is_64 { 8 } else { 4 },
+            ..Self::default()
+        };
+        for header in &elf.program_headers {
+            if header.p_type != elf::program_header::PT_LOAD || header.p_filesz == 0 {
+                continue;
+            }
+            let start = header.p_offset as usize;
+            image.mappings.push(Mapping {
+                file: start..start.saturating_add(header.p_filesz as usize),
+                address: header.p_vaddr,
+            });
+        }
+        for header in &elf.section_headers {
+            if header.sh_type == elf::section_header::SHT_NOBITS || header.sh_size == 0 {
+                continue;
+            }
+            let name = elf.shdr_strtab.get_at(header.sh_name).unwrap_or_default();
+            let start = header.sh_offset as usize;
+            image.sections.push(Region {
+                name: name.to_string(),
+                range: start..start.saturating_add(header.sh_size as usize),
+            });
+        }
+
+        let relocatable = elf.header.e_type == elf::header::ET_REL;
+        let tables = [(&elf.syms, &elf.strtab), (&elf.dynsyms, &elf.dynstrtab)];
+        for (symbols, strtab) in tables {
+            for symbol in symbols.iter() {
+                let Some(header) = elf.section_headers.get(symbol.st_shndx) else {
+                    continue;
+                };
+                let name = strtab.get_at(symbol.st_name).unwrap_or_default();
+                if name.is_empty() || header.sh_type == elf::section_header::SHT_NOBITS {
+                    continue;
+                }
+                // Relocatable objects store section relative values, everything else virtual addresses.
+                let section_offset = if relocatable {
+                    symbol.st_value
+                } else {
+                    match symbol.st_value.checked_sub(header.sh_addr) {
+                        Some(offset) if offset < header.sh_size => offset,
+                        _ => continue,
+                    }
+                };
+                let start = (header.sh_offset + section_offset) as usize;
+                image.symbols.push(Region {
+                    name: name.to_string(),
+                    range: start..start + (symbol.st_size as usize).max(1),
+                });
+            }
+        }
+        image
+    }
+
+    fn from_pe(pe: &goblin::pe::PE) -> Self {
+        let mut image = Self {
+            pointer_width: if pe.is_64 { 8 } else { 4 },
+            ..Self::default()
+        };
+        for section in &pe.sections {
+            if section.size_of_raw_data == 0 {
+                continue;
+            }
+            let start = section.pointer_to_raw_data as usize;
+            image.sections.push(Region {
+                name: section.name().unwrap_or_default().to_string(),
+                range: start..start + section.size_of_raw_data as usize,
+            });
+            // Raw data is padded to the file alignment, only the virtual size gets loaded.
+            let loaded = match section.virtual_size {
+                0 => section.size_of_raw_data,
+                size => size.min(section.size_of_raw_data),
+            };
+            image.mappings.push(Mapping {
+                file: start..start + loaded as usize,
+                address: pe.image_base as u64 + section.virtual_address as u64,
+            });
+        }
+        for export in &pe.exports {
+            let (Some(name), Some(start)) = (export.name, export.offset) else {
+                continue;
+            };
+            image.symbols.push(Region {
+                name: name.to_string(),
+                range: start..start + export.size.max(1),
+            });
+        }
+        image
+    }
+
+    /// The virtual address `offset` is loaded at, `None` if it isn't loaded.
+    pub fn address_of(&self, offset: usize) -> Option<u64> {
+        let mapping = self.mappings.iter().find(|mapping| mapping.file.contains(&offset))?;
+        Some(mapping.address + (offset - mapping.file.start) as u64)
+    }
+
+    /// The file offset loaded at `address`, `None` if no file contents end up there.
+    pub fn offset_of(&self, address: u64) -> Option<usize> {
+        self.mappings.iter().find_map(|mapping| {
+            let delta = usize::try_from(address.checked_sub(mapping.address)?).ok()?;
+            (delta < mapping.file.len()).then_some(mapping.file.start + delta)
+        })
+    }
+
+    /// Hex digits needed for the highest virtual address.
+    pub fn address_digits(&self) -> usize {
+        let max = self
+            .mappings
+            .iter()
+            .map(|mapping| mapping.address + mapping.file.len() as u64)
+            .max()
+            .unwrap_or(0);
+        if max > u32::MAX as u64 {
+            16
+        } else {
+            8
+        }
+    }
+
+    pub fn section_at(&self, offset: usize) -> Option<&Region> {
+        self.sections.iter().find(|section| section.range.contains(&offset))
+    }
+
+    /// The innermost symbol covering `offset`.
+    pub fn symbol_at(&self, offset: usize) -> Option<&Region> {
+        let end = self.symbols.partition_point(|symbol| symbol.range.start <= offset);
+        self.symbols[..end].iter().rev().find(|symbol| symbol.range.contains(&offset))
+    }
+}
@@ -0,0 +1,177 @@
+//! Formatting of byte ranges for the clipboard and for export.
+
+use cosmic::iced::Color;
+
+use super::theme::Theme;
+
+/// Bytes per row in exported dumps.
+pub const BYTES_PER_ROW: usize = 16;
+
+fn css_color(color: Color) -> String {
+    let [r, g, b, _] = color.into_rgba8();
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+fn printable(byte: u8) -> char {
+    if byte.is_ascii_graphic() || byte == b' ' {
+        byte as char
+    } else {
+        '.'
+    }
+}
+
+fn escape_html(ch: char) -> String {
+    match ch {
+        '<' => "&lt;".to_string(),
+        '>' => "&gt;".to_string(),
+        '&' => "&amp;".to_string(),
+        '"' => "&quot;".to_string(),
+        ' ' => "&nbsp;".to_string(),
+        _ => ch.to_string(),
+    }
+}
+
+/// Space separated hex pairs, the format pasted back by [`super::parse_hex`].
+pub fn to_hex(data: &[u8]) -> String {
+    data.iter().map(|byte| format!("{byte:02X}")).collect::<Vec<_>>().join(" ")
+}
+
+/// Renders `data`, which starts at absolute offset `start`, as an HTML table with
+/// offset, hex and text columns in the colors of `theme`.
+pub fn to_html(data: &[u8], start: usize, theme: &Theme) -> String {
+    let mut html = format!(
+        "<table style=\"font-family: monospace; border-collapse: collapse; background: {}\">\n",
+        css_color(theme.background)
+    );
+    for (row, chunk) in data.chunks(BYTES_PER_ROW).enumerate() {
+        let hex: Vec<String> = chunk.iter().map(|b| format!("{b:02X}")).collect();
+        let text: String = chunk.iter().map(|b| escape_html(printable(*b))).collect();
+        html.push_str(&format!(
+            "<tr><td style=\"color: {}; padding-right: 1em\">{:08X}</td><td style=\"color: {}; padding-right: 1em\">{}</td><td style=\"color: {}\">{}</td></tr>\n",
+            css_color(theme.offset_number),
+            start + row * BYTES_PER_ROW,
+            css_color(theme.hex),
+            hex.join(" "),
+            css_color(theme.ascii),
+            text
+        ));
+    }
+    html.push_str("</table>\n");
+    html
+}
+
+/// Dump rows per exported page, below the two header lines.
+const ROWS_PER_PAGE: usize = 64;
+
+/// Formats one dump row with offset, hex and text columns.
+fn text_row(chunk: &[u8], offset: usize) -> String {
+    let hex: Vec<String> = chunk.iter().map(|b| format!("{b:02X}")).collect();
+    let text: String = chunk.iter().map(|b| printable(*b)).collect();
+    format!("{:08X}  {:<width$}  {}", offset, hex.join(" "), text, width = BYTES_PER_ROW * 3 - 1)
+}
+
+/// Renders the dump rows without page headers, one line per row.
+pub fn to_rows(data: &[u8], start: usize) -> String {
+    let mut text = String::new();
+    for (i, chunk) in data.chunks(BYTES_PER_ROW).enumerate() {
+        text.push_str(&text_row(chunk, start + i * BYTES_PER_ROW));
+        text.push('\n');
+    }
+    text
+}
+
+/// Splits the dump of `data` into pages, each starting with a header line naming `title`.
+fn pages(data: &[u8], start: usize, title: &str) -> Vec<Vec<String>> {
+    let rows: Vec<String> = data
+        .chunks(BYTES_PER_ROW)
+        .enumerate()
+        .map(|(i, chunk)| text_row(chunk, start + i * BYTES_PER_ROW))
+        .collect();
+    let page_count = rows.len().div_ceil(ROWS_PER_PAGE).max(1);
+    (0..page_count)
+        .map(|page| {
+            let mut lines = vec![format!("{}    {}/{}", title, page + 1, page_count), String::new()];
+            lines.extend(rows.iter().skip(page * ROWS_PER_PAGE).take(ROWS_PER_PAGE).cloned());
+            lines
+        })
+        .collect()
+}
+
+/// Renders a plain text dump with one form feed separated page per `ROWS_PER_PAGE` rows.
+pub fn to_text(data: &[u8], start: usize, title: &str) -> String {
+    pages(data, start, title)
+        .into_iter()
+        .map(|lines| lines.join("\n"))
+        .collect::<Vec<_>>()
+        .join("\n\x0c\n")
+}
+
+/// Makes `line` safe for a PDF string in the Courier font, which only covers ASCII here. Dashes
+/// become `-`, other characters outside printable ASCII become `?`.
+fn escape_pdf(line: &str) -> String {
+    let mut escaped = String::with_capacity(line.len());
+    for ch in line.chars() {
+        match ch {
+            '\\' | '(' | ')' => {
+                escaped.push('\\');
+                escaped.push(ch);
+            }
+            '\u{2010}'..='\u{2015}' => escaped.push('-'),
+            ' '..='~' => escaped.push(ch),
+            _ => escaped.push('?'),
+        }
+    }
+    escaped
+}
+
+/// Renders the dump as an A4 PDF using the built-in Courier font.
+pub fn to_pdf(data: &[u8], start: usize, title: &str) -> Vec<u8> {
+    let pages = pages(data, start, title);
+    // Objects 1-3 are the catalog, the page tree and the font, then a page and its content per page.
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            (0..pages.len()).map(|i| format!("{} 0 R", 4 + i * 2)).collect::<Vec<_>>().join(" "),
+            pages.len()
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Courier >>".to_string(),
+    ];
+    for (i, lines) in pages.iter().enumerate() {
+        let mut content = String::from("BT /F1 9 Tf 11 TL 36 806 Td\n");
+        for line in lines {
+            content.push_str(&format!("({}) Tj T*\n", escape_pdf(line)));
+        }
+        content.push_str("ET");
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 595 842] /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+            5 + i * 2
+        ));
+        objects.push(format!("<< /Length {} >>\nstream\n{}\nendstream", content.len(), content));
+    }
+
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n{}\nendobj\n", i + 1, object).as_bytes());
+    }
+    let xref = pdf.len();
+    pdf.extend_from_slice(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes());
+    for offset in offsets {
+        pdf.extend_from_slice(format!("{offset:010} 00000 n \n").as_bytes());
+    }
+    pdf.extend_from_slice(format!("trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n", objects.len() + 1, xref).as_bytes());
+    pdf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pdf_strings_are_escaped_ascii() {
+        assert_eq!(escape_pdf("a (b) \\c"), "a \\(b\\) \\\\c");
+        assert_eq!(escape_pdf("/tmp/ä.bin — 2024"), "/tmp/?.bin - 2024");
+    }
+}
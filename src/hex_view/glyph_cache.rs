@@ -0,0 +1,66 @@
+//! Pre-shaped text for the cells of the hex and text panes. There are only 256 different cells per
+//! pane, so they are shaped once per font, size and encoding and reused for every row while
+//! scrolling instead of shaping each row anew.
+
+use cosmic::{
+    iced::{
+        alignment::{Horizontal, Vertical},
+        Font, Pixels, Size,
+    },
+    iced_core::text::{LineHeight, Shaping, Text, Wrapping},
+};
+
+use super::Plain;
+
+/// A text pane glyph and where it sits in its cell.
+pub struct TextGlyph {
+    pub paragraph: Plain,
+    /// Shift from the cell start that centers glyphs wider or narrower than a cell.
+    pub x_offset: f32,
+}
+
+#[derive(Default)]
+pub struct GlyphCache {
+    hex: Vec<Plain>,
+    text: Vec<TextGlyph>,
+}
+
+fn shape(content: &str, font: Font, font_size: f32) -> Plain {
+    Plain::new(Text {
+        content,
+        font,
+        size: Pixels(font_size),
+        vertical_alignment: Vertical::Top,
+        horizontal_alignment: Horizontal::Left,
+        shaping: Shaping::Advanced,
+        line_height: LineHeight::Relative(1.0),
+        bounds: Size::INFINITY,
+        wrapping: Wrapping::None,
+    })
+}
+
+impl GlyphCache {
+    /// Shapes the two digit strings and the text pane glyph `text_char` picks for every byte value.
+    pub fn new(font: Font, font_size: f32, cell_width: f32, text_char: impl Fn(u8) -> char) -> Self {
+        let hex = (0..=255u8).map(|byte| shape(&format!("{:02X}", byte), font, font_size)).collect();
+        let text = (0..=255u8)
+            .map(|byte| {
+                let mut content = [0; 4];
+                let paragraph = shape(text_char(byte).encode_utf8(&mut content), font, font_size);
+                TextGlyph {
+                    x_offset: (cell_width - paragraph.min_width()) / 2.0,
+                    paragraph,
+                }
+            })
+            .collect();
+        Self { hex, text }
+    }
+
+    pub fn hex(&self, byte: u8) -> Option<&Plain> {
+        self.hex.get(byte as usize)
+    }
+
+    pub fn text(&self, byte: u8) -> Option<&TextGlyph> {
+        self.text.get(byte as usize)
+    }
+}
@@ -8,20 +8,22 @@ use cosmic::{
     iced::{
         self, event,
         keyboard::Key,
-        mouse::{self, Cursor},
+        mouse::{self, click, Cursor},
+        time::{Duration, Instant},
         touch,
         widget::scrollable,
-        Element, Event, Length, Point, Rectangle, Renderer, Size, Vector,
+        window, Element, Event, Length, Point, Rectangle, Renderer, Size, Vector,
     },
+    iced_accessibility,
     iced_core::{
         self,
         widget::{operation, tree},
     },
-    widget::canvas::{Path, Stroke, Text},
+    widget::canvas::{Frame, Path, Stroke, Text},
     Theme,
 };
 
-use crate::hex_view::EditMode;
+use crate::hex_view::{buffer::DataBuffer, numeric::NumericColumn, EditMode};
 
 use super::{HexView, Message};
 
@@ -33,27 +35,401 @@ impl<'a> HexViewWidget<'a> {
     pub fn show(hex_view: &'a HexView) -> Element<'a, Message, Theme, cosmic::iced::Renderer> {
         let scroll_properties: scrollable::Scrollbar = scrollable::Scrollbar::default();
         let id = hex_view.id.clone();
+        // Only rows with a fixed number of bytes can be wider than the view.
+        let direction = if hex_view.content_width().is_some() {
+            scrollable::Direction::Both {
+                vertical: scroll_properties,
+                horizontal: scrollable::Scrollbar::default(),
+            }
+        } else {
+            scrollable::Direction::Vertical(scroll_properties)
+        };
         scrollable(HexViewWidget { hex_view })
             .id(id.into())
             .on_scroll(|_viewport| Message::Redraw)
             .width(Length::Fill)
             .height(Length::Fill)
-            .direction(scrollable::Direction::Vertical(scroll_properties))
+            .direction(direction)
             .into()
     }
 }
+/// Time an arrow key has to be held before it repeats.
+const KEY_REPEAT_DELAY: Duration = Duration::from_millis(400);
+const KEY_REPEAT_INTERVAL: Duration = Duration::from_millis(30);
+
+/// Rows (or nibbles) moved per repeat, doubling every second the key is held, up to 32.
+fn key_repeat_steps(held: Duration) -> usize {
+    1 << held.as_secs().min(5)
+}
+
+/// Touch held this long without moving starts a selection.
+const LONG_PRESS: Duration = Duration::from_millis(500);
+/// Movement in pixels before a touch counts as a scroll instead of a tap.
+const TOUCH_SLOP: f32 = 10.0;
+/// Finger distance ratio that changes the font size by one step.
+const PINCH_STEP: f32 = 1.15;
+/// Kinetic scrolling, velocities in pixels per second.
+const FLING_MIN_VELOCITY: f32 = 300.0;
+const FLING_STOP_VELOCITY: f32 = 20.0;
+const FLING_FRICTION: f32 = 4.0;
+/// A finger resting this long before lifting doesn't fling.
+const FLING_MAX_PAUSE: Duration = Duration::from_millis(100);
+
+/// Time the pointer has to rest on a byte before its tooltip shows.
+const TOOLTIP_DELAY: Duration = Duration::from_millis(500);
+const TOOLTIP_PADDING: f32 = 6.0;
+
+/// Maps a window position into widget coordinates, like the mouse handlers do.
+fn widget_point(position: Point, bounds: Rectangle) -> Point {
+    Point::new(position.x - bounds.x, position.y - bounds.y)
+}
+
+fn finger_distance(fingers: &[(touch::Finger, Point)]) -> f32 {
+    match fingers {
+        [(_, a), (_, b), ..] => a.distance(*b),
+        _ => 0.0,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TouchMode {
+    /// Not yet a scroll or a long press.
+    Pending,
+    Scroll,
+    /// Started by a long press, `moved` once the selection was dragged.
+    Select {
+        moved: bool,
+    },
+}
+
+/// The finger driving scrolling or selection.
+pub struct TouchState {
+    finger: touch::Finger,
+    start: Point,
+    last: Point,
+    since: Instant,
+    last_move: Instant,
+    /// Scroll velocity in pixels per second.
+    velocity: f32,
+    mode: TouchMode,
+}
+
+/// An arrow key that is held down and repeated by the widget.
+pub struct HeldKey {
+    key: keyboard::key::Named,
+    since: Instant,
+    next: Instant,
+}
+
+impl<'a> HexViewWidget<'a> {
+    /// Copies the selection as hex pairs from the hex pane or as text from the text pane.
+    fn copy(&self, clipboard: &mut dyn iced_core::Clipboard) {
+        if let Some(text) = self.hex_view.selection_text() {
+            clipboard.write(iced_core::clipboard::Kind::Standard, text);
+        }
+    }
+
+    fn paste(&self, clipboard: &mut dyn iced_core::Clipboard, shell: &mut iced_core::Shell<'_, Message>) {
+        if let Some(text) = clipboard.read(iced_core::clipboard::Kind::Standard) {
+            shell.publish(Message::Paste(text));
+        }
+    }
+
+    /// Caret movement for an arrow key pressed `steps` times.
+    fn arrow_move(&self, key: keyboard::key::Named, steps: usize) -> Message {
+        let position = self.hex_view.cursor.position;
+        match key {
+            keyboard::key::Named::ArrowDown => Message::MoveRows(steps as isize),
+            keyboard::key::Named::ArrowUp => Message::MoveRows(-(steps as isize)),
+            keyboard::key::Named::ArrowLeft => Message::MoveCaret(position.saturating_sub(steps)),
+            _ => Message::MoveCaret(position + steps),
+        }
+    }
+
+    /// Top left aligned text in the view font.
+    fn row_text(&self, content: String, position: Point, color: iced::Color) -> Text {
+        Text {
+            font: self.hex_view.font,
+            size: iced::Pixels(self.hex_view.font_size),
+            color,
+            content,
+            position,
+            line_height: LineHeight::Relative(1.0),
+            horizontal_alignment: iced::alignment::Horizontal::Left,
+            vertical_alignment: iced::alignment::Vertical::Top,
+            shaping: Shaping::Advanced,
+        }
+    }
+
+    /// Draws the background, the selection and the caret. They change with every caret move, so they
+    /// live in their own cache below the byte content, which is only rebuilt on edits and scrolling.
+    fn draw_overlay(&self, frame: &mut Frame, buffer: &DataBuffer, size: Size, y: f32) {
+        let rect = Path::rectangle(Point::ORIGIN, size);
+        frame.fill(&rect, self.hex_view.theme.background);
+        // Everything else moves with the horizontal scrolling of wide rows.
+        frame.translate(Vector::new(-self.hex_view.viewport.get().x, 0.0));
+
+        let layout = self.hex_view.layout();
+        let numbers_in_row = layout.numbers_in_row;
+        let font_measure = self.hex_view.font_measure;
+        let text_cell = Size::new(layout.text_cell_width, font_measure.height);
+        // Marks `count` cells from `column` in the shown panes.
+        let fill_cells = |frame: &mut Frame, column: usize, count: usize, line_y: f32, color: iced::Color| {
+            for (x, width) in [layout.hex_span(column, count), layout.text_span(column, count)].into_iter().flatten() {
+                frame.fill_rectangle(Point::new(x, line_y), Size::new(width, font_measure.height), color);
+            }
+        };
+
+        let first_row = self.hex_view.row_at(y);
+        let rows = (size.height / font_measure.height) as usize + 2;
+        let visible = first_row * numbers_in_row..(first_row + rows) * numbers_in_row;
+        for (color, ranges) in self.hex_view.highlights.visible() {
+            for highlight in ranges {
+                if highlight.is_empty() || highlight.end <= visible.start || highlight.start >= visible.end {
+                    continue;
+                }
+                for row in highlight.start.max(visible.start) / numbers_in_row..=(highlight.end.min(visible.end) - 1) / numbers_in_row {
+                    let offset = row * numbers_in_row;
+                    let first = highlight.start.max(offset);
+                    let last = highlight.end.min(offset + numbers_in_row);
+                    fill_cells(frame, first - offset, last - first, self.hex_view.row_y(row) - y, color);
+                }
+            }
+        }
+
+        if let Some(selection) = self.hex_view.selection {
+            for row in first_row..first_row + rows {
+                let offset = row * numbers_in_row;
+                let row_end = (offset + numbers_in_row).min(buffer.len());
+                let line_y = self.hex_view.row_y(row) - y;
+                for run in selection.runs(offset..row_end) {
+                    fill_cells(frame, run.start - offset, run.len(), line_y, self.hex_view.theme.selection);
+                }
+            }
+        }
+
+        if let Some(mark) = self.hex_view.mark {
+            let line_y = self.hex_view.row_y(mark / numbers_in_row) - y;
+            let column = mark % numbers_in_row;
+            let stroke = Stroke::default().with_color(self.hex_view.theme.bookmark).with_width(2.0);
+            if layout.show_hex {
+                frame.stroke_rectangle(
+                    Point::new(layout.hex_x(column), line_y),
+                    Size::new(font_measure.width * 2.0, font_measure.height),
+                    stroke,
+                );
+            }
+            if layout.show_text {
+                frame.stroke_rectangle(Point::new(layout.text_x(column), line_y), text_cell, stroke);
+            }
+        }
+
+        if self.hex_view.cursor.blink {
+            return;
+        }
+        let caret_line = self.hex_view.cursor.position / (numbers_in_row * 2);
+        let caret_line_offset = self.hex_view.cursor.position % (numbers_in_row * 2);
+
+        let caret_cell = caret_line_offset / 2;
+
+        let y = self.hex_view.row_y(caret_line) - y;
+        let c = self.hex_view.theme.caret;
+        if layout.show_hex {
+            let mut x = layout.hex_x(caret_cell);
+            if self.hex_view.cursor.in_hex == EditMode::Hex {
+                if !caret_line_offset.is_multiple_of(2) {
+                    x += font_measure.width;
+                }
+                if self.hex_view.insert_mode {
+                    frame.fill_rectangle(Point::new(x, y), Size::new(2.0, font_measure.height), c);
+                } else {
+                    frame.fill_rectangle(Point::new(x, y), font_measure, c);
+                }
+            } else {
+                frame.stroke_rectangle(
+                    Point::new(x, y),
+                    Size::new(font_measure.width * 2.0, font_measure.height),
+                    Stroke::default().with_color(c),
+                );
+            }
+        }
+
+        if layout.show_text {
+            let x = layout.text_x(caret_cell);
+            if self.hex_view.cursor.in_hex == EditMode::Hex {
+                frame.stroke_rectangle(Point::new(x, y), text_cell, Stroke::default().with_color(c));
+            } else {
+                frame.fill_rectangle(Point::new(x, y), text_cell, c);
+            }
+        }
+    }
+
+    /// Offset, relative offset and row/column of the byte at `offset`.
+    fn tooltip_lines(&self, offset: usize) -> Vec<String> {
+        let numbers_in_row = self.hex_view.numbers_in_row();
+        vec![
+            crate::fl!(
+                "tooltip-offset",
+                hex = format!("{:08X}", offset),
+                dec = crate::i18n::format_number(offset as u64)
+            ),
+            crate::fl!("tooltip-relative", offset = self.hex_view.relative_offset(offset)),
+            crate::fl!(
+                "tooltip-position",
+                row = (offset / numbers_in_row).to_string(),
+                column = (offset % numbers_in_row).to_string()
+            ),
+        ]
+    }
+
+    /// Multi-byte values starting at `offset` in the view's byte order, shown while Ctrl is held.
+    fn value_preview_lines(&self, buffer: &DataBuffer, offset: usize) -> Vec<String> {
+        let endian = self.hex_view.endian;
+        let mut lines = vec![crate::fl!(
+            "tooltip-offset",
+            hex = format!("{:08X}", offset),
+            dec = crate::i18n::format_number(offset as u64)
+        )];
+        if let Some(value) = buffer.get_uint(offset, 2, endian) {
+            lines.push(crate::fl!("preview-u16", value = value.to_string()));
+        }
+        if let Some(value) = buffer.get_uint(offset, 4, endian) {
+            lines.push(crate::fl!("preview-u32", value = value.to_string()));
+        }
+        if let Some(value) = buffer.get_uint(offset, 8, endian) {
+            lines.push(crate::fl!("preview-u64", value = value.to_string()));
+        }
+        if let Some(value) = buffer.get_f32(offset, endian) {
+            lines.push(crate::fl!("preview-f32", value = value.to_string()));
+        }
+        if let Some(value) = buffer.get_f64(offset, endian) {
+            lines.push(crate::fl!("preview-f64", value = value.to_string()));
+        }
+        lines
+    }
+
+    /// Draws a box with `lines` below and right of `point`, flipped to stay inside `size`.
+    fn draw_tooltip(&self, frame: &mut Frame, point: Point, lines: &[String], size: Size) {
+        let font_measure = self.hex_view.font_measure;
+        let chars = lines.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+        let box_size = Size::new(
+            chars as f32 * font_measure.width + TOOLTIP_PADDING * 2.0,
+            lines.len() as f32 * font_measure.height + TOOLTIP_PADDING * 2.0,
+        );
+        let mut x = point.x + font_measure.width;
+        let mut y = point.y + font_measure.height;
+        if x + box_size.width > size.width {
+            x = (point.x - box_size.width).max(0.0);
+        }
+        if y + box_size.height > size.height {
+            y = (point.y - box_size.height).max(0.0);
+        }
+        let theme = &self.hex_view.theme;
+        frame.fill_rectangle(Point::new(x, y), box_size, theme.background);
+        frame.stroke_rectangle(Point::new(x, y), box_size, Stroke::default().with_color(theme.offset_number));
+        for (i, line) in lines.iter().enumerate() {
+            let position = Point::new(x + TOOLTIP_PADDING, y + TOOLTIP_PADDING + i as f32 * font_measure.height);
+            frame.fill_text(self.row_text(line.clone(), position, theme.hex));
+        }
+    }
+
+    /// Draws the hex and text pane cells of the visible rows from the glyph cache. Nothing is shaped
+    /// here, so this stays cheap while scrolling.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+    fn draw_cells(&self, renderer: &mut Renderer, buffer: &DataBuffer, size: Size, x: f32, y: f32) {
+        use iced_core::text::Renderer as _;
+
+        let layout = self.hex_view.layout();
+        let numbers_in_row = layout.numbers_in_row;
+        let clip = Rectangle::new(Point::ORIGIN, size);
+        let glyphs = &self.hex_view.glyphs;
+
+        let mut line = self.hex_view.row_at(y);
+        let mut offset = line * numbers_in_row;
+        while offset < buffer.len() {
+            let line_y = self.hex_view.row_y(line) - y;
+            if line_y > size.height {
+                break;
+            }
+            let row_end = (offset + numbers_in_row).min(buffer.len());
+            for (cell, &byte) in buffer.data[offset..row_end].iter().enumerate() {
+                if let Some(hex) = glyphs.hex(byte).filter(|_| layout.show_hex) {
+                    let position = Point::new(layout.hex_x(cell) - x, line_y);
+                    renderer.fill_paragraph(hex.raw(), position, self.hex_view.theme.hex, clip);
+                }
+                if let Some(text) = glyphs.text(byte).filter(|_| layout.show_text) {
+                    let position = Point::new(layout.text_x(cell) + text.x_offset - x, line_y);
+                    renderer.fill_paragraph(text.paragraph.raw(), position, self.hex_view.theme.ascii, clip);
+                }
+            }
+            line += 1;
+            offset += numbers_in_row;
+        }
+    }
+
+    /// Draws a line in front of every record start in `row`, across both panes if the record starts the row.
+    fn draw_record_separators(&self, frame: &mut Frame, row: std::ops::Range<usize>, record_size: usize, line_y: f32) {
+        let font_measure = self.hex_view.font_measure;
+        let layout = self.hex_view.layout();
+        let color = self.hex_view.theme.offset_number;
+        let first = row.start.next_multiple_of(record_size);
+        for start in (first..row.end).step_by(record_size) {
+            let cell = start - row.start;
+            if cell == 0 {
+                let width = layout.text_start() + layout.text_width() - layout.gutter_width;
+                frame.fill_rectangle(Point::new(layout.gutter_width, line_y), Size::new(width, 1.0), color);
+            } else {
+                let height = Size::new(1.0, font_measure.height);
+                if layout.show_hex {
+                    frame.fill_rectangle(Point::new(layout.hex_x(cell) - font_measure.width / 2.0, line_y), height, color);
+                }
+                if layout.show_text {
+                    frame.fill_rectangle(Point::new(layout.text_x(cell), line_y), height, color);
+                }
+            }
+        }
+    }
+
+    /// Draws marks next to the scrollbar for modified regions, search matches and bookmarks,
+    /// each at the height its offset has within the whole file.
+    fn draw_markers(&self, frame: &mut Frame, buffer: &DataBuffer, size: Size) {
+        const MARKER_WIDTH: f32 = 4.0;
+        const SCROLLBAR_WIDTH: f32 = 12.0;
+        if buffer.is_empty() {
+            return;
+        }
+        let x = self.hex_view.viewport.get().x + size.width - SCROLLBAR_WIDTH - MARKER_WIDTH;
+        let y_for = |offset: usize| offset as f32 / buffer.len() as f32 * size.height;
+        let theme = &self.hex_view.theme;
+
+        // One bucket per pixel row, so the cost only depends on the viewport height.
+        if self.hex_view.is_dirty() {
+            let buckets = size.height.max(1.0) as usize;
+            let bucket_len = buffer.len().div_ceil(buckets);
+            for bucket in 0..buckets {
+                let start = bucket * bucket_len;
+                if start >= buffer.len() {
+                    break;
+                }
+                if buffer.is_modified(start..(start + bucket_len).min(buffer.len())) {
+                    frame.fill_rectangle(Point::new(x, y_for(start)), Size::new(MARKER_WIDTH, 2.0), theme.modified);
+                }
+            }
+        }
+        for offset in &self.hex_view.matches {
+            frame.fill_rectangle(Point::new(x, y_for(*offset)), Size::new(MARKER_WIDTH, 2.0), theme.search_match);
+        }
+        for offset in &self.hex_view.bookmarks {
+            frame.fill_rectangle(Point::new(x - MARKER_WIDTH, y_for(*offset)), Size::new(MARKER_WIDTH * 2.0, 2.0), theme.bookmark);
+        }
+    }
+}
+
 impl<'a> Widget<Message, Theme, Renderer> for HexViewWidget<'a> {
     fn size(&self) -> Size<Length> {
-        let numbers_in_row = self.hex_view.numbers_in_row();
-        let height = if let Some(buffer) = &self.hex_view.buffer {
-            let lines = buffer.len() / numbers_in_row + 1;
-            lines as f32
-        } else {
-            1.0
-        };
         Size {
-            width: Length::Fill,
-            height: Length::Fixed(height * self.hex_view.font_measure.height),
+            width: self.hex_view.content_width().map_or(Length::Fill, Length::Fixed),
+            height: Length::Fixed(self.hex_view.content_height()),
         }
     }
 
@@ -62,26 +438,52 @@ impl<'a> Widget<Message, Theme, Renderer> for HexViewWidget<'a> {
     }
 
     fn layout(&self, _tree: &mut Tree, _renderer: &Renderer, limits: &iced_core::layout::Limits) -> iced_core::layout::Node {
-        let numbers_in_row = self.hex_view.numbers_in_row();
-        let height = if let Some(buffer) = &self.hex_view.buffer {
-            let lines = buffer.len() / numbers_in_row + 1;
-            lines as f32
-        } else {
-            1.0
+        let size = limits.resolve(self.size().width, Length::Fixed(self.hex_view.content_height()), Size::ZERO);
+        iced::advanced::layout::Node::new(size)
+    }
+
+    fn operate(&self, tree: &mut Tree, _layout: iced_core::Layout<'_>, _renderer: &Renderer, operation: &mut dyn operation::Operation<()>) {
+        let state = tree.state.downcast_mut::<State>();
+        operation.focusable(state, Some(&self.hex_view.focus_id));
+    }
+
+    fn id(&self) -> Option<iced_core::id::Id> {
+        Some(self.hex_view.focus_id.clone())
+    }
+
+    /// One focusable node whose value describes the caret. It is a polite live region, so screen
+    /// readers announce caret moves and edits.
+    fn a11y_nodes(&self, layout: iced_core::Layout<'_>, _state: &Tree, _cursor: Cursor) -> iced_accessibility::A11yTree {
+        use iced_accessibility::{
+            accesskit::{Action, Live, NodeBuilder, Rect, Role},
+            A11yNode, A11yTree,
         };
 
-        let size = limits.resolve(Length::Fill, Length::Fixed(height * self.hex_view.font_measure.height), Size::ZERO);
-        iced::advanced::layout::Node::new(size)
+        let Rectangle { x, y, width, height } = layout.bounds();
+        let mut node = NodeBuilder::new(Role::Document);
+        node.add_action(Action::Focus);
+        node.set_bounds(Rect::new(x as f64, y as f64, (x + width) as f64, (y + height) as f64));
+        node.set_name(crate::fl!(
+            "a11y-hex-view",
+            file = self.hex_view.path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default()
+        ));
+        node.set_value(self.hex_view.caret_description());
+        node.set_live(Live::Polite);
+        if self.hex_view.read_only {
+            node.set_read_only();
+        }
+        A11yTree::leaf(node, self.hex_view.focus_id.clone())
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
     fn draw(
         &self,
-        _tree: &Tree,
+        tree: &Tree,
         renderer: &mut Renderer,
         _theme: &Theme,
         _style: &iced::advanced::renderer::Style,
         layout: iced::advanced::Layout,
-        _cursor: Cursor,
+        cursor: Cursor,
         viewport: &Rectangle,
     ) {
         let Some(buffer) = &self.hex_view.buffer else {
@@ -92,111 +494,99 @@ impl<'a> Widget<Message, Theme, Renderer> for HexViewWidget<'a> {
         vp.y -= bounds.y;
         vp.x -= bounds.x;
 
-        self.hex_view.viewport.set(vp);
+        self.hex_view.set_viewport(vp);
+        self.hex_view.clear_dirty_caches();
 
-        let geometry = self.hex_view.cache.draw(renderer, viewport.size(), |frame| {
-            let rect = Path::rectangle(Point::ORIGIN, viewport.size());
-            frame.fill(&rect, self.hex_view.theme.background);
+        let x = viewport.x - bounds.x;
+        let y = viewport.y - bounds.y;
+        let overlay = self.hex_view.overlay.draw(renderer, viewport.size(), |frame| {
+            self.draw_overlay(frame, buffer, viewport.size(), y);
+        });
 
+        let geometry = self.hex_view.cache.draw(renderer, viewport.size(), |frame| {
+            frame.translate(Vector::new(-x, 0.0));
             let numbers_in_row = self.hex_view.numbers_in_row();
 
-            let y = viewport.y - bounds.y;
-            let mut line = (y / self.hex_view.font_measure.height.max(16.0)).floor();
+            let mut line = self.hex_view.row_at(y);
 
-            let mut offset = line as usize * numbers_in_row;
-            let cell_size = self.hex_view.theme.calc_cell_width(self.hex_view.font_measure);
-            let offset_margin_width = self.hex_view.theme.calc_offset_margin_width(self.hex_view.font_measure);
+            let mut offset = line * numbers_in_row;
 
-            let last_x = offset_margin_width + (numbers_in_row as f32) * cell_size + self.hex_view.theme.hex_ascii_spacing();
             while offset < buffer.len() {
-                let line_y = line * self.hex_view.font_measure.height - y;
+                let line_y = self.hex_view.row_y(line) - y;
                 if line_y > viewport.height {
                     break;
                 }
-                let text = Text {
-                    font: self.hex_view.font,
-                    size: iced::Pixels(self.hex_view.font_size),
-                    color: self.hex_view.theme.offset_number,
-                    content: format!("{:08X} ", offset),
-                    position: iced::Point::new(0.0, line_y),
-                    line_height: LineHeight::Relative(1.0),
-                    horizontal_alignment: iced::alignment::Horizontal::Left,
-                    vertical_alignment: iced::alignment::Vertical::Top,
-                    shaping: Shaping::Advanced,
-                };
+                frame.fill_text(self.row_text(self.hex_view.gutter_label(offset), Point::new(0.0, line_y), self.hex_view.theme.offset_number));
 
-                frame.fill_text(text);
-
-                for i in 0..numbers_in_row {
-                    let o = offset + i;
-                    if o >= buffer.len() {
-                        break;
-                    }
-                    let x = i as f32 * cell_size + offset_margin_width;
-                    let text = Text {
-                        font: self.hex_view.font,
-                        size: iced::Pixels(self.hex_view.font_size),
-                        color: self.hex_view.theme.hex,
-                        content: format!("{:02X} ", buffer.get_byte(o)),
-                        position: iced::Point::new(x, line_y),
-                        line_height: LineHeight::Relative(1.0),
-                        horizontal_alignment: iced::alignment::Horizontal::Left,
-                        vertical_alignment: iced::alignment::Vertical::Top,
-                        shaping: Shaping::Advanced,
-                    };
-                    frame.fill_text(text);
-
-                    let x = i as f32 * self.hex_view.font_measure.width + last_x;
-                    let ch = buffer.get_byte(o) as char;
-                    let text = Text {
-                        font: self.hex_view.font,
-                        size: iced::Pixels(self.hex_view.font_size),
-                        color: self.hex_view.theme.ascii,
-                        content: format!("{} ", if char::is_ascii_control(&ch) { '.' } else { ch }),
-                        position: iced::Point::new(x, line_y),
-                        line_height: LineHeight::Relative(1.0),
-                        horizontal_alignment: iced::alignment::Horizontal::Left,
-                        vertical_alignment: iced::alignment::Vertical::Top,
-                        shaping: Shaping::Advanced,
-                    };
-                    frame.fill_text(text);
-                }
-                line += 1.0;
-                offset += numbers_in_row;
-            }
-            let caret_line = self.hex_view.cursor.position / (numbers_in_row * 2);
-            let caret_line_offset = self.hex_view.cursor.position % (numbers_in_row * 2);
+                let row = offset..offset + numbers_in_row;
+                if self.hex_view.bookmarks.iter().any(|bookmark| row.contains(bookmark)) {
+                    frame.fill_rectangle(
+                        Point::new(0.0, line_y),
+                        Size::new(3.0, self.hex_view.font_measure.height),
+                        self.hex_view.theme.bookmark,
+                    );
+                }
 
-            let caret_cell = caret_line_offset / 2;
+                let row_end = (offset + numbers_in_row).min(buffer.len());
+                if let Some(record_size) = self.hex_view.record_size {
+                    self.draw_record_separators(frame, offset..row_end, record_size, line_y);
+                }
 
-            let y = caret_line as f32 * self.hex_view.font_measure.height - y;
-            let mut x = caret_cell as f32 * cell_size + offset_margin_width;
-            let c = self.hex_view.theme.caret;
-            if self.hex_view.cursor.in_hex == EditMode::Hex {
-                if caret_line_offset % 2 != 0 {
-                    x += self.hex_view.font_measure.width;
+                if self.hex_view.numeric_column != NumericColumn::None {
+                    let values = self.hex_view.numeric_column.format_row(&buffer.data[offset..row_end], self.hex_view.endian);
+                    let x = self.hex_view.extra_columns_x(numbers_in_row);
+                    frame.fill_text(self.row_text(values, Point::new(x, line_y), self.hex_view.theme.hex));
                 }
-                frame.fill_rectangle(Point::new(x, y), self.hex_view.font_measure, c);
-            } else {
-                frame.stroke_rectangle(
-                    Point::new(x, y),
-                    Size::new(self.hex_view.font_measure.width * 2.0, self.hex_view.font_measure.height),
-                    Stroke::default().with_color(c),
-                );
+                if let Some(checksum) = self.hex_view.row_checksum.compute(&buffer.data[offset..row_end]) {
+                    let x = self.hex_view.checksum_column_x(numbers_in_row);
+                    frame.fill_text(self.row_text(format!("{:02X}", checksum), Point::new(x, line_y), self.hex_view.theme.offset_number));
+                }
+                line += 1;
+                offset += numbers_in_row;
             }
-
-            let x: f32 = last_x + caret_cell as f32 * self.hex_view.font_measure.width;
-            if self.hex_view.cursor.in_hex == EditMode::Hex {
-                frame.stroke_rectangle(Point::new(x, y), self.hex_view.font_measure, Stroke::default().with_color(c));
-            } else {
-                frame.fill_rectangle(Point::new(x, y), self.hex_view.font_measure, c);
+            if buffer.is_empty() {
+                frame.fill_text(self.row_text(
+                    crate::fl!("empty-file"),
+                    Point::new(self.hex_view.layout().hex_x(1), -y),
+                    self.hex_view.theme.offset_number,
+                ));
             }
+            self.draw_markers(frame, buffer, viewport.size());
         });
 
+        // The tooltip follows the pointer, so it is drawn fresh every frame instead of cached.
+        // Holding Ctrl shows the value preview right away instead of waiting for the delay.
+        let state = tree.state.downcast_ref::<State>();
+        let preview = state.modifiers.control();
+        let tooltip = state
+            .hover
+            .filter(|since| (preview || since.elapsed() >= TOOLTIP_DELAY) && !state.dragging)
+            .and(cursor.position_over(bounds))
+            .and_then(|position| {
+                let offset = self.hex_view.offset_at(Point::new(position.x - bounds.x, position.y - bounds.y))?;
+                let lines = if preview {
+                    self.value_preview_lines(buffer, offset)
+                } else {
+                    self.tooltip_lines(offset)
+                };
+                let mut frame = Frame::new(renderer, viewport.size());
+                let point = Point::new(position.x - viewport.x, position.y - viewport.y);
+                self.draw_tooltip(&mut frame, point, &lines, viewport.size());
+                Some(frame.into_geometry())
+            });
+
         use iced::advanced::Renderer as _;
-        renderer.with_translation(Vector::new(bounds.x, viewport.y), |renderer| {
+        renderer.with_translation(Vector::new(viewport.x, viewport.y), |renderer| {
             use iced::advanced::graphics::geometry::Renderer as _;
+            renderer.draw_geometry(overlay);
             renderer.draw_geometry(geometry);
+            self.draw_cells(renderer, buffer, viewport.size(), x, y);
+            // Text is drawn above all geometry of a layer, the tooltip needs its own to cover the cells.
+            if let Some(tooltip) = tooltip {
+                renderer.with_layer(Rectangle::new(Point::ORIGIN, viewport.size()), |renderer| {
+                    renderer.draw_geometry(tooltip);
+                });
+            }
         });
     }
 
@@ -213,24 +603,48 @@ impl<'a> Widget<Message, Theme, Renderer> for HexViewWidget<'a> {
     ) -> event::Status {
         let state = tree.state.downcast_mut::<State>();
 
+        // Focus may have been moved by a widget operation (e.g. the find bar taking focus),
+        // keep the view in sync so it stops treating keystrokes as edits.
+        if state.is_focused != self.hex_view.cursor.focus {
+            shell.publish(Message::SetFocus(state.is_focused));
+        }
+        if self.hex_view.scroll_anchor.get().is_some() {
+            shell.publish(Message::RestoreAnchor);
+        }
+
         let bounds = layout.bounds();
         match _event {
-            iced::Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) => {
+            iced::Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, text, .. }) => {
                 if state.is_focused {
                     match key {
-                        Key::Named(keyboard::key::Named::ArrowDown) => {
-                            let numbers_in_row = self.hex_view.numbers_in_row() * 2;
-                            shell.publish(Message::MoveCaret(self.hex_view.cursor.position + numbers_in_row));
-                        }
-                        Key::Named(keyboard::key::Named::ArrowUp) => {
-                            let numbers_in_row = self.hex_view.numbers_in_row() * 2;
-                            shell.publish(Message::MoveCaret(self.hex_view.cursor.position.saturating_sub(numbers_in_row)));
+                        // Modified arrows are navigation shortcuts handled by the application key binds.
+                        Key::Named(
+                            keyboard::key::Named::ArrowDown
+                            | keyboard::key::Named::ArrowUp
+                            | keyboard::key::Named::ArrowLeft
+                            | keyboard::key::Named::ArrowRight,
+                        ) if modifiers.control() || modifiers.alt() => {
+                            return event::Status::Ignored;
                         }
-                        Key::Named(keyboard::key::Named::ArrowLeft) => {
-                            shell.publish(Message::MoveCaret(self.hex_view.cursor.position.saturating_sub(1)));
-                        }
-                        Key::Named(keyboard::key::Named::ArrowRight) => {
-                            shell.publish(Message::MoveCaret(self.hex_view.cursor.position + 1));
+                        Key::Named(
+                            named @ (keyboard::key::Named::ArrowDown
+                            | keyboard::key::Named::ArrowUp
+                            | keyboard::key::Named::ArrowLeft
+                            | keyboard::key::Named::ArrowRight),
+                        ) => {
+                            // Repeats are generated here while the key is held, so the
+                            // compositor's own repeat events are dropped.
+                            if state.held_key.as_ref().is_some_and(|held| held.key == named) {
+                                return event::Status::Captured;
+                            }
+                            shell.publish(self.arrow_move(named, 1));
+                            let now = Instant::now();
+                            state.held_key = Some(HeldKey {
+                                key: named,
+                                since: now,
+                                next: now + KEY_REPEAT_DELAY,
+                            });
+                            shell.request_redraw(window::RedrawRequest::At(now + KEY_REPEAT_DELAY));
                         }
                         Key::Named(keyboard::key::Named::Home) => {
                             if modifiers.control() || modifiers.macos_command() {
@@ -244,9 +658,7 @@ impl<'a> Widget<Message, Theme, Renderer> for HexViewWidget<'a> {
                         }
                         Key::Named(keyboard::key::Named::End) => {
                             if modifiers.control() || modifiers.macos_command() {
-                                if let Some(buffer) = &self.hex_view.buffer {
-                                    shell.publish(Message::MoveCaret(buffer.len().saturating_sub(1) * 2));
-                                }
+                                shell.publish(Message::MoveCaret(self.hex_view.max_caret_position()));
                             } else {
                                 let numbers_in_row = self.hex_view.numbers_in_row() * 2;
                                 let pos = self.hex_view.cursor.position - self.hex_view.cursor.position % numbers_in_row + numbers_in_row - 2;
@@ -256,35 +668,308 @@ impl<'a> Widget<Message, Theme, Renderer> for HexViewWidget<'a> {
                         Key::Named(keyboard::key::Named::Tab) => {
                             shell.publish(Message::SwitchMode);
                         }
+                        Key::Named(keyboard::key::Named::Insert) if modifiers.command() => {
+                            self.copy(_clipboard);
+                        }
+                        Key::Named(keyboard::key::Named::Insert) if modifiers.shift() => {
+                            self.paste(_clipboard, shell);
+                        }
+                        Key::Named(keyboard::key::Named::Insert) => {
+                            shell.publish(Message::ToggleInsertMode);
+                        }
+                        Key::Character(ref c) if modifiers.command() && c.as_str() == "c" => {
+                            self.copy(_clipboard);
+                        }
+                        Key::Character(ref c) if modifiers.command() && c.as_str() == "v" => {
+                            self.paste(_clipboard, shell);
+                        }
+                        // Alt+PageUp/Down moves by records through the application key binds.
+                        Key::Named(keyboard::key::Named::PageUp | keyboard::key::Named::PageDown) if modifiers.alt() => {
+                            return event::Status::Ignored;
+                        }
                         Key::Named(keyboard::key::Named::PageUp) => {
                             shell.publish(Message::PageUp);
                         }
                         Key::Named(keyboard::key::Named::PageDown) => {
                             shell.publish(Message::PageDown);
                         }
-                        Key::Character(ch) => {
-                            let str = ch.to_string();
-                            if str.len() == 1 {
-                                let ch = str.chars().next().unwrap();
+                        _ => {
+                            // Shortcuts belong to the application key binds.
+                            if modifiers.control() || modifiers.alt() || modifiers.logo() {
+                                return event::Status::Ignored;
+                            }
+                            // Use the text the key produced instead of the key itself, so dead key
+                            // compositions and characters from non-Latin layouts come through.
+                            let Some(text) = text.filter(|text| !text.chars().any(char::is_control)) else {
+                                return event::Status::Ignored;
+                            };
+                            for ch in text.chars() {
                                 shell.publish(Message::TypeChar(ch));
                             }
                         }
-                        _ => {}
                     }
+                    return event::Status::Captured;
+                }
+            }
+
+            iced::Event::Keyboard(keyboard::Event::KeyReleased { key: Key::Named(named), .. }) => {
+                if state.held_key.as_ref().is_some_and(|held| held.key == named) {
+                    state.held_key = None;
+                }
+            }
+
+            iced::Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                if state.hover.is_some() && modifiers.control() != state.modifiers.control() {
+                    shell.request_redraw(window::RedrawRequest::NextFrame);
+                }
+                state.modifiers = modifiers;
+            }
+
+            Event::Window(window::Event::RedrawRequested(now)) => {
+                if let Some(touch) = state.touch.as_mut().filter(|touch| touch.mode == TouchMode::Pending) {
+                    // Holding a finger still starts a selection, lifting it without moving opens the context menu.
+                    if now - touch.since >= LONG_PRESS {
+                        touch.mode = TouchMode::Select { moved: false };
+                        state.is_focused = true;
+                        shell.publish(Message::SetFocus(true));
+                        shell.publish(Message::Click(widget_point(touch.start, bounds)));
+                    } else {
+                        shell.request_redraw(window::RedrawRequest::At(touch.since + LONG_PRESS));
+                    }
+                }
+                if let Some((velocity, last)) = state.fling {
+                    let elapsed = (now - last).as_secs_f32();
+                    shell.publish(Message::ScrollPixels(velocity * elapsed));
+                    let velocity = velocity * (-FLING_FRICTION * elapsed).exp();
+                    state.fling = (velocity.abs() > FLING_STOP_VELOCITY).then_some((velocity, now));
+                    shell.request_redraw(window::RedrawRequest::NextFrame);
+                }
+                if !state.is_focused {
+                    state.held_key = None;
+                }
+                if let Some(held) = &mut state.held_key {
+                    if now >= held.next {
+                        shell.publish(self.arrow_move(held.key, key_repeat_steps(now - held.since)));
+                        held.next = now + KEY_REPEAT_INTERVAL;
+                    }
+                    shell.request_redraw(window::RedrawRequest::At(held.next));
+                }
+            }
+
+            // Shift turns the wheel sideways. Trackpads send both axes at once.
+            Event::Mouse(mouse::Event::WheelScrolled { delta }) if cursor.is_over(bounds) => {
+                let swap = |x: f32, y: f32| if state.modifiers.shift() { (y, x) } else { (x, y) };
+                match delta {
+                    mouse::ScrollDelta::Lines { x, y } => {
+                        let (x, y) = swap(x, y);
+                        if x != 0.0 {
+                            let columns = x * self.hex_view.scroll_lines as f32;
+                            shell.publish(Message::ScrollHorizontal(-columns * self.hex_view.layout().cell_width));
+                        }
+                        if y != 0.0 {
+                            shell.publish(Message::Scroll(y));
+                        }
+                    }
+                    mouse::ScrollDelta::Pixels { x, y } => {
+                        let (x, y) = swap(x, y);
+                        if x != 0.0 {
+                            shell.publish(Message::ScrollHorizontal(-x));
+                        }
+                        if y != 0.0 {
+                            shell.publish(Message::ScrollPixels(-y));
+                        }
+                    }
+                }
+                return event::Status::Captured;
+            }
+
+            Event::Mouse(mouse::Event::CursorMoved { position }) if state.dragging => {
+                // The cursor is unavailable once it leaves the scrollable, so map the raw
+                // window position into widget coordinates.
+                shell.publish(Message::Drag(widget_point(position, bounds)));
+            }
+
+            // Restart the tooltip delay whenever the pointer moves.
+            Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                let now = Instant::now();
+                state.hover = cursor.is_over(bounds).then_some(now);
+                if state.hover.is_some() {
+                    shell.request_redraw(window::RedrawRequest::At(now + TOOLTIP_DELAY));
                 }
             }
 
-            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) | Event::Touch(touch::Event::FingerPressed { .. }) => {
+            Event::Mouse(mouse::Event::CursorLeft) => {
+                state.hover = None;
+            }
+
+            Event::Touch(touch::Event::FingerPressed { id, position })
+                if Rectangle {
+                    height: _viewport.height,
+                    ..bounds
+                }
+                .contains(position) =>
+            {
+                state.fling = None;
+                state.fingers.push((id, position));
+                match state.fingers.len() {
+                    1 => {
+                        let now = Instant::now();
+                        state.touch = Some(TouchState {
+                            finger: id,
+                            start: position,
+                            last: position,
+                            since: now,
+                            last_move: now,
+                            velocity: 0.0,
+                            mode: TouchMode::Pending,
+                        });
+                        shell.request_redraw(window::RedrawRequest::At(now + LONG_PRESS));
+                    }
+                    2 => {
+                        if state.touch.take().is_some_and(|touch| matches!(touch.mode, TouchMode::Select { .. })) {
+                            shell.publish(Message::EndDrag);
+                        }
+                        state.pinch = Some(finger_distance(&state.fingers));
+                    }
+                    _ => {}
+                }
+                return event::Status::Captured;
+            }
+
+            Event::Touch(touch::Event::FingerMoved { id, position }) => {
+                let Some(finger) = state.fingers.iter_mut().find(|(finger, _)| *finger == id) else {
+                    return event::Status::Ignored;
+                };
+                finger.1 = position;
+                if let Some(base) = state.pinch {
+                    let distance = finger_distance(&state.fingers);
+                    if distance > base * PINCH_STEP {
+                        shell.publish(Message::Zoom(1));
+                        state.pinch = Some(distance);
+                    } else if distance < base / PINCH_STEP {
+                        shell.publish(Message::Zoom(-1));
+                        state.pinch = Some(distance);
+                    }
+                    return event::Status::Captured;
+                }
+                let Some(touch) = state.touch.as_mut().filter(|touch| touch.finger == id) else {
+                    return event::Status::Ignored;
+                };
+                if touch.mode == TouchMode::Pending && touch.start.distance(position) > TOUCH_SLOP {
+                    touch.mode = TouchMode::Scroll;
+                }
+                match touch.mode {
+                    TouchMode::Pending => {}
+                    TouchMode::Scroll => {
+                        let now = Instant::now();
+                        let delta = touch.last.y - position.y;
+                        let elapsed = (now - touch.last_move).as_secs_f32().max(0.001);
+                        touch.velocity = touch.velocity * 0.2 + delta / elapsed * 0.8;
+                        touch.last_move = now;
+                        shell.publish(Message::ScrollPixels(delta));
+                    }
+                    TouchMode::Select { .. } => {
+                        touch.mode = TouchMode::Select { moved: true };
+                        shell.publish(Message::Drag(widget_point(position, bounds)));
+                    }
+                }
+                touch.last = position;
+                return event::Status::Captured;
+            }
+
+            Event::Touch(touch::Event::FingerLifted { id, position } | touch::Event::FingerLost { id, position }) => {
+                let Some(index) = state.fingers.iter().position(|(finger, _)| *finger == id) else {
+                    return event::Status::Ignored;
+                };
+                state.fingers.remove(index);
+                if state.fingers.len() < 2 {
+                    state.pinch = None;
+                }
+                let Some(touch) = state.touch.take_if(|touch| touch.finger == id) else {
+                    return event::Status::Captured;
+                };
+                match touch.mode {
+                    // A tap places the caret like a click.
+                    TouchMode::Pending => {
+                        state.is_focused = true;
+                        shell.publish(Message::SetFocus(true));
+                        shell.publish(Message::Click(widget_point(position, bounds)));
+                        shell.publish(Message::EndDrag);
+                    }
+                    TouchMode::Scroll => {
+                        if touch.velocity.abs() > FLING_MIN_VELOCITY && touch.last_move.elapsed() < FLING_MAX_PAUSE {
+                            state.fling = Some((touch.velocity, Instant::now()));
+                            shell.request_redraw(window::RedrawRequest::NextFrame);
+                        }
+                    }
+                    TouchMode::Select { moved } => {
+                        shell.publish(Message::EndDrag);
+                        if !moved {
+                            shell.publish(Message::LongPress(Point::new(position.x - bounds.x, position.y - bounds.y)));
+                        }
+                    }
+                }
+                return event::Status::Captured;
+            }
+
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) if state.dragging => {
+                state.dragging = false;
+                shell.publish(Message::EndDrag);
+            }
+
+            // Middle click pastes the primary selection at the caret, like in terminals.
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Middle)) if cursor.is_over(bounds) => {
+                state.is_focused = true;
+                shell.publish(Message::SetFocus(true));
+                if let Some(text) = _clipboard.read(iced_core::clipboard::Kind::Primary) {
+                    shell.publish(Message::Paste(text));
+                }
+                return event::Status::Captured;
+            }
+
+            // Right clicking the offset gutter selects the row the context menu acts on.
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Right)) => {
+                if let Some(mut pos) = cursor.position_over(bounds) {
+                    pos.x -= bounds.x;
+                    pos.y -= bounds.y;
+                    if pos.x < self.hex_view.layout().gutter_width {
+                        shell.publish(Message::Click(pos));
+                    }
+                }
+            }
+
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                state.hover = None;
                 if cursor.is_over(bounds) {
                     state.is_focused = true;
                     shell.publish(Message::SetFocus(true));
                     if let Some(mut pos) = cursor.position() {
-                        println!("pos: {:?} bounds:{:?}", pos, bounds);
+                        let click = click::Click::new(pos, mouse::Button::Left, state.last_click);
+                        state.last_click = Some(click);
 
                         pos.x -= bounds.x;
                         pos.y -= bounds.y;
 
-                        shell.publish(Message::Click(pos));
+                        match click.kind() {
+                            click::Kind::Double => {
+                                let group_width = if state.modifiers.control() {
+                                    8
+                                } else if state.modifiers.shift() {
+                                    2
+                                } else {
+                                    4
+                                };
+                                shell.publish(Message::DoubleClick(pos, group_width));
+                            }
+                            _ if state.modifiers.alt() => {
+                                state.dragging = true;
+                                shell.publish(Message::BlockClick(pos));
+                            }
+                            _ => {
+                                state.dragging = true;
+                                shell.publish(Message::Click(pos));
+                            }
+                        }
                     }
                 } else {
                     state.is_focused = false;
@@ -306,11 +991,35 @@ impl<'a> From<HexViewWidget<'a>> for Element<'a, Message, Theme, iced::Renderer>
 
 pub struct State {
     pub is_focused: bool,
+    pub modifiers: keyboard::Modifiers,
+    pub last_click: Option<click::Click>,
+    pub dragging: bool,
+    pub held_key: Option<HeldKey>,
+    /// Fingers currently touching the view.
+    pub fingers: Vec<(touch::Finger, Point)>,
+    pub touch: Option<TouchState>,
+    /// Finger distance at the last pinch zoom step.
+    pub pinch: Option<f32>,
+    /// Velocity and time of the last kinetic scroll step.
+    pub fling: Option<(f32, Instant)>,
+    /// Time the pointer last moved over the view.
+    pub hover: Option<Instant>,
 }
 
 impl State {
     pub fn new() -> State {
-        State { is_focused: false }
+        State {
+            is_focused: false,
+            modifiers: keyboard::Modifiers::default(),
+            last_click: None,
+            dragging: false,
+            held_key: None,
+            fingers: Vec::new(),
+            touch: None,
+            pinch: None,
+            fling: None,
+            hover: None,
+        }
     }
 }
 
@@ -0,0 +1,106 @@
+//! Highlight layers: independent sets of byte ranges, each with its own color, that can be hidden
+//! one by one. They are drawn below the selection in the order of [`Layer::ALL`], later layers on top.
+
+use std::ops::Range;
+
+use cosmic::iced::Color;
+use serde::{Deserialize, Serialize};
+
+use super::theme::Theme;
+use crate::fl;
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum Layer {
+    /// Imported regions and other user annotations.
+    Annotation,
+    /// Fields of a decoded structure, e.g. protobuf.
+    Template,
+    /// Bytes that differ in a comparison.
+    Diff,
+    /// Search and YARA matches.
+    Search,
+}
+
+impl Layer {
+    pub const ALL: [Layer; 4] = [Layer::Annotation, Layer::Template, Layer::Diff, Layer::Search];
+
+    pub fn name(self) -> String {
+        match self {
+            Layer::Annotation => fl!("layer-annotation"),
+            Layer::Template => fl!("layer-template"),
+            Layer::Diff => fl!("layer-diff"),
+            Layer::Search => fl!("layer-search"),
+        }
+    }
+
+    /// Color used unless the settings name one. Translucent, so overlapping layers stay visible.
+    pub fn default_color(self, theme: &Theme) -> Color {
+        match self {
+            Layer::Annotation => theme.annotation,
+            Layer::Template => Color::from_rgba8(160, 120, 220, 0.5),
+            Layer::Diff => Color { a: 0.6, ..theme.modified },
+            Layer::Search => theme.search_match,
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Parses `#RRGGBB` or `#RRGGBBAA`.
+pub fn parse_color(input: &str) -> Option<Color> {
+    let digits = input.trim().trim_start_matches('#');
+    if (digits.len() != 6 && digits.len() != 8) || !digits.is_ascii() {
+        return None;
+    }
+    let mut rgba = [0xFF; 4];
+    for (i, chunk) in digits.as_bytes().chunks(2).enumerate() {
+        rgba[i] = u8::from_str_radix(std::str::from_utf8(chunk).ok()?, 16).ok()?;
+    }
+    Some(Color::from_rgba8(rgba[0], rgba[1], rgba[2], rgba[3] as f32 / 255.0))
+}
+
+#[derive(Clone, Debug)]
+pub struct Layers {
+    ranges: [Vec<Range<usize>>; 4],
+    colors: [Color; 4],
+    visible: [bool; 4],
+}
+
+impl Default for Layers {
+    fn default() -> Self {
+        Self {
+            ranges: Default::default(),
+            colors: Layer::ALL.map(|layer| layer.default_color(&Theme::new())),
+            visible: [true; 4],
+        }
+    }
+}
+
+impl Layers {
+    pub fn set(&mut self, layer: Layer, ranges: Vec<Range<usize>>) {
+        self.ranges[layer.index()] = ranges;
+    }
+
+    pub fn extend(&mut self, layer: Layer, ranges: impl IntoIterator<Item = Range<usize>>) {
+        self.ranges[layer.index()].extend(ranges);
+    }
+
+    pub fn clear(&mut self) {
+        self.ranges.iter_mut().for_each(Vec::clear);
+    }
+
+    pub fn set_style(&mut self, layer: Layer, color: Color, visible: bool) {
+        self.colors[layer.index()] = color;
+        self.visible[layer.index()] = visible;
+    }
+
+    /// The ranges of the shown layers with their colors, bottom layer first.
+    pub fn visible(&self) -> impl Iterator<Item = (Color, &[Range<usize>])> {
+        Layer::ALL
+            .into_iter()
+            .filter(|layer| self.visible[layer.index()] && !self.ranges[layer.index()].is_empty())
+            .map(|layer| (self.colors[layer.index()], self.ranges[layer.index()].as_slice()))
+    }
+}
@@ -0,0 +1,147 @@
+//! IPS patches: records of bytes to write at 24 bit offsets, with an optional truncation length.
+
+const HEADER: &[u8] = b"PATCH";
+const FOOTER: &[u8] = b"EOF";
+/// Offset that reads as the footer, records can't start there.
+const FOOTER_OFFSET: usize = 0x454F46;
+const MAX_OFFSET: usize = 0xFF_FFFF;
+const MAX_RECORD_LEN: usize = 0xFFFF;
+
+/// Writes the records of `patch` into `data`, growing it as needed.
+pub fn apply(data: &mut Vec<u8>, patch: &[u8]) -> Result<(), String> {
+    let mut rest = patch.strip_prefix(HEADER).ok_or("not an IPS patch")?;
+    loop {
+        if let Some(truncate) = rest.strip_prefix(FOOTER) {
+            if let [a, b, c] = truncate {
+                data.truncate(u32::from_be_bytes([0, *a, *b, *c]) as usize);
+            }
+            return Ok(());
+        }
+        let [o1, o2, o3, s1, s2, tail @ ..] = rest else {
+            return Err("patch ends in the middle of a record".to_string());
+        };
+        let offset = u32::from_be_bytes([0, *o1, *o2, *o3]) as usize;
+        let size = u16::from_be_bytes([*s1, *s2]) as usize;
+        let (bytes, tail) = if size == 0 {
+            let [c1, c2, value, tail @ ..] = tail else {
+                return Err("patch ends in the middle of a record".to_string());
+            };
+            (vec![*value; u16::from_be_bytes([*c1, *c2]) as usize], tail)
+        } else {
+            let bytes = tail.get(..size).ok_or("patch ends in the middle of a record")?;
+            (bytes.to_vec(), &tail[size..])
+        };
+        if data.len() < offset + bytes.len() {
+            data.resize(offset + bytes.len(), 0);
+        }
+        data[offset..offset + bytes.len()].copy_from_slice(&bytes);
+        rest = tail;
+    }
+}
+
+/// Creates a patch turning `original` into `modified`. Fails if a change lies beyond the
+/// 16 MiB IPS can address.
+pub fn create(original: &[u8], modified: &[u8]) -> Result<Vec<u8>, String> {
+    let mut patch = HEADER.to_vec();
+    let differs = |i: usize| original.get(i) != modified.get(i);
+    let mut i = 0;
+    while i < modified.len() {
+        if !differs(i) {
+            i += 1;
+            continue;
+        }
+        // Back up one byte rather than writing a record that reads as the footer.
+        let start = if i == FOOTER_OFFSET { i - 1 } else { i };
+        let mut end = i;
+        while end < modified.len() && end - start < MAX_RECORD_LEN && differs(end) {
+            end += 1;
+        }
+        if start > MAX_OFFSET {
+            return Err(format!("change at 0x{:X} is beyond the 16 MiB IPS can address", start));
+        }
+        patch.extend_from_slice(&(start as u32).to_be_bytes()[1..]);
+        patch.extend_from_slice(&((end - start) as u16).to_be_bytes());
+        patch.extend_from_slice(&modified[start..end]);
+        i = end;
+    }
+    patch.extend_from_slice(FOOTER);
+    if modified.len() < original.len() {
+        if modified.len() > MAX_OFFSET {
+            return Err(format!("length 0x{:X} is beyond the 16 MiB IPS can address", modified.len()));
+        }
+        patch.extend_from_slice(&(modified.len() as u32).to_be_bytes()[1..]);
+    }
+    Ok(patch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(original: &[u8], modified: &[u8]) -> Vec<u8> {
+        let patch = create(original, modified).unwrap();
+        let mut data = original.to_vec();
+        apply(&mut data, &patch).unwrap();
+        assert_eq!(data, modified);
+        patch
+    }
+
+    #[test]
+    fn round_trip_of_changes_and_growth() {
+        let original = vec![0; 32];
+        let mut modified = original.clone();
+        modified[3] = 1;
+        modified[10..14].copy_from_slice(&[1, 2, 3, 4]);
+        modified.extend_from_slice(&[5, 6]);
+        let patch = round_trip(&original, &modified);
+        assert_eq!(&patch[..5], HEADER);
+        assert_eq!(&patch[5..11], &[0, 0, 3, 0, 1, 1]);
+        assert_eq!(&patch[patch.len() - 3..], FOOTER);
+    }
+
+    #[test]
+    fn unchanged_data_gives_an_empty_patch() {
+        assert_eq!(round_trip(&[1, 2, 3], &[1, 2, 3]), b"PATCHEOF");
+    }
+
+    #[test]
+    fn record_at_the_footer_offset_starts_a_byte_earlier() {
+        let original = vec![0; FOOTER_OFFSET + 2];
+        let mut modified = original.clone();
+        modified[FOOTER_OFFSET] = 0xFF;
+        let patch = round_trip(&original, &modified);
+        assert_eq!(&patch[5..8], &((FOOTER_OFFSET - 1) as u32).to_be_bytes()[1..]);
+        assert_eq!(&patch[8..10], &[0, 2]);
+        assert_eq!(&patch[10..12], &[0, 0xFF]);
+    }
+
+    #[test]
+    fn shorter_data_is_truncated() {
+        let patch = round_trip(&[1, 2, 3, 4, 5], &[1, 9, 3]);
+        assert_eq!(&patch[patch.len() - 6..], &[b'E', b'O', b'F', 0, 0, 3]);
+    }
+
+    #[test]
+    fn changes_beyond_16_mib_fail() {
+        let original = vec![0; MAX_OFFSET + 2];
+        let mut modified = original.clone();
+        modified[MAX_OFFSET + 1] = 1;
+        assert!(create(&original, &modified).is_err());
+    }
+
+    #[test]
+    fn apply_run_length_records() {
+        // Four times 0xAA at offset 2.
+        let mut data = vec![0; 3];
+        apply(&mut data, b"PATCH\x00\x00\x02\x00\x00\x00\x04\xAAEOF").unwrap();
+        assert_eq!(data, vec![0, 0, 0xAA, 0xAA, 0xAA, 0xAA]);
+    }
+
+    #[test]
+    fn apply_rejects_broken_patches() {
+        let mut data = vec![0; 4];
+        assert!(apply(&mut data, b"PATCHED").is_err());
+        assert!(apply(&mut data, b"PATCH\x00\x00\x01\x00\x04\x01").is_err());
+        assert!(apply(&mut data, b"NOPE").is_err());
+    }
+}
@@ -0,0 +1,95 @@
+//! Offset/name/size listings exported from disassemblers such as Ghidra or IDA.
+//!
+//! Columns are separated by commas, tabs or spaces. A header line naming the columns picks their
+//! order, otherwise it is address, name and an optional size. Addresses are hex like disassemblers
+//! print them, IDA style `segment:address` works too. Sizes are decimal unless prefixed with `0x`.
+
+use super::parse_offset;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub address: u64,
+    pub name: String,
+    /// Zero for labels without a size, these only become bookmarks.
+    pub size: usize,
+}
+
+/// Column indices of address, name and size.
+struct Columns {
+    address: usize,
+    name: usize,
+    size: Option<usize>,
+}
+
+impl Default for Columns {
+    fn default() -> Self {
+        Self {
+            address: 0,
+            name: 1,
+            size: Some(2),
+        }
+    }
+}
+
+impl Columns {
+    /// Reads a header line, `None` if it doesn't name an address column.
+    fn from_header(fields: &[String]) -> Option<Self> {
+        let find = |names: &[&str]| fields.iter().position(|field| names.iter().any(|name| field.to_lowercase().contains(name)));
+        Some(Self {
+            address: find(&["address", "offset", "location", "start"])?,
+            name: find(&["name", "label", "symbol"])?,
+            size: find(&["size", "length"]),
+        })
+    }
+}
+
+fn split(line: &str) -> Vec<String> {
+    let fields: Vec<&str> = if line.contains(',') {
+        line.split(',').collect()
+    } else if line.contains('\t') {
+        line.split('\t').collect()
+    } else {
+        line.split_whitespace().collect()
+    };
+    fields.into_iter().map(|field| field.trim().trim_matches('"').to_string()).collect()
+}
+
+fn parse_address(field: &str) -> Option<u64> {
+    let field = field.rsplit(':').next()?.trim();
+    let hex = field
+        .strip_prefix("0x")
+        .or_else(|| field.strip_prefix("0X"))
+        .or_else(|| field.strip_suffix('h'))
+        .unwrap_or(field);
+    u64::from_str_radix(hex, 16).ok()
+}
+
+pub fn parse(text: &str) -> Result<Vec<Entry>, String> {
+    let mut columns = None;
+    let mut entries = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with('#') || line.starts_with("//") {
+            continue;
+        }
+        let fields = split(line);
+        if columns.is_none() {
+            if let Some(header) = Columns::from_header(&fields) {
+                columns = Some(header);
+                continue;
+            }
+        }
+        let columns = columns.get_or_insert_with(Columns::default);
+        let address = fields
+            .get(columns.address)
+            .and_then(|field| parse_address(field))
+            .ok_or_else(|| format!("line {}: invalid address", number + 1))?;
+        let name = fields.get(columns.name).cloned().unwrap_or_default();
+        let size = columns
+            .size
+            .and_then(|column| fields.get(column))
+            .and_then(|field| parse_offset(field))
+            .unwrap_or(0);
+        entries.push(Entry { address, name, size });
+    }
+    Ok(entries)
+}
@@ -0,0 +1,163 @@
+//! File type detection by magic numbers.
+
+struct Magic {
+    offset: usize,
+    signature: &'static [u8],
+    mime: &'static str,
+    description: &'static str,
+}
+
+const MAGICS: &[Magic] = &[
+    Magic {
+        offset: 0,
+        signature: b"\x89PNG\r\n\x1a\n",
+        mime: "image/png",
+        description: "PNG image",
+    },
+    Magic {
+        offset: 0,
+        signature: b"\xFF\xD8\xFF",
+        mime: "image/jpeg",
+        description: "JPEG image",
+    },
+    Magic {
+        offset: 0,
+        signature: b"GIF8",
+        mime: "image/gif",
+        description: "GIF image",
+    },
+    Magic {
+        offset: 0,
+        signature: b"BM",
+        mime: "image/bmp",
+        description: "BMP image",
+    },
+    Magic {
+        offset: 0,
+        signature: b"%PDF-",
+        mime: "application/pdf",
+        description: "PDF document",
+    },
+    Magic {
+        offset: 0,
+        signature: b"PK\x03\x04",
+        mime: "application/zip",
+        description: "ZIP archive",
+    },
+    Magic {
+        offset: 0,
+        signature: b"\x1F\x8B",
+        mime: "application/gzip",
+        description: "gzip compressed data",
+    },
+    Magic {
+        offset: 0,
+        signature: b"BZh",
+        mime: "application/x-bzip2",
+        description: "bzip2 compressed data",
+    },
+    Magic {
+        offset: 0,
+        signature: b"\xFD7zXZ\x00",
+        mime: "application/x-xz",
+        description: "XZ compressed data",
+    },
+    Magic {
+        offset: 0,
+        signature: b"7z\xBC\xAF\x27\x1C",
+        mime: "application/x-7z-compressed",
+        description: "7-Zip archive",
+    },
+    Magic {
+        offset: 0,
+        signature: b"\x28\xB5\x2F\xFD",
+        mime: "application/zstd",
+        description: "Zstandard compressed data",
+    },
+    Magic {
+        offset: 257,
+        signature: b"ustar",
+        mime: "application/x-tar",
+        description: "tar archive",
+    },
+    Magic {
+        offset: 0,
+        signature: b"\x7FELF",
+        mime: "application/x-elf",
+        description: "ELF executable",
+    },
+    Magic {
+        offset: 0,
+        signature: b"MZ",
+        mime: "application/vnd.microsoft.portable-executable",
+        description: "DOS/Windows executable",
+    },
+    Magic {
+        offset: 0,
+        signature: b"\xCF\xFA\xED\xFE",
+        mime: "application/x-mach-binary",
+        description: "Mach-O executable",
+    },
+    Magic {
+        offset: 0,
+        signature: b"\xCA\xFE\xBA\xBE",
+        mime: "application/java-vm",
+        description: "Java class / Mach-O fat binary",
+    },
+    Magic {
+        offset: 0,
+        signature: b"\x00asm",
+        mime: "application/wasm",
+        description: "WebAssembly module",
+    },
+    Magic {
+        offset: 0,
+        signature: b"SQLite format 3\x00",
+        mime: "application/vnd.sqlite3",
+        description: "SQLite database",
+    },
+    Magic {
+        offset: 0,
+        signature: b"OggS",
+        mime: "audio/ogg",
+        description: "Ogg container",
+    },
+    Magic {
+        offset: 0,
+        signature: b"fLaC",
+        mime: "audio/flac",
+        description: "FLAC audio",
+    },
+    Magic {
+        offset: 0,
+        signature: b"ID3",
+        mime: "audio/mpeg",
+        description: "MP3 audio",
+    },
+    Magic {
+        offset: 8,
+        signature: b"WAVE",
+        mime: "audio/wav",
+        description: "WAVE audio",
+    },
+    Magic {
+        offset: 8,
+        signature: b"AVI ",
+        mime: "video/x-msvideo",
+        description: "AVI video",
+    },
+    Magic {
+        offset: 4,
+        signature: b"ftyp",
+        mime: "video/mp4",
+        description: "ISO media (MP4)",
+    },
+];
+
+/// Returns the MIME type and a description of `data` if a known signature matches.
+pub fn detect(data: &[u8]) -> Option<(&'static str, &'static str)> {
+    MAGICS
+        .iter()
+        .find(|magic| data.get(magic.offset..).is_some_and(|data| data.starts_with(magic.signature)))
+        .map(|magic| (magic.mime, magic.description))
+}
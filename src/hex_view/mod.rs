@@ -1,11 +1,36 @@
-use std::{cell::Cell, path::PathBuf};
+use std::{
+    cell::Cell,
+    collections::{BTreeMap, VecDeque},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
 
-pub mod buffer;
+pub mod arch;
+pub mod bitmap;
+pub mod bookmarks;
+pub mod checksum;
+pub mod decode;
+pub mod executable;
+pub mod export;
+pub mod glyph_cache;
 pub mod hexviewwidget;
+pub mod highlight;
+pub mod ips;
+pub mod listing;
+pub mod magic;
+pub mod numeric;
+pub mod pcm;
+pub mod protobuf;
+pub mod reorder;
 pub mod theme;
+pub mod transform;
 pub mod undo;
+pub mod xor;
+
+pub use cosmic_hex::{buffer, caret, layout, pattern, selection};
+pub use layout::EditMode;
 
-use buffer::DataBuffer;
+use buffer::{DataBuffer, Endian};
 use cosmic::iced_core::Text;
 use cosmic::{
     iced::{
@@ -22,16 +47,34 @@ use cosmic::{
     widget::Id,
     Task,
 };
+use layout::Layout;
+use selection::Selection;
 use theme::Theme;
 use undo::UndoOperation;
 
 use crate::HexResult;
 
-#[derive(Default, PartialEq, Eq, Debug)]
-pub enum EditMode {
-    #[default]
-    Hex,
-    Ascii,
+/// Number of recent edit positions remembered for the jump list.
+const MAX_EDIT_POSITIONS: usize = 16;
+
+const SCROLL_ANIMATION_DURATION: Duration = Duration::from_millis(150);
+
+/// Tallest scrollable content in pixels. f32 offsets get too coarse for exact row positions
+/// beyond this, so larger buffers map the scroll range linearly onto the rows instead.
+const MAX_CONTENT_HEIGHT: f32 = 4_000_000.0;
+
+/// A scroll from `from` to `to` that eases out over `SCROLL_ANIMATION_DURATION`.
+pub struct ScrollAnimation {
+    pub from: f32,
+    pub to: f32,
+    pub start: Instant,
+}
+
+/// A byte kept on its screen row while the rows are laid out again.
+#[derive(Clone, Copy, Debug)]
+pub struct ScrollAnchor {
+    pub offset: usize,
+    pub screen_row: f64,
 }
 
 #[derive(Default)]
@@ -46,19 +89,110 @@ pub struct HexView {
     pub path: PathBuf,
     pub theme: Theme,
     pub cache: Cache,
+    /// Background, selection and caret, see [`HexView::redraw_overlay`].
+    pub overlay: Cache,
+    /// The caches are cleared by the next draw, so bursts of redraws clear them once per frame.
+    cache_dirty: Cell<bool>,
+    overlay_dirty: Cell<bool>,
     pub font: Font,
     pub font_size: f32,
     pub scale_factor: f32,
     pub font_measure: Size<f32>,
+    /// Shaped cells of both panes for the current font and encoding.
+    pub(crate) glyphs: glyph_cache::GlyphCache,
+    /// Text pane cells are two characters wide, for fonts with wide CJK glyphs.
+    pub double_width_text: bool,
+    /// Leaves a character wide gap between the byte groups of the hex pane.
+    pub group_gaps: bool,
+    pub show_hex_pane: bool,
+    pub show_text_pane: bool,
     pub viewport: Cell<Rectangle>,
 
     pub cursor: Cursor,
+    pub selection: Option<Selection>,
+    /// Typing inserts new bytes instead of overwriting existing ones.
+    pub insert_mode: bool,
+    /// Rejects all edits to the buffer.
+    pub read_only: bool,
+    /// Shows virtual addresses of executables in the gutter and takes them for jumps.
+    pub virtual_addresses: bool,
+    /// Offset that relative offsets are measured from.
+    pub offset_base: usize,
+    /// Secondary marker, the status bar shows the distance from it to the caret.
+    pub mark: Option<usize>,
+    /// Bookmarked byte offsets, kept sorted.
+    pub bookmarks: Vec<usize>,
+    /// Notes attached to bookmarks, e.g. from an imported bookmark list.
+    pub bookmark_notes: BTreeMap<usize, String>,
+    /// Named regions imported from a disassembler listing, sorted by start offset.
+    pub regions: Vec<executable::Region>,
+    /// Offsets of the last search matches, shown next to the scrollbar.
+    pub matches: Vec<usize>,
+    /// The needle and data version `matches` were searched for, repeated searches reuse them.
+    pub matches_for: Option<(Vec<u8>, u64)>,
+    /// Sum, minimum and maximum of the selected bytes with the selection and data version they were
+    /// computed for in the background.
+    pub selection_stats: Option<((Selection, u64), (u64, u8, u8))>,
+    /// The selection and data version stats were last requested for.
+    pub stats_for: Option<(Selection, u64)>,
+    /// Highlighted byte ranges, e.g. search hits, decoded fields and differences.
+    pub highlights: highlight::Layers,
+    /// Caret offsets of the most recent edits, oldest first.
+    pub edit_positions: VecDeque<usize>,
+    /// Offset where the current drag selection started.
+    pub drag_anchor: Option<usize>,
+    /// The current drag selects a block of columns instead of a range.
+    pub drag_block: bool,
+    /// Last pointer position of the drag selection in widget coordinates.
+    pub drag_point: Point,
+    /// Pixels to scroll per auto scroll tick while the pointer is dragged outside the viewport.
+    pub drag_scroll: f32,
+    /// Animate scrolling instead of jumping.
+    pub smooth_scrolling: bool,
+    /// Rows scrolled per mouse wheel notch.
+    pub scroll_lines: usize,
+    /// Bytes per group for group wise caret movement.
+    pub group_size: usize,
+    /// Fixed bytes per row, `None` fits as many as the viewport is wide.
+    pub bytes_per_row: Option<usize>,
+    /// Added to the offsets shown in the gutter.
+    pub base_address: u64,
+    /// Size of the fixed size records the data consists of, separators are drawn between them.
+    pub record_size: Option<usize>,
+    /// Checksum shown after the text pane of every row.
+    pub row_checksum: checksum::RowChecksum,
+    /// Decimal values shown right of the text pane.
+    pub numeric_column: numeric::NumericColumn,
+    pub scroll_animation: Option<ScrollAnimation>,
+    /// Kept on its screen row once the rows were laid out again, see [`Self::anchor_caret`].
+    pub(crate) scroll_anchor: Cell<Option<ScrollAnchor>>,
+    /// Encoding used to interpret the text pane, single byte encodings also for drawing it.
+    pub encoding: &'static encoding_rs::Encoding,
+    /// Byte order of multi-byte values shown and searched for in this view.
+    pub endian: Endian,
 
     pub buffer: Option<DataBuffer>,
+    /// Sections and symbols if the data is an ELF or PE file, as of the last load or save.
+    pub image: Option<executable::Image>,
     pub last_save: usize,
+    /// Dirty state last reported through [`Message::DirtyChanged`].
+    reported_dirty: bool,
     pub undo_buffer: Vec<Box<dyn UndoOperation>>,
     pub redo_buffer: Vec<Box<dyn UndoOperation>>,
     pub id: Id,
+    /// Id of the hex widget itself, used to move keyboard focus to it.
+    pub focus_id: Id,
+}
+
+/// The data and edit history of a view, moved between the tabs of a duplicated view.
+pub struct Document {
+    buffer: Option<DataBuffer>,
+    image: Option<executable::Image>,
+    last_save: usize,
+    reported_dirty: bool,
+    undo_buffer: Vec<Box<dyn UndoOperation>>,
+    redo_buffer: Vec<Box<dyn UndoOperation>>,
+    edit_positions: VecDeque<usize>,
 }
 
 #[derive(Debug, Clone)]
@@ -70,25 +204,217 @@ pub enum Message {
     TypeChar(char),
     SetFocus(bool),
     Click(Point),
+    /// Like [`Message::Click`], but dragging selects the same columns across rows.
+    BlockClick(Point),
+    /// Selects the group around the clicked byte, the second value is the group width in bytes.
+    DoubleClick(Point, usize),
+    Select(Selection),
+    /// Extends the selection from the clicked byte to the given point.
+    Drag(Point),
+    EndDrag,
+    /// Scrolls and extends the selection while dragging beyond the viewport.
+    AutoScroll,
+    /// Mouse wheel movement in notches, positive values scroll up.
+    Scroll(f32),
+    /// Scrolls by the given number of pixels, positive values scroll down.
+    ScrollPixels(f32),
+    /// Scrolls wide rows sideways by that many pixels.
+    ScrollHorizontal(f32),
+    /// Pinch zoom, changes the font size by the given number of steps.
+    Zoom(i32),
+    /// A touch long press at the given point of the visible area, opens the context menu.
+    LongPress(Point),
+    /// Pastes at the caret, hex pairs in the hex pane and text in the text pane encoding otherwise.
+    Paste(String),
+    /// Advances the running scroll animation.
+    AnimateScroll,
+    /// Scrolls the caret back to the screen row remembered by [`HexView::anchor_caret`].
+    RestoreAnchor,
+    /// Shows or hides the caret, sent periodically while the view has focus.
+    BlinkCaret,
+    /// Selects the run of identical bytes under the caret.
+    SelectRun,
+    /// Overwrites the bytes at the given offset as one undoable step.
+    SetBytes(usize, Vec<u8>),
+    /// Restores the selected bytes to their values at the last load or save.
+    RevertSelection,
+    /// Reverses or (de)interleaves the selection, each row of a block selection on its own.
+    ReorderSelection(reorder::Reorder),
+    /// Applies a letter substitution to the selection.
+    TransformSelection(transform::TextTransform),
+    /// Repeats the pattern over the selection, leaving a tail too short for a whole copy unchanged.
+    FillSelection(Vec<u8>),
+    /// Truncates the data to the length or extends it with the fill byte.
+    Resize(usize, u8),
     SwitchMode,
+    /// Measures relative offsets from the caret.
+    SetOffsetBase,
+    ToggleBookmark,
+    ToggleVirtualAddresses,
+    /// Reads a pointer at the caret and moves the caret to where it points.
+    FollowPointer,
+    /// Pins the mark at the caret, or removes it if it is already there.
+    ToggleMark,
+    NextBookmark,
+    PreviousBookmark,
+    ToggleInsertMode,
+    ToggleReadOnly,
     PageUp,
     PageDown,
+    /// Moves the caret to the next (`true`) or previous group boundary.
+    MoveGroup(bool),
+    /// Moves the caret by the given number of rows.
+    MoveRows(isize),
+    /// Moves the caret to the next (`true`) or previous run of bytes changed since the last save.
+    MoveModified(bool),
+    SetRecordSize(Option<usize>),
+    /// Moves the caret by the given number of records, keeping its position inside the record.
+    MoveRecords(isize),
+    /// Moves the caret to the start of the record with the given index.
+    GoToRecord(usize),
+    /// Emitted when the view gains (`true`) or loses unsaved changes.
+    DirtyChanged(bool),
+}
+/// Text encodings offered for the text pane and the text view.
+pub fn text_encodings() -> [&'static encoding_rs::Encoding; 13] {
+    [
+        encoding_rs::WINDOWS_1252,
+        encoding_rs::UTF_8,
+        encoding_rs::UTF_16LE,
+        encoding_rs::UTF_16BE,
+        encoding_rs::ISO_8859_2,
+        encoding_rs::WINDOWS_1251,
+        encoding_rs::KOI8_R,
+        encoding_rs::IBM866,
+        encoding_rs::SHIFT_JIS,
+        encoding_rs::EUC_JP,
+        encoding_rs::GBK,
+        encoding_rs::BIG5,
+        encoding_rs::EUC_KR,
+    ]
+}
+
+/// Encodes `ch` in `encoding`, `None` if it is a control character or can't be represented.
+pub fn encode_char(encoding: &'static encoding_rs::Encoding, ch: char) -> Option<Vec<u8>> {
+    if ch.is_control() {
+        return None;
+    }
+    // encoding_rs only decodes UTF-16, encoding to it has to be done by hand.
+    let mut units = [0; 2];
+    if encoding == encoding_rs::UTF_16LE {
+        return Some(ch.encode_utf16(&mut units).iter().flat_map(|unit| unit.to_le_bytes()).collect());
+    }
+    if encoding == encoding_rs::UTF_16BE {
+        return Some(ch.encode_utf16(&mut units).iter().flat_map(|unit| unit.to_be_bytes()).collect());
+    }
+    let (bytes, _, had_errors) = encoding.encode(ch.encode_utf8(&mut [0; 4]));
+    (!had_errors).then(|| bytes.into_owned())
+}
+
+/// Encodes `text` with [`encode_char`], `None` if any character can't be encoded.
+pub fn encode_str(encoding: &'static encoding_rs::Encoding, text: &str) -> Option<Vec<u8>> {
+    let chars = text.chars().map(|ch| encode_char(encoding, ch)).collect::<Option<Vec<_>>>()?;
+    Some(chars.concat())
+}
+
+/// Parses an offset or length typed by the user. Accepts decimal, `0x`/`$` prefixed or `h` suffixed hex.
+pub fn parse_offset(input: &str) -> Option<usize> {
+    let input = input.trim();
+    if let Some(hex) = input
+        .strip_prefix("0x")
+        .or_else(|| input.strip_prefix("0X"))
+        .or_else(|| input.strip_prefix('$'))
+    {
+        usize::from_str_radix(hex, 16).ok()
+    } else if let Some(hex) = input.strip_suffix('h').or_else(|| input.strip_suffix('H')) {
+        usize::from_str_radix(hex, 16).ok()
+    } else {
+        input.parse().ok()
+    }
+}
+
+/// Parses hex pairs, ignoring whitespace. Returns `None` for anything else.
+pub fn parse_hex(text: &str) -> Option<Vec<u8>> {
+    let digits = text
+        .chars()
+        .filter(|ch| !ch.is_whitespace())
+        .map(|ch| ch.to_digit(16).map(|digit| digit as u8))
+        .collect::<Option<Vec<_>>>()?;
+    if digits.is_empty() || !digits.len().is_multiple_of(2) {
+        return None;
+    }
+    Some(digits.chunks(2).map(|pair| pair[0] << 4 | pair[1]).collect())
 }
+
+/// Offsets at which `a` and `b` differ, bytes only one of them has count as differences.
+pub fn diff_offsets(a: &[u8], b: &[u8]) -> Vec<usize> {
+    let common = a.len().min(b.len());
+    let mut offsets: Vec<usize> = (0..common).filter(|i| a[*i] != b[*i]).collect();
+    offsets.extend(common..a.len().max(b.len()));
+    offsets
+}
+
+/// Offsets of the non-overlapping occurrences of `needle` in `haystack`. Candidates are found by the
+/// first byte, only those are compared in full.
+fn occurrences<'a>(haystack: &'a [u8], needle: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+    let mut i = 0;
+    std::iter::from_fn(move || {
+        let first = needle.first()?;
+        while i + needle.len() <= haystack.len() {
+            let skip = haystack[i..=haystack.len() - needle.len()].iter().position(|byte| byte == first)?;
+            if haystack[i + skip..].starts_with(needle) {
+                let found = i + skip;
+                i = found + needle.len();
+                return Some(found);
+            }
+            i += skip + 1;
+        }
+        None
+    })
+}
+
+/// Offsets of the non-overlapping occurrences of `needle` in `haystack`.
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(haystack = haystack.len(), needle = needle.len())))]
+pub fn find_occurrences(haystack: &[u8], needle: &[u8]) -> Vec<usize> {
+    occurrences(haystack, needle).collect()
+}
+
+/// Counts the non-overlapping occurrences of `needle` in `haystack`, like [`find_occurrences`] finds them.
+pub fn count_occurrences(haystack: &[u8], needle: &[u8]) -> usize {
+    occurrences(haystack, needle).count()
+}
+
 type Plain = iced_core::text::paragraph::Plain<<Renderer as iced_core::text::Renderer>::Paragraph>;
 
 impl HexView {
     pub fn redraw(&mut self) {
-        self.cache.clear();
+        self.cache_dirty.set(true);
+        self.overlay_dirty.set(true);
+    }
+
+    /// Redraws only the caret and the selection, the byte content stays cached.
+    pub fn redraw_overlay(&mut self) {
+        self.overlay_dirty.set(true);
+    }
+
+    /// Clears the caches that were invalidated since the last frame, called by the widget's draw.
+    pub(crate) fn clear_dirty_caches(&self) {
+        if self.cache_dirty.replace(false) {
+            self.cache.clear();
+        }
+        if self.overlay_dirty.replace(false) {
+            self.overlay.clear();
+        }
     }
 
     pub fn set_font_size(&mut self, font_size: f32) {
         self.font_size = font_size;
-        self.font_measure = Self::font_measure(self.font_size, self.scale_factor, self.font);
+        self.update_font();
     }
 
     pub fn set_scale_factor(&mut self, scale_factor: f32) {
         self.scale_factor = scale_factor;
-        self.font_measure = Self::font_measure(self.font_size, self.scale_factor, self.font);
+        self.update_font();
     }
 
     fn font_measure(font_size: f32, scale_factor: f32, font: Font) -> Size<f32> {
@@ -107,8 +433,35 @@ impl HexView {
         paragraph.min_bounds()
     }
 
+    /// Measures the font and shapes the cells, call after changing the font or the encoding.
     pub fn update_font(&mut self) {
         self.font_measure = Self::font_measure(self.font_size, self.scale_factor, self.font);
+        self.glyphs = glyph_cache::GlyphCache::new(self.font, self.font_size, self.text_cell_width(), |byte| self.text_char(byte));
+        self.redraw();
+    }
+
+    /// The character the text pane shows for `byte`, decoded with single byte encodings.
+    fn text_char(&self, byte: u8) -> char {
+        let ch = if self.encoding.is_single_byte() {
+            let (text, _) = self.encoding.decode_without_bom_handling(&[byte]);
+            text.chars().next().unwrap_or(char::REPLACEMENT_CHARACTER)
+        } else {
+            byte as char
+        };
+        if ch.is_control() || ch == char::REPLACEMENT_CHARACTER {
+            '.'
+        } else {
+            ch
+        }
+    }
+
+    /// Width of a text pane cell.
+    pub(crate) fn text_cell_width(&self) -> f32 {
+        if self.double_width_text {
+            self.font_measure.width * 2.0
+        } else {
+            self.font_measure.width
+        }
     }
 
     pub(crate) fn new(path: PathBuf, buffer: DataBuffer) -> Self {
@@ -116,232 +469,1178 @@ impl HexView {
         let scale_factor = 1.0;
         let font = Font::MONOSPACE;
         let font_measure = Self::font_measure(font_size, scale_factor, font);
+        let image = executable::Image::parse(&buffer.data);
         Self {
             path,
-            theme: Theme::default(),
+            theme: Theme::new(),
             cache: Cache::default(),
+            overlay: Cache::default(),
+            cache_dirty: Cell::new(false),
+            overlay_dirty: Cell::new(false),
             cursor: Cursor {
                 position: 0,
                 blink: false,
-                focus: true,
+                focus: false,
                 in_hex: EditMode::Hex,
             },
+            selection: None,
+            insert_mode: false,
+            read_only: false,
+            offset_base: 0,
+            virtual_addresses: false,
+            mark: None,
+            bookmarks: Vec::new(),
+            bookmark_notes: BTreeMap::new(),
+            regions: Vec::new(),
+            matches: Vec::new(),
+            matches_for: None,
+            selection_stats: None,
+            stats_for: None,
+            highlights: highlight::Layers::default(),
+            edit_positions: VecDeque::new(),
+            drag_anchor: None,
+            drag_block: false,
+            drag_point: Point::ORIGIN,
+            drag_scroll: 0.0,
+            smooth_scrolling: true,
+            scroll_lines: 3,
+            group_size: 4,
+            bytes_per_row: None,
+            base_address: 0,
+            record_size: None,
+            row_checksum: checksum::RowChecksum::None,
+            numeric_column: numeric::NumericColumn::None,
+            scroll_animation: None,
+            scroll_anchor: Cell::new(None),
+            encoding: encoding_rs::WINDOWS_1252,
+            endian: Endian::Little,
             font,
             font_size,
             scale_factor,
             font_measure,
+            glyphs: glyph_cache::GlyphCache::default(),
+            double_width_text: false,
+            group_gaps: false,
+            show_hex_pane: true,
+            show_text_pane: true,
             buffer: Some(buffer),
+            image,
             viewport: Cell::new(Rectangle::default()),
             id: Id::unique(),
+            focus_id: Id::unique(),
             last_save: 0,
+            reported_dirty: false,
             undo_buffer: Vec::new(),
             redo_buffer: Vec::new(),
         }
     }
 
     pub(crate) fn numbers_in_row(&self) -> usize {
-        let char_width = self.font_measure.width;
+        if let Some(bytes_per_row) = self.bytes_per_row {
+            return bytes_per_row;
+        }
         let width = self.viewport.get().width;
-
-        let offset_margin_width: f32 = self.theme.calc_offset_margin_width(self.font_measure);
-        let cell_width = self.theme.calc_cell_width(self.font_measure);
-
         for i in 2.. {
-            if offset_margin_width + (i as f32) * cell_width + self.theme.hex_ascii_spacing() + (i as f32) * char_width > width {
+            if self.row_width(i) > width {
                 return i - 1;
             }
         }
         return 1;
     }
 
-    fn scroll_to_caret(&self) -> Task<Message> {
+    /// Shows or hides the hex and text panes, keeping at least one and the caret in a shown one.
+    pub(crate) fn set_panes(&mut self, show_hex: bool, show_text: bool) {
+        self.show_hex_pane = show_hex || !show_text;
+        self.show_text_pane = show_text;
+        if !self.show_hex_pane {
+            self.cursor.in_hex = EditMode::Ascii;
+        } else if !self.show_text_pane {
+            self.cursor.in_hex = EditMode::Hex;
+        }
+    }
+
+    /// Geometry of the rows as they are drawn.
+    pub(crate) fn layout(&self) -> Layout {
+        self.layout_for(self.numbers_in_row())
+    }
+
+    fn layout_for(&self, numbers_in_row: usize) -> Layout {
+        Layout {
+            text_cell_width: self.text_cell_width(),
+            group_size: self.group_size,
+            group_gap: if self.group_gaps { self.font_measure.width } else { 0.0 },
+            show_hex: self.show_hex_pane,
+            show_text: self.show_text_pane,
+            ..self.theme.layout(self.font_measure, numbers_in_row)
+        }
+    }
+
+    /// Left edge of the columns after the text pane.
+    pub(crate) fn extra_columns_x(&self, numbers_in_row: usize) -> f32 {
+        self.layout_for(numbers_in_row).extra_columns_x()
+    }
+
+    /// Width of the numeric column including its spacing, zero when it is hidden.
+    fn numeric_column_width(&self, numbers_in_row: usize) -> f32 {
+        self.numeric_column.chars_per_byte().map_or(0.0, |chars| {
+            chars * numbers_in_row as f32 * self.font_measure.width + self.theme.hex_ascii_spacing()
+        })
+    }
+
+    pub(crate) fn checksum_column_x(&self, numbers_in_row: usize) -> f32 {
+        self.extra_columns_x(numbers_in_row) + self.numeric_column_width(numbers_in_row)
+    }
+
+    /// Width of the rows when the bytes per row are fixed. They may be wider than the view, which
+    /// then scrolls horizontally. `None` when the rows are fit to the view.
+    pub(crate) fn content_width(&self) -> Option<f32> {
+        self.bytes_per_row.map(|numbers_in_row| self.row_width(numbers_in_row))
+    }
+
+    /// Width of a row showing `numbers_in_row` bytes, including the optional columns.
+    fn row_width(&self, numbers_in_row: usize) -> f32 {
+        let mut width = self.checksum_column_x(numbers_in_row) - self.theme.hex_ascii_spacing();
+        if self.row_checksum != checksum::RowChecksum::None {
+            width += self.theme.hex_ascii_spacing() + self.font_measure.width * 2.0;
+        }
+        width
+    }
+
+    /// Rows of the view, including the one past the end the caret can move to for appending.
+    pub(crate) fn row_count(&self) -> u64 {
+        let len = self.buffer.as_ref().map_or(0, |buffer| buffer.len());
+        (len / self.numbers_in_row()) as u64 + 1
+    }
+
+    /// Height of the scrollable content, capped at [`MAX_CONTENT_HEIGHT`].
+    pub(crate) fn content_height(&self) -> f32 {
+        (self.row_count() as f64 * self.font_measure.height as f64).min(MAX_CONTENT_HEIGHT as f64) as f32
+    }
+
+    /// Rows per viewport height, fractional.
+    fn visible_rows(&self) -> f64 {
+        self.viewport.get().height as f64 / self.font_measure.height as f64
+    }
+
+    fn is_scaled(&self) -> bool {
+        self.row_count() as f64 * self.font_measure.height as f64 > MAX_CONTENT_HEIGHT as f64
+    }
+
+    /// The (fractional) row at the top of the viewport when scrolled to `scroll_y`.
+    pub(crate) fn top_row(&self, scroll_y: f32) -> f64 {
+        if !self.is_scaled() {
+            return scroll_y as f64 / self.font_measure.height as f64;
+        }
+        let max_scroll = self.max_scroll_y() as f64;
+        let max_top = (self.row_count() as f64 - self.visible_rows()).max(0.0);
+        if max_scroll <= 0.0 {
+            return 0.0;
+        }
+        (scroll_y as f64 / max_scroll).clamp(0.0, 1.0) * max_top
+    }
+
+    /// Inverse of [`Self::top_row`].
+    fn scroll_y_for_row(&self, row: f64) -> f32 {
+        if !self.is_scaled() {
+            return (row * self.font_measure.height as f64) as f32;
+        }
+        let max_top = (self.row_count() as f64 - self.visible_rows()).max(1.0);
+        ((row / max_top).clamp(0.0, 1.0) * self.max_scroll_y() as f64) as f32
+    }
+
+    /// The row at `y` in widget coordinates.
+    pub(crate) fn row_at(&self, y: f32) -> usize {
+        let scroll_y = self.viewport.get().y;
+        (self.top_row(scroll_y) + (y - scroll_y) as f64 / self.font_measure.height as f64).max(0.0) as usize
+    }
+
+    /// The top of `row` in widget coordinates.
+    pub(crate) fn row_y(&self, row: usize) -> f32 {
+        let scroll_y = self.viewport.get().y;
+        scroll_y + ((row as f64 - self.top_row(scroll_y)) * self.font_measure.height as f64) as f32
+    }
+
+    fn caret_row(&self) -> f64 {
+        (self.cursor.position / (self.numbers_in_row() * 2)) as f64
+    }
+
+    /// Remembers the screen row of the caret before the rows change, e.g. with the font size or
+    /// the bytes per row. The widget asks for [`Message::RestoreAnchor`] once it drew the new rows.
+    pub(crate) fn anchor_caret(&self) {
+        if self.scroll_anchor.get().is_none() {
+            self.scroll_anchor.set(Some(ScrollAnchor {
+                offset: self.cursor.position / 2,
+                screen_row: self.caret_row() - self.top_row(self.scroll_target()),
+            }));
+        }
+    }
+
+    /// Takes the viewport the widget was drawn in. If the new size changes the bytes per row or
+    /// cuts off rows, the caret is anchored to stay on screen, or the top row if the caret was
+    /// scrolled out of view, so page movement and the viewport agree again right away.
+    pub(crate) fn set_viewport(&self, viewport: Rectangle) {
+        let old = self.viewport.get();
+        if old.size() == viewport.size() || old.width <= 0.0 || self.scroll_anchor.get().is_some() {
+            self.viewport.set(viewport);
+            return;
+        }
         let numbers_in_row = self.numbers_in_row();
-        let row = self.cursor.position / (numbers_in_row * 2);
-        let row = row as f32;
-        let row = row * self.font_measure.height;
-
-        let y = self.viewport.get().y;
-        let height = self.viewport.get().height;
-        if row < y {
-            scrollable::scroll_to::<Message>(self.id.clone(), AbsoluteOffset { x: 0.0, y: row })
-        } else if row > y + height {
-            scrollable::scroll_to::<Message>(
-                self.id.clone(),
-                AbsoluteOffset {
-                    x: 0.0,
-                    y: row + self.font_measure.height - height,
-                },
-            )
+        let top = self.top_row(old.y);
+        let screen_row = self.caret_row() - top;
+        let anchor = if (0.0..self.visible_rows()).contains(&screen_row) {
+            ScrollAnchor {
+                offset: self.cursor.position / 2,
+                screen_row,
+            }
+        } else {
+            ScrollAnchor {
+                offset: top as usize * numbers_in_row,
+                screen_row: 0.0,
+            }
+        };
+        self.viewport.set(viewport);
+        if self.numbers_in_row() != numbers_in_row || viewport.height < old.height {
+            self.scroll_anchor.set(Some(anchor));
+        }
+    }
+
+    /// Scrolls back to where the view was when it was last drawn. Tabs share the scrollable, so
+    /// this is needed whenever the tab becomes active again.
+    pub(crate) fn restore_scroll(&mut self) -> Task<Message> {
+        self.scroll_animation = None;
+        self.set_scroll_y(self.viewport.get().y)
+    }
+
+    /// Scrolls to `y` right away, keeping the horizontal scroll position.
+    fn set_scroll_y(&self, y: f32) -> Task<Message> {
+        scrollable::scroll_to::<Message>(self.id.clone(), AbsoluteOffset { x: self.viewport.get().x, y })
+    }
+
+    fn scroll_to_caret(&mut self) -> Task<Message> {
+        let row = self.caret_row();
+
+        let top = self.top_row(self.viewport.get().y);
+        let visible_rows = self.visible_rows();
+        if row < top {
+            self.scroll_to_y(self.scroll_y_for_row(row))
+        } else if row + 1.0 > top + visible_rows {
+            self.scroll_to_y(self.scroll_y_for_row(row + 1.0 - visible_rows))
         } else {
             Task::none()
         }
     }
 
+    /// The scroll position the view is at or, while animating, heading to.
+    fn scroll_target(&self) -> f32 {
+        self.scroll_animation.as_ref().map_or(self.viewport.get().y, |animation| animation.to)
+    }
+
+    fn max_scroll_y(&self) -> f32 {
+        (self.content_height() - self.viewport.get().height).max(0.0)
+    }
+
+    /// Scrolls to `y`, animated if smooth scrolling is enabled.
+    fn scroll_to_y(&mut self, y: f32) -> Task<Message> {
+        // Scrolling on purpose wins over keeping the caret row.
+        self.scroll_anchor.set(None);
+        let y = y.clamp(0.0, self.max_scroll_y());
+        if !self.smooth_scrolling {
+            self.scroll_animation = None;
+            return self.set_scroll_y(y);
+        }
+        self.scroll_animation = Some(ScrollAnimation {
+            from: self.viewport.get().y,
+            to: y,
+            start: Instant::now(),
+        });
+        Task::none()
+    }
+
+    /// Selects from the drag anchor to the byte under `drag_point`, clamping points outside the data.
+    fn extend_drag_selection(&mut self) {
+        let Some(anchor) = self.drag_anchor else {
+            return;
+        };
+        let len = self.buffer.as_ref().map_or(0, |buffer| buffer.len());
+        if len == 0 {
+            return;
+        }
+        let numbers_in_row = self.numbers_in_row();
+        let margin = self.layout().gutter_width;
+        let point = Point::new(self.drag_point.x.max(margin), self.drag_point.y.max(0.0));
+        let offset = match self.hit_test(point) {
+            Some((position, _)) => position / 2,
+            // Right of the text pane, extend to the end of the row.
+            None => self.row_at(point.y) * numbers_in_row + numbers_in_row - 1,
+        }
+        .min(len - 1);
+        self.cursor.position = offset * 2;
+        self.selection = caret::drag_selection(anchor, offset, self.drag_block.then_some(numbers_in_row));
+        self.redraw_overlay();
+    }
+
+    /// Selects all bytes of `row` and moves the caret to its start.
+    fn select_row(&mut self, row: usize) -> Task<Message> {
+        let len = self.buffer.as_ref().map_or(0, |buffer| buffer.len());
+        let numbers_in_row = self.numbers_in_row();
+        let start = row * numbers_in_row;
+        if start >= len {
+            return Task::none();
+        }
+        self.cursor.position = start * 2;
+        self.selection = Some(Selection::new(start, (start + numbers_in_row).min(len) - 1));
+        self.redraw_overlay();
+        self.copy_selection_to_primary()
+    }
+
+    /// The last caret position, one past the end of the data in insert mode so bytes can be appended.
+    pub(crate) fn max_caret_position(&self) -> usize {
+        let len = self.buffer.as_ref().map_or(0, |buffer| buffer.len());
+        caret::max_position(len, self.read_only)
+    }
+
+    /// The byte under `point` in widget coordinates, in either pane.
+    pub(crate) fn offset_at(&self, point: Point) -> Option<usize> {
+        let len = self.buffer.as_ref().map_or(0, |buffer| buffer.len());
+        self.hit_test(point).map(|(position, _)| position / 2).filter(|offset| *offset < len)
+    }
+
+    /// The gutter label of `offset`, its virtual address in virtual address mode.
+    pub(crate) fn gutter_label(&self, offset: usize) -> String {
+        let address = match (&self.image, self.virtual_addresses) {
+            (Some(image), true) => image.address_of(offset),
+            _ => None,
+        };
+        match address {
+            Some(address) => format!("{:0width$X}", address, width = self.theme.offset_digits),
+            None => format!("{:0width$X}", self.base_address.wrapping_add(offset as u64), width = self.theme.offset_digits),
+        }
+    }
+
+    /// Maps an offset typed by the user to a file offset, it is a virtual address in virtual address mode.
+    pub fn resolve_address(&self, value: u64) -> Option<usize> {
+        match (&self.image, self.virtual_addresses) {
+            (Some(image), true) => image.offset_of(value),
+            _ => usize::try_from(value).ok(),
+        }
+    }
+
+    /// `offset` relative to [`Self::offset_base`], signed and in hex.
+    pub fn relative_offset(&self, offset: usize) -> String {
+        if offset >= self.offset_base {
+            format!("+0x{:X}", offset - self.offset_base)
+        } else {
+            format!("-0x{:X}", self.offset_base - offset)
+        }
+    }
+
+    /// Maps a point in widget coordinates to a caret position and the pane that was hit.
+    fn hit_test(&self, point: Point) -> Option<(usize, EditMode)> {
+        self.layout().hit_test(point.x, self.row_at(point.y))
+    }
+
     pub fn update(&mut self, message: Message) -> Task<Message> {
+        // The caret stays visible while the user is doing something.
+        if !matches!(message, Message::BlinkCaret) && self.cursor.blink {
+            self.cursor.blink = false;
+            self.redraw_overlay();
+        }
         match message {
             Message::Redraw => {
                 self.redraw();
             }
 
+            Message::BlinkCaret => {
+                self.cursor.blink = !self.cursor.blink;
+                self.redraw_overlay();
+            }
+
             Message::MoveCaret(position) => {
-                self.cursor.position = position.clamp(0, (self.buffer.as_ref().unwrap().len() - 1) * 2);
-                self.redraw();
+                self.cursor.position = position.min(self.max_caret_position());
+                self.redraw_overlay();
                 return self.scroll_to_caret();
             }
 
             Message::TypeChar(ch) => {
-                if let Some(buffer) = &mut self.buffer {
+                if self.cursor.in_hex == EditMode::Ascii {
+                    return self.type_text(ch);
+                }
+                let Some(digit) = ch.to_digit(16).map(|digit| digit as u8) else {
+                    return Task::none();
+                };
+                if let Some(buffer) = &self.buffer {
                     let first_char = self.cursor.position % 2 == 0;
                     let pos = self.cursor.position / 2;
-                    let old_byte = buffer.data[pos];
-                    if self.cursor.in_hex == EditMode::Hex {
-                        if ch.is_ascii_hexdigit() {
-                            let digit = ch.to_digit(16).unwrap() as u8;
-                            let new_byte = if first_char {
-                                (old_byte & 0x0F) | (digit << 4)
-                            } else {
-                                (old_byte & 0xF0) | digit
-                            };
-
-                            let operation = undo::UndoChangeByte::new(pos, self.cursor.position, old_byte, self.cursor.position + 1, new_byte);
-                            return self.commit_operation(operation);
-                        }
-                    } else {
-                        let new_byte = ch as u8;
-                        let operation = undo::UndoChangeByte::new(pos, self.cursor.position, old_byte, pos * 2 + 2, new_byte);
+                    if (self.insert_mode || pos == buffer.len()) && first_char {
+                        let operation = undo::UndoInsertBytes::new(pos, self.cursor.position, self.cursor.position + 1, vec![digit << 4]);
                         return self.commit_operation(operation);
                     }
+                    let Some(&old_byte) = buffer.data.get(pos) else {
+                        return Task::none();
+                    };
+                    let new_byte = caret::set_nibble(old_byte, digit, first_char);
+
+                    let operation = undo::UndoChangeByte::new(pos, self.cursor.position, old_byte, self.cursor.position + 1, new_byte);
+                    return self.commit_operation(operation);
                 }
             }
 
             Message::SetFocus(focus) => {
                 self.cursor.focus = focus;
+                self.cursor.blink = false;
+                self.redraw_overlay();
             }
 
             Message::Click(point) => {
-                let numbers_in_row = self.numbers_in_row();
-
-                let char_width = self.font_measure.width;
-                let left_margin: f32 = 9.0 * char_width;
-                let x = point.x - left_margin;
-
-                let cell_width = self.theme.calc_cell_width(self.font_measure);
-                let numbers_width = (numbers_in_row as f32) * cell_width;
-                let text_width = (numbers_in_row as f32) * char_width;
-
-                if x >= 0.0 {
-                    if x <= numbers_width {
-                        let clicked_cell = (x / cell_width) as usize;
-                        let clicked_cell_x = x - (clicked_cell as f32 * cell_width);
-
-                        let mut position = ((point.y / self.font_measure.height) as usize * numbers_in_row + clicked_cell) * 2;
-
-                        if clicked_cell_x > char_width {
-                            position += 1;
-                        }
-
-                        self.cursor.position = position;
-                        self.cursor.in_hex = EditMode::Hex;
-                    } else {
-                        let x = x - numbers_width;
-                        if x < text_width {
-                            let number = (x / char_width) as usize;
-                            let position = (point.y / self.font_measure.height) as usize * numbers_in_row + number;
-                            self.cursor.position = position * 2;
-                            self.cursor.in_hex = EditMode::Ascii;
-                        }
+                if point.x < self.layout().gutter_width {
+                    return self.select_row(self.row_at(point.y));
+                }
+                if let Some((position, mode)) = self.hit_test(point) {
+                    self.cursor.position = position.min(self.max_caret_position());
+                    self.cursor.in_hex = mode;
+                    self.drag_anchor = Some(self.cursor.position / 2);
+                }
+                self.drag_block = false;
+                self.selection = None;
+                self.redraw_overlay();
+            }
+
+            Message::BlockClick(point) => {
+                let task = self.update(Message::Click(point));
+                self.drag_block = self.drag_anchor.is_some();
+                return task;
+            }
+
+            Message::DoubleClick(point, group_width) => {
+                let Some((position, mode)) = self.hit_test(point) else {
+                    return Task::none();
+                };
+                let Some(buffer) = &self.buffer else {
+                    return Task::none();
+                };
+                let offset = position / 2;
+                if offset >= buffer.len() {
+                    return Task::none();
+                }
+                let selection = if mode == EditMode::Hex {
+                    caret::group_selection(offset, group_width, buffer.len())
+                } else {
+                    let (start, end) = buffer.text_run(offset);
+                    Selection::new(start, end)
+                };
+                self.cursor.position = selection.start() * 2;
+                self.cursor.in_hex = mode;
+                self.selection = Some(selection);
+                self.redraw_overlay();
+                return self.copy_selection_to_primary();
+            }
+            Message::Select(selection) => {
+                self.cursor.position = selection.start() * 2;
+                self.selection = Some(selection);
+                self.redraw_overlay();
+                return self.scroll_to_caret();
+            }
+
+            Message::Drag(point) => {
+                self.drag_point = point;
+                let viewport = self.viewport.get();
+                self.drag_scroll = if point.y < viewport.y {
+                    point.y - viewport.y
+                } else if point.y > viewport.y + viewport.height {
+                    point.y - viewport.y - viewport.height
+                } else {
+                    0.0
+                };
+                self.extend_drag_selection();
+            }
+
+            Message::EndDrag => {
+                self.drag_scroll = 0.0;
+                if self.drag_anchor.take().is_some() {
+                    return self.copy_selection_to_primary();
+                }
+            }
+
+            Message::Paste(text) => {
+                return self.paste(&text);
+            }
+
+            Message::AutoScroll => {
+                if self.drag_anchor.is_none() || self.drag_scroll == 0.0 {
+                    return Task::none();
+                }
+                let max_step = self.font_measure.height * 4.0;
+                let delta = (self.drag_scroll / 2.0).clamp(-max_step, max_step);
+                self.drag_point.y = (self.drag_point.y + delta).max(0.0);
+                self.extend_drag_selection();
+                return scrollable::scroll_by::<Message>(self.id.clone(), AbsoluteOffset { x: 0.0, y: delta });
+            }
+
+            Message::Scroll(lines) => {
+                let rows = -lines as f64 * self.scroll_lines as f64;
+                if !self.smooth_scrolling && !self.is_scaled() {
+                    let delta = rows as f32 * self.font_measure.height;
+                    return scrollable::scroll_by::<Message>(self.id.clone(), AbsoluteOffset { x: 0.0, y: delta });
+                }
+                return self.scroll_to_y(self.scroll_y_for_row(self.top_row(self.scroll_target()) + rows));
+            }
+
+            Message::ScrollPixels(delta) => {
+                self.scroll_animation = None;
+                if self.is_scaled() {
+                    let rows = delta as f64 / self.font_measure.height as f64;
+                    let y = self.scroll_y_for_row(self.top_row(self.viewport.get().y) + rows);
+                    return self.set_scroll_y(y);
+                }
+                return scrollable::scroll_by::<Message>(self.id.clone(), AbsoluteOffset { x: 0.0, y: delta });
+            }
+
+            Message::ScrollHorizontal(delta) => {
+                if self.content_width().is_some() {
+                    return scrollable::scroll_by::<Message>(self.id.clone(), AbsoluteOffset { x: delta, y: 0.0 });
+                }
+            }
+
+            Message::RestoreAnchor => {
+                let Some(anchor) = self.scroll_anchor.take() else {
+                    return Task::none();
+                };
+                self.scroll_animation = None;
+                // A smaller view may not reach down to the old screen row anymore.
+                let screen_row = anchor.screen_row.clamp(0.0, (self.visible_rows() - 1.0).max(0.0));
+                let row = (anchor.offset / self.numbers_in_row()) as f64;
+                let y = self.scroll_y_for_row(row - screen_row).clamp(0.0, self.max_scroll_y());
+                return self.set_scroll_y(y);
+            }
+
+            Message::AnimateScroll => {
+                let Some(animation) = &self.scroll_animation else {
+                    return Task::none();
+                };
+                let t = (animation.start.elapsed().as_secs_f32() / SCROLL_ANIMATION_DURATION.as_secs_f32()).min(1.0);
+                let eased = 1.0 - (1.0 - t).powi(3);
+                let y = animation.from + (animation.to - animation.from) * eased;
+                if t >= 1.0 {
+                    self.scroll_animation = None;
+                }
+                return self.set_scroll_y(y);
+            }
+
+            Message::SelectRun => {
+                if let Some(buffer) = &self.buffer {
+                    let offset = self.cursor.position / 2;
+                    if offset < buffer.len() {
+                        let (start, end) = buffer.run_bounds(offset);
+                        self.selection = Some(Selection::new(start, end));
+                        self.redraw_overlay();
+                        return self.copy_selection_to_primary();
                     }
                 }
+            }
 
-                self.redraw();
+            Message::SetBytes(offset, bytes) => {
+                if let Some(buffer) = &self.buffer {
+                    let Some(old_value) = buffer.data.get(offset..offset + bytes.len()) else {
+                        return Task::none();
+                    };
+                    let operation = undo::UndoChangeBytes::new(offset, self.cursor.position, old_value.to_vec(), self.cursor.position, bytes);
+                    return self.commit_operation(operation);
+                }
+            }
+
+            Message::FillSelection(pattern) => {
+                if pattern.is_empty() {
+                    return Task::none();
+                }
+                // Block selections are filled row by row, each row starting with the pattern again.
+                return self.change_selection(|_, _, bytes| {
+                    let len = bytes.len() - bytes.len() % pattern.len();
+                    bytes[..len].copy_from_slice(&pattern.repeat(len / pattern.len()));
+                });
+            }
+
+            Message::Resize(len, fill) => {
+                let Some(buffer) = &self.buffer else {
+                    return Task::none();
+                };
+                let old_len = buffer.len();
+                if len > old_len {
+                    let operation = undo::UndoInsertBytes::new(old_len, self.cursor.position, self.cursor.position, vec![fill; len - old_len]);
+                    return self.commit_operation(operation);
+                }
+                if len < old_len {
+                    let new_caret_pos = self.cursor.position.min(len * 2);
+                    let operation = undo::UndoRemoveBytes::new(len, self.cursor.position, new_caret_pos, buffer.data[len..].to_vec());
+                    self.selection = self.selection.filter(|selection| selection.end() < len);
+                    return self.commit_operation(operation);
+                }
+            }
+
+            Message::ReorderSelection(reorder) => {
+                return self.change_selection(|_, _, bytes| reorder.apply(bytes));
+            }
+
+            Message::TransformSelection(transform) => {
+                return self.change_selection(|_, _, bytes| bytes.iter_mut().for_each(|byte| *byte = transform.apply(*byte)));
             }
+
+            Message::RevertSelection => {
+                // Bytes past the end of the original (after inserting) have nothing to go back to.
+                return self.change_selection(|buffer, run, bytes| {
+                    let original = buffer.original();
+                    let end = run.end.min(original.len());
+                    if run.start < end {
+                        bytes[..end - run.start].copy_from_slice(&original[run.start..end]);
+                    }
+                });
+            }
+
+            Message::DirtyChanged(_) => {}
+
             Message::SwitchMode => {
+                if !(self.show_hex_pane && self.show_text_pane) {
+                    return Task::none();
+                }
                 if self.cursor.in_hex == EditMode::Hex {
                     self.cursor.in_hex = EditMode::Ascii;
                 } else {
                     self.cursor.in_hex = EditMode::Hex;
                 }
+                self.redraw_overlay();
+            }
+
+            Message::SetOffsetBase => {
+                self.offset_base = self.cursor.position / 2;
+            }
+
+            Message::ToggleBookmark => {
+                let offset = self.cursor.position / 2;
+                match self.bookmarks.binary_search(&offset) {
+                    Ok(index) => {
+                        self.bookmarks.remove(index);
+                        self.bookmark_notes.remove(&offset);
+                    }
+                    Err(index) => self.bookmarks.insert(index, offset),
+                }
+                self.redraw();
+            }
+
+            Message::ToggleVirtualAddresses => {
+                self.virtual_addresses = !self.virtual_addresses && self.image.is_some();
+                self.theme.offset_digits = match (&self.image, self.virtual_addresses) {
+                    (Some(image), true) => image.address_digits(),
+                    _ => 8,
+                };
                 self.redraw();
             }
 
+            Message::FollowPointer => {
+                let Some(buffer) = &self.buffer else {
+                    return Task::none();
+                };
+                let width = self.image.as_ref().map_or(4, |image| image.pointer_width);
+                let Some(target) = buffer
+                    .get_uint(self.cursor.position / 2, width, self.endian)
+                    .and_then(|value| self.resolve_address(value))
+                else {
+                    return Task::none();
+                };
+                if target < buffer.len() {
+                    return self.update(Message::MoveCaret(target * 2));
+                }
+            }
+
+            Message::ToggleMark => {
+                let offset = self.cursor.position / 2;
+                self.mark = if self.mark == Some(offset) { None } else { Some(offset) };
+                self.redraw_overlay();
+            }
+
+            Message::NextBookmark => {
+                let offset = self.cursor.position / 2;
+                if let Some(bookmark) = self.bookmarks.iter().find(|bookmark| **bookmark > offset) {
+                    return self.update(Message::MoveCaret(bookmark * 2));
+                }
+            }
+
+            Message::PreviousBookmark => {
+                let offset = self.cursor.position / 2;
+                if let Some(bookmark) = self.bookmarks.iter().rev().find(|bookmark| **bookmark < offset) {
+                    return self.update(Message::MoveCaret(bookmark * 2));
+                }
+            }
+
+            Message::ToggleInsertMode => {
+                self.insert_mode = !self.insert_mode;
+                self.cursor.position = self.cursor.position.min(self.max_caret_position());
+                self.redraw_overlay();
+            }
+
+            Message::ToggleReadOnly => {
+                self.read_only = !self.read_only;
+            }
+
             Message::PageUp => {
-                let numbers_in_row = self.numbers_in_row();
-                let height = self.viewport.get().height;
-                let line_count = height / self.font_measure.height;
+                return self.move_page(false);
+            }
 
-                self.cursor.position = self.cursor.position.saturating_sub(line_count as usize * numbers_in_row * 2);
-                self.redraw();
+            Message::MoveGroup(forward) => {
+                let offset = caret::move_group(self.cursor.position / 2, self.group_size, forward);
+                return self.update(Message::MoveCaret(offset * 2));
+            }
 
-                return scrollable::scroll_to::<Message>(
-                    self.id.clone(),
-                    AbsoluteOffset {
-                        x: 0.0,
-                        y: (self.viewport.get().y - height).max(0.0),
-                    },
-                );
+            Message::MoveRows(rows) => {
+                let position = caret::move_rows(self.cursor.position, rows, self.numbers_in_row());
+                return self.update(Message::MoveCaret(position));
             }
 
             Message::PageDown => {
-                let numbers_in_row = self.numbers_in_row();
-                let height = self.viewport.get().height;
-                let line_count = height / self.font_measure.height;
+                return self.move_page(true);
+            }
+
+            Message::MoveModified(forward) => {
+                let Some(buffer) = &self.buffer else {
+                    return Task::none();
+                };
+                let offset = self.cursor.position / 2;
+                let target = if forward {
+                    buffer.next_modified(offset)
+                } else {
+                    buffer.previous_modified(offset)
+                };
+                if let Some(target) = target {
+                    return self.update(Message::MoveCaret(target * 2));
+                }
+            }
 
-                self.cursor.position += line_count as usize * numbers_in_row * 2;
-                let _ = self.cursor.position.clamp(0, self.buffer.as_ref().unwrap().len());
+            Message::SetRecordSize(record_size) => {
+                self.record_size = record_size;
                 self.redraw();
+            }
 
-                return scrollable::scroll_to::<Message>(
-                    self.id.clone(),
-                    AbsoluteOffset {
-                        x: 0.0,
-                        y: self.viewport.get().y + height,
-                    },
-                );
+            Message::MoveRecords(records) => {
+                let Some(record_size) = self.record_size else {
+                    return Task::none();
+                };
+                let delta = records.unsigned_abs() * record_size * 2;
+                let position = if records < 0 {
+                    self.cursor.position.saturating_sub(delta)
+                } else {
+                    self.cursor.position + delta
+                };
+                return self.update(Message::MoveCaret(position));
+            }
+
+            Message::GoToRecord(record) => {
+                if let Some(record_size) = self.record_size {
+                    return self.update(Message::MoveCaret(record.saturating_mul(record_size).saturating_mul(2)));
+                }
             }
             _ => {}
         }
         Task::none()
     }
 
+    /// Moves the caret one page up or down, keeping its column and its row on screen.
+    fn move_page(&mut self, down: bool) -> Task<Message> {
+        let numbers_in_row = self.numbers_in_row();
+        let page_rows = ((self.viewport.get().height / self.font_measure.height) as usize).max(1);
+        let row = self.cursor.position / (numbers_in_row * 2);
+        self.cursor.position = caret::move_page(self.cursor.position, page_rows, numbers_in_row, self.max_caret_position(), down);
+        let new_row = self.cursor.position / (numbers_in_row * 2);
+        self.redraw_overlay();
+
+        let caret_screen_row = row as f64 - self.top_row(self.scroll_target());
+        self.scroll_to_y(self.scroll_y_for_row(new_row as f64 - caret_screen_row))
+    }
+
+    /// Writes `ch` encoded in the text pane encoding at the caret. Multi-byte characters
+    /// overwrite as many bytes as they need, characters the encoding can't represent are rejected.
+    fn type_text(&mut self, ch: char) -> Task<Message> {
+        let Some(bytes) = encode_char(self.encoding, ch) else {
+            return Task::none();
+        };
+        self.write_bytes(bytes)
+    }
+
+    /// The selection as clipboard text, hex pairs in the hex pane and decoded text in the text pane.
+    /// Block selections give one line per row.
+    pub(crate) fn selection_text(&self) -> Option<String> {
+        let (selection, buffer) = (self.selection?, self.buffer.as_ref()?);
+        let lines: Vec<String> = selection
+            .runs(0..buffer.len())
+            .into_iter()
+            .map(|run| {
+                let data = &buffer.data[run];
+                match self.cursor.in_hex {
+                    EditMode::Hex => export::to_hex(data),
+                    EditMode::Ascii => self.encoding.decode_without_bom_handling(data).0.into_owned(),
+                }
+            })
+            .collect();
+        Some(lines.join("\n"))
+    }
+
+    /// The bookmarks with their notes, for exporting.
+    pub(crate) fn bookmark_list(&self) -> Vec<bookmarks::Bookmark> {
+        self.bookmarks
+            .iter()
+            .map(|offset| bookmarks::Bookmark {
+                offset: *offset,
+                note: self.bookmark_notes.get(offset).cloned().unwrap_or_default(),
+            })
+            .collect()
+    }
+
+    /// Adds imported bookmarks, skipping offsets past the end.
+    pub(crate) fn add_bookmarks(&mut self, list: Vec<bookmarks::Bookmark>) {
+        let len = self.buffer.as_ref().map_or(0, |buffer| buffer.len());
+        for bookmark in list.into_iter().filter(|bookmark| bookmark.offset < len) {
+            if let Err(index) = self.bookmarks.binary_search(&bookmark.offset) {
+                self.bookmarks.insert(index, bookmark.offset);
+            }
+            if !bookmark.note.is_empty() {
+                self.bookmark_notes.insert(bookmark.offset, bookmark.note);
+            }
+        }
+        self.redraw();
+    }
+
+    /// Turns a disassembler listing into named regions and bookmarks at their starts. Addresses are
+    /// virtual addresses for executables, otherwise offsets from the base address.
+    pub(crate) fn import_listing(&mut self, entries: Vec<listing::Entry>) {
+        let len = self.buffer.as_ref().map_or(0, |buffer| buffer.len());
+        let mut list = Vec::new();
+        for entry in entries {
+            let offset = match &self.image {
+                Some(image) => image.offset_of(entry.address),
+                None => entry.address.checked_sub(self.base_address).and_then(|offset| usize::try_from(offset).ok()),
+            };
+            let Some(offset) = offset.filter(|offset| *offset < len) else {
+                continue;
+            };
+            if entry.size > 0 {
+                let range = offset..offset.saturating_add(entry.size).min(len);
+                self.highlights.extend(highlight::Layer::Annotation, [range.clone()]);
+                self.regions.push(executable::Region {
+                    name: entry.name.clone(),
+                    range,
+                });
+            }
+            list.push(bookmarks::Bookmark { offset, note: entry.name });
+        }
+        self.regions.sort_by_key(|region| region.range.start);
+        self.add_bookmarks(list);
+    }
+
+    /// What a screen reader announces for the caret: offset, byte value and character.
+    pub(crate) fn caret_description(&self) -> String {
+        let offset = self.cursor.position / 2;
+        let Some(byte) = self.buffer.as_ref().and_then(|buffer| buffer.data.get(offset).copied()) else {
+            return crate::fl!("a11y-end-of-data", offset = format!("{:X}", offset));
+        };
+        let character = match byte {
+            b' ' => crate::fl!("a11y-space"),
+            _ if byte.is_ascii_graphic() => (byte as char).to_string(),
+            _ => crate::fl!("a11y-no-character"),
+        };
+        let mut description = crate::fl!(
+            "a11y-caret",
+            offset = format!("{:X}", offset),
+            hex = format!("{:02X}", byte),
+            dec = byte.to_string(),
+            character = character
+        );
+        if self.buffer.as_ref().is_some_and(|buffer| buffer.is_modified(offset..offset + 1)) {
+            description.push_str(&crate::fl!("a11y-modified"));
+        }
+        description
+    }
+
+    /// Highlights the search matches, each `len` bytes long.
+    pub(crate) fn highlight_matches(&mut self, len: usize) {
+        let ranges = self.matches.iter().map(|offset| *offset..offset + len).collect();
+        self.highlights.set(highlight::Layer::Search, ranges);
+        self.redraw();
+    }
+
+    /// The innermost imported region covering `offset`.
+    pub fn region_at(&self, offset: usize) -> Option<&executable::Region> {
+        let end = self.regions.partition_point(|region| region.range.start <= offset);
+        self.regions[..end].iter().rev().find(|region| region.range.contains(&offset))
+    }
+
+    /// The selected bytes, block selections without the bytes between their rows.
+    pub(crate) fn selected_bytes(&self) -> Option<Vec<u8>> {
+        let (selection, buffer) = (self.selection?, self.buffer.as_ref()?);
+        Some(
+            selection
+                .runs(0..buffer.len())
+                .into_iter()
+                .flat_map(|run| buffer.data[run].iter().copied())
+                .collect(),
+        )
+    }
+
+    /// Puts the selection on the primary selection, the X11/Wayland middle click clipboard.
+    fn copy_selection_to_primary(&self) -> Task<Message> {
+        match self.selection_text() {
+            Some(text) => cosmic::iced::clipboard::write_primary(text),
+            None => Task::none(),
+        }
+    }
+
+    /// Writes pasted text at the caret, overwriting only up to the end of the data.
+    fn paste(&mut self, text: &str) -> Task<Message> {
+        let hex = if self.cursor.in_hex == EditMode::Hex { parse_hex(text) } else { None };
+        let mut bytes = hex.unwrap_or_else(|| text.chars().filter_map(|ch| encode_char(self.encoding, ch)).flatten().collect());
+        if !self.insert_mode {
+            let len = self.buffer.as_ref().map_or(0, |buffer| buffer.len());
+            bytes.truncate(len.saturating_sub(self.cursor.position / 2));
+        }
+        if bytes.is_empty() {
+            return Task::none();
+        }
+        self.write_bytes(bytes)
+    }
+
+    /// Inserts or overwrites `bytes` at the caret as one undoable step and moves the caret behind them.
+    fn write_bytes(&mut self, bytes: Vec<u8>) -> Task<Message> {
+        let Some(buffer) = &self.buffer else {
+            return Task::none();
+        };
+        let pos = self.cursor.position / 2;
+        let new_caret_pos = (pos + bytes.len()) * 2;
+        if self.insert_mode || pos == buffer.len() {
+            let operation = undo::UndoInsertBytes::new(pos, self.cursor.position, new_caret_pos, bytes);
+            return self.commit_operation(operation);
+        }
+        let Some(old_value) = buffer.data.get(pos..pos + bytes.len()) else {
+            return Task::none();
+        };
+        let operation = undo::UndoChangeBytes::new(pos, self.cursor.position, old_value.to_vec(), new_caret_pos, bytes);
+        self.commit_operation(operation)
+    }
+
+    /// Rewrites the selected bytes as one undoable step. `change` is called with the offsets and bytes of every
+    /// run of the selection, one per row for block selections, bytes between the runs stay as they are.
+    fn change_selection(&mut self, mut change: impl FnMut(&DataBuffer, std::ops::Range<usize>, &mut [u8])) -> Task<Message> {
+        let (Some(selection), Some(buffer)) = (self.selection, &self.buffer) else {
+            return Task::none();
+        };
+        let span = selection.start()..selection.range().end.min(buffer.len());
+        let mut new_value = buffer.data[span.clone()].to_vec();
+        for run in selection.runs(span.clone()) {
+            let bytes = &mut new_value[run.start - span.start..run.end - span.start];
+            change(buffer, run, bytes);
+        }
+        if new_value == buffer.data[span.clone()] {
+            return Task::none();
+        }
+        let operation = undo::UndoChangeBytes::new(span.start, self.cursor.position, buffer.data[span].to_vec(), self.cursor.position, new_value);
+        self.commit_operation(operation)
+    }
+
+    /// Moves keyboard focus to the hex widget.
+    pub fn focus(&self) -> Task<Message> {
+        cosmic::iced_runtime::task::widget(iced_core::widget::operation::focusable::focus(self.focus_id.clone()))
+    }
+
+    /// The selection and data version the selection stats belong to.
+    pub(crate) fn stats_key(&self) -> Option<(Selection, u64)> {
+        Some((self.selection?, self.buffer.as_ref()?.version()))
+    }
+
+    /// Stats of the current selection, `None` while they are computed.
+    pub(crate) fn current_stats(&self) -> Option<(u64, u8, u8)> {
+        let key = self.stats_key()?;
+        self.selection_stats.filter(|(for_key, _)| *for_key == key).map(|(_, stats)| stats)
+    }
+
     pub(crate) fn is_dirty(&self) -> bool {
         self.undo_buffer.len() != self.last_save
     }
 
-    fn commit_operation(&mut self, operation: undo::UndoChangeByte) -> Task<Message> {
+    /// Returns the new dirty state if the undo stack crossed the saved marker since the last call.
+    pub(crate) fn take_dirty_change(&mut self) -> Option<bool> {
+        let dirty = self.is_dirty();
+        if dirty == self.reported_dirty {
+            return None;
+        }
+        self.reported_dirty = dirty;
+        Some(dirty)
+    }
+
+    fn commit_operation(&mut self, operation: impl UndoOperation + 'static) -> Task<Message> {
+        if self.read_only {
+            return Task::none();
+        }
+        let offset = self.cursor.position / 2;
+        self.edit_positions.retain(|position| *position != offset);
+        if self.edit_positions.len() == MAX_EDIT_POSITIONS {
+            self.edit_positions.pop_front();
+        }
+        self.edit_positions.push_back(offset);
+
         let _ = operation.redo(self);
+        self.clamp_to_data();
         self.redo_buffer.clear();
         self.undo_buffer.push(Box::new(operation));
         self.redraw();
-        self.scroll_to_caret()
+        match self.take_dirty_change() {
+            Some(dirty) => Task::batch([Task::done(Message::DirtyChanged(dirty)), self.scroll_to_caret()]),
+            None => self.scroll_to_caret(),
+        }
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), fields(path = ?self.path)))]
     pub(crate) fn save(&mut self) -> HexResult<()> {
-        if let Some(data) = &self.buffer {
+        let path = self.path.clone();
+        self.write_to(&path)
+    }
+
+    /// Writes the data to `path` and makes it the saved state.
+    fn write_to(&mut self, path: &Path) -> HexResult<()> {
+        if let Some(data) = &mut self.buffer {
+            std::fs::write(path, &data.data)?;
             self.last_save = self.undo_buffer.len();
-            std::fs::write(&self.path, &data.data)?;
+            data.mark_saved();
+            self.image = executable::Image::parse(&data.data);
         }
         Ok(())
     }
 
+    /// Saves the data to `path` and makes it the path of the view. A new file takes over the mode
+    /// bits and ownership of the current file so copies of protected files stay protected.
+    pub(crate) fn save_as(&mut self, path: PathBuf) -> HexResult<()> {
+        let source = std::fs::metadata(&self.path).ok();
+        let created = !path.exists();
+        // The view keeps its path if writing fails, so the next save doesn't go to the new file.
+        self.write_to(&path)?;
+        self.path = path;
+        if let (true, Some(source)) = (created, source) {
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::MetadataExt;
+                if let Err(err) = std::os::unix::fs::chown(&self.path, Some(source.uid()), Some(source.gid())) {
+                    log::warn!("failed to keep ownership of {:?}: {}", self.path, err);
+                }
+            }
+            // The data is written at this point, a copy with the default mode is still a copy.
+            if let Err(err) = std::fs::set_permissions(&self.path, source.permissions()) {
+                log::warn!("failed to keep the mode of {:?}: {}", self.path, err);
+            }
+        }
+        if let Ok(canonical) = std::fs::canonicalize(&self.path) {
+            self.path = canonical;
+        }
+        Ok(())
+    }
+
+    /// Discards all edits and reads the file again, keeping the caret if it is still inside the data.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), fields(path = ?self.path)))]
+    pub(crate) fn reload(&mut self) -> HexResult<()> {
+        let data = std::fs::read(&self.path)?;
+        self.image = executable::Image::parse(&data);
+        self.buffer = Some(DataBuffer::new(data));
+        self.undo_buffer.clear();
+        self.redo_buffer.clear();
+        self.last_save = 0;
+        self.edit_positions.clear();
+        self.matches.clear();
+        self.highlights.clear();
+        self.clamp_to_data();
+        self.redraw();
+        Ok(())
+    }
+
+    /// Moves the data and edit history out, leaving an empty, clean view behind. `None` if another
+    /// view of a duplicated view holds them.
+    pub(crate) fn take_document(&mut self) -> Option<Document> {
+        self.buffer.as_ref()?;
+        let document = Document {
+            buffer: self.buffer.take(),
+            image: self.image.take(),
+            last_save: self.last_save,
+            reported_dirty: self.reported_dirty,
+            undo_buffer: std::mem::take(&mut self.undo_buffer),
+            redo_buffer: std::mem::take(&mut self.redo_buffer),
+            edit_positions: std::mem::take(&mut self.edit_positions),
+        };
+        self.last_save = 0;
+        self.reported_dirty = false;
+        Some(document)
+    }
+
+    /// Shows `document`, keeping the caret and selection inside the data it has now.
+    pub(crate) fn set_document(&mut self, document: Document) {
+        self.buffer = document.buffer;
+        self.image = document.image;
+        self.last_save = document.last_save;
+        self.reported_dirty = document.reported_dirty;
+        self.undo_buffer = document.undo_buffer;
+        self.redo_buffer = document.redo_buffer;
+        self.edit_positions = document.edit_positions;
+        self.clamp_to_data();
+        self.redraw();
+    }
+
     pub(crate) fn undo(&mut self) -> HexResult<()> {
+        if self.read_only {
+            return Ok(());
+        }
         if let Some(undo) = self.undo_buffer.pop() {
             undo.undo(self)?;
             self.redo_buffer.push(undo);
+            self.clamp_to_data();
         }
         Ok(())
     }
 
     pub fn redo(&mut self) -> HexResult<()> {
+        if self.read_only {
+            return Ok(());
+        }
         if let Some(redo) = self.redo_buffer.pop() {
             redo.redo(self)?;
             self.undo_buffer.push(redo);
+            self.clamp_to_data();
         }
         Ok(())
     }
 
+    /// Keeps the caret inside the data and drops a selection or mark past its end, after the data
+    /// shrank.
+    fn clamp_to_data(&mut self) {
+        self.cursor.position = self.cursor.position.min(self.max_caret_position());
+        let len = self.buffer.as_ref().map_or(0, |buffer| buffer.len());
+        self.selection = self.selection.filter(|selection| selection.end() < len);
+        self.mark = self.mark.filter(|mark| *mark < len);
+    }
+
+    /// Returns the offsets of all non-overlapping occurrences of `needle`.
+    pub(crate) fn find_all(&self, needle: &[u8]) -> Vec<usize> {
+        self.buffer.as_ref().map_or_else(Vec::new, |buffer| find_occurrences(&buffer.data, needle))
+    }
+
+    /// Replaces all occurrences of `needle` as a single undo step.
+    pub(crate) fn replace_all(&mut self, needle: &[u8], replacement: &[u8]) -> Task<Message> {
+        let offsets = self.find_all(needle);
+        if offsets.is_empty() {
+            return Task::none();
+        }
+        let operation = undo::UndoReplaceAll::new(offsets, needle.to_vec(), replacement.to_vec(), self.cursor.position);
+        self.commit_operation(operation)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(needle = needle.len())))]
     pub(crate) fn find_next(&mut self, needle: &[u8]) -> bool {
-        for i in self.cursor.position / 2..self.buffer.as_ref().unwrap().len() {
-            if self.buffer.as_ref().unwrap().data[i..].starts_with(needle) {
+        // Views of a duplicated view that don't hold the data find nothing.
+        let Some(buffer) = &self.buffer else {
+            return false;
+        };
+        for i in self.cursor.position / 2..buffer.len() {
+            if buffer.data[i..].starts_with(needle) {
                 self.cursor.position = i * 2;
                 return true;
             }
@@ -349,9 +1648,13 @@ impl HexView {
         false
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(needle = needle.len())))]
     pub(crate) fn find_previous(&mut self, needle: &[u8]) -> bool {
+        let Some(buffer) = &self.buffer else {
+            return false;
+        };
         for i in (0..self.cursor.position / 2).rev() {
-            if self.buffer.as_ref().unwrap().data[i..].starts_with(needle) {
+            if buffer.data[i..].starts_with(needle) {
                 self.cursor.position = i * 2;
                 return true;
             }
@@ -359,3 +1662,97 @@ impl HexView {
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use undo::{UndoChangeByte, UndoChangeBytes};
+
+    /// A second view of the same file that doesn't hold the data, like Duplicate View creates.
+    fn duplicate(view: &HexView) -> HexView {
+        let mut duplicate = HexView::new(view.path.clone(), DataBuffer::new(Vec::new()));
+        duplicate.buffer = None;
+        duplicate
+    }
+
+    #[test]
+    fn find_occurrences_skips_overlaps() {
+        assert_eq!(find_occurrences(b"aaaa", b"aa"), vec![0, 2]);
+        assert_eq!(find_occurrences(b"xabxab", b"ab"), vec![1, 4]);
+        assert_eq!(find_occurrences(b"xab", b"abc"), Vec::<usize>::new());
+        assert_eq!(find_occurrences(b"ab", b""), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn count_occurrences_matches_find_occurrences() {
+        assert_eq!(count_occurrences(b"aaaa", b"aa"), 2);
+        assert_eq!(count_occurrences(b"aaa", b"aa"), 1);
+        assert_eq!(count_occurrences(b"xabxab", b"ab"), 2);
+        assert_eq!(count_occurrences(b"ab", b""), 0);
+    }
+
+    #[test]
+    fn closing_an_inactive_duplicate_keeps_the_document() {
+        let mut holder = HexView::new(PathBuf::from("test.bin"), DataBuffer::new(vec![1, 2, 3]));
+        let _ = holder.commit_operation(UndoChangeByte::new(0, 0, 1, 2, 0xFF));
+        let mut inactive = duplicate(&holder);
+
+        assert!(inactive.take_document().is_none());
+        assert_eq!(holder.buffer.as_ref().map(|buffer| buffer.data.clone()), Some(vec![0xFF, 2, 3]));
+        assert_eq!(holder.undo_buffer.len(), 1);
+        assert!(holder.is_dirty());
+    }
+
+    #[test]
+    fn closing_the_holder_moves_the_document() {
+        let mut holder = HexView::new(PathBuf::from("test.bin"), DataBuffer::new(vec![1, 2, 3]));
+        let _ = holder.commit_operation(UndoChangeByte::new(0, 0, 1, 2, 0xFF));
+        let mut other = duplicate(&holder);
+
+        if let Some(document) = holder.take_document() {
+            other.set_document(document);
+        }
+        assert_eq!(other.buffer.as_ref().map(|buffer| buffer.data.clone()), Some(vec![0xFF, 2, 3]));
+        assert_eq!(other.undo_buffer.len(), 1);
+        assert!(other.is_dirty());
+        assert!(other.undo().is_ok());
+        assert_eq!(other.buffer.as_ref().map(|buffer| buffer.data.clone()), Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn finding_in_a_duplicate_without_the_document_finds_nothing() {
+        let holder = HexView::new(PathBuf::from("test.bin"), DataBuffer::new(vec![1, 2, 3]));
+        let mut view = duplicate(&holder);
+        assert!(!view.find_next(&[2]));
+        assert!(!view.find_previous(&[2]));
+    }
+
+    #[test]
+    fn undoing_a_multi_byte_change_clears_the_marks() {
+        let mut view = HexView::new(PathBuf::from("test.bin"), DataBuffer::new(vec![1, 2, 3, 4]));
+        let _ = view.commit_operation(UndoChangeBytes::new(1, 2, vec![2, 3], 6, vec![0xAA, 0xBB]));
+        assert!(view.buffer.as_ref().is_some_and(|buffer| buffer.is_modified(1..3)));
+
+        assert!(view.undo().is_ok());
+        assert!(view.buffer.as_ref().is_some_and(|buffer| !buffer.is_modified(0..4)));
+        assert!(view.redo().is_ok());
+        assert_eq!(view.buffer.as_ref().map(|buffer| buffer.data.clone()), Some(vec![1, 0xAA, 0xBB, 4]));
+        assert!(view.buffer.as_ref().is_some_and(|buffer| buffer.is_modified(1..3)));
+    }
+
+    #[test]
+    fn undoing_replace_all_shifts_the_offsets() {
+        let data = b"ab-ab-ab".to_vec();
+        for replacement in [&b"xyz"[..], b"x"] {
+            let mut view = HexView::new(PathBuf::from("test.bin"), DataBuffer::new(data.clone()));
+            let _ = view.replace_all(b"ab", replacement);
+            let expected = [replacement, b"-", replacement, b"-", replacement].concat();
+            assert_eq!(view.buffer.as_ref().map(|buffer| buffer.data.clone()), Some(expected.clone()));
+
+            assert!(view.undo().is_ok());
+            assert_eq!(view.buffer.as_ref().map(|buffer| buffer.data.clone()), Some(data.clone()));
+            assert!(view.redo().is_ok());
+            assert_eq!(view.buffer.as_ref().map(|buffer| buffer.data.clone()), Some(expected));
+        }
+    }
+}
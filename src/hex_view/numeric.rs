@@ -0,0 +1,60 @@
+//! Decimal column shown next to the text pane.
+
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+use super::buffer::Endian;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum NumericColumn {
+    #[default]
+    None,
+    U8,
+    I8,
+    /// 16-bit values in the view's byte order.
+    U16,
+    I16,
+}
+
+impl NumericColumn {
+    pub const ALL: [NumericColumn; 5] = [
+        NumericColumn::None,
+        NumericColumn::U8,
+        NumericColumn::I8,
+        NumericColumn::U16,
+        NumericColumn::I16,
+    ];
+
+    /// Characters the column needs per byte, `None` if it is hidden.
+    pub fn chars_per_byte(self) -> Option<f32> {
+        match self {
+            NumericColumn::None => None,
+            NumericColumn::U8 => Some(4.0),
+            NumericColumn::I8 => Some(5.0),
+            NumericColumn::U16 => Some(3.0),
+            NumericColumn::I16 => Some(3.5),
+        }
+    }
+
+    /// One row of right aligned values. A trailing byte that doesn't fill a 16-bit value is left out.
+    pub fn format_row(self, data: &[u8], endian: Endian) -> String {
+        let mut row = String::new();
+        match self {
+            NumericColumn::None => {}
+            NumericColumn::U8 => data.iter().for_each(|byte| {
+                let _ = write!(row, "{:>3} ", byte);
+            }),
+            NumericColumn::I8 => data.iter().for_each(|byte| {
+                let _ = write!(row, "{:>4} ", *byte as i8);
+            }),
+            NumericColumn::U16 => data.chunks_exact(2).for_each(|pair| {
+                let _ = write!(row, "{:>5} ", u16::from_le_bytes(endian.normalize([pair[0], pair[1]])));
+            }),
+            NumericColumn::I16 => data.chunks_exact(2).for_each(|pair| {
+                let _ = write!(row, "{:>6} ", i16::from_le_bytes(endian.normalize([pair[0], pair[1]])));
+            }),
+        }
+        row
+    }
+}
@@ -0,0 +1,34 @@
+//! Interprets raw bytes as PCM audio samples.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PcmFormat {
+    pub sample_rate: u32,
+    /// Bits per sample: 8 (unsigned), 16, 24 or 32 (signed little endian).
+    pub bits: u16,
+    pub channels: u16,
+}
+
+impl Default for PcmFormat {
+    fn default() -> Self {
+        Self {
+            sample_rate: 44100,
+            bits: 16,
+            channels: 1,
+        }
+    }
+}
+
+impl PcmFormat {
+    /// Decodes `data` into interleaved samples normalized to `-1.0..=1.0`.
+    pub fn to_samples(&self, data: &[u8]) -> Vec<f32> {
+        let width = (self.bits / 8).max(1) as usize;
+        data.chunks_exact(width)
+            .map(|s| match self.bits {
+                8 => (s[0] as f32 - 128.0) / 128.0,
+                16 => i16::from_le_bytes([s[0], s[1]]) as f32 / i16::MAX as f32,
+                24 => (i32::from_le_bytes([0, s[0], s[1], s[2]]) >> 8) as f32 / 8_388_607.0,
+                _ => i32::from_le_bytes([s[0], s[1], s[2], s[3]]) as f32 / i32::MAX as f32,
+            })
+            .collect()
+    }
+}
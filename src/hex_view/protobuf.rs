@@ -0,0 +1,89 @@
+//! Heuristic decoder for protobuf wire format without a schema.
+
+use std::ops::Range;
+
+use super::decode::uleb128;
+
+#[derive(Clone, Debug)]
+pub enum ProtoValue {
+    Varint(u64),
+    Fixed64(u64),
+    Fixed32(u32),
+    Bytes,
+    Message(Vec<ProtoField>),
+}
+
+#[derive(Clone, Debug)]
+pub struct ProtoField {
+    pub number: u64,
+    pub wire_type: u8,
+    /// Absolute byte range of the field including its key.
+    pub range: Range<usize>,
+    /// Absolute byte range of the payload only.
+    pub payload: Range<usize>,
+    pub value: ProtoValue,
+}
+
+/// Byte ranges of the fields that aren't nested messages, for highlighting.
+pub fn leaf_ranges(fields: &[ProtoField]) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    for field in fields {
+        match &field.value {
+            ProtoValue::Message(children) => ranges.extend(leaf_ranges(children)),
+            _ => ranges.push(field.range.clone()),
+        }
+    }
+    ranges
+}
+
+/// Decodes `data`, which starts at absolute offset `base`, as a sequence of protobuf fields.
+/// Returns `None` when the bytes are not a well formed message.
+pub fn decode(data: &[u8], base: usize) -> Option<Vec<ProtoField>> {
+    let mut fields = Vec::new();
+    let mut pos = 0;
+    while pos < data.len() {
+        let start = pos;
+        let (key, len) = uleb128(&data[pos..])?;
+        pos += len;
+        let number = key >> 3;
+        let wire_type = (key & 7) as u8;
+        if number == 0 {
+            return None;
+        }
+        let (value, payload) = match wire_type {
+            0 => {
+                let (value, len) = uleb128(&data[pos..])?;
+                (ProtoValue::Varint(value), pos..pos + len)
+            }
+            1 => {
+                let bytes = data.get(pos..pos + 8)?;
+                (ProtoValue::Fixed64(u64::from_le_bytes(bytes.try_into().ok()?)), pos..pos + 8)
+            }
+            2 => {
+                let (len, len_size) = uleb128(&data[pos..])?;
+                let payload_start = pos + len_size;
+                let payload_end = payload_start.checked_add(len as usize)?;
+                let bytes = data.get(payload_start..payload_end)?;
+                let value = match decode(bytes, base + payload_start) {
+                    Some(children) if !children.is_empty() => ProtoValue::Message(children),
+                    _ => ProtoValue::Bytes,
+                };
+                (value, payload_start..payload_end)
+            }
+            5 => {
+                let bytes = data.get(pos..pos + 4)?;
+                (ProtoValue::Fixed32(u32::from_le_bytes(bytes.try_into().ok()?)), pos..pos + 4)
+            }
+            _ => return None,
+        };
+        pos = payload.end;
+        fields.push(ProtoField {
+            number,
+            wire_type,
+            range: base + start..base + pos,
+            payload: base + payload.start..base + payload.end,
+            value,
+        });
+    }
+    Some(fields)
+}
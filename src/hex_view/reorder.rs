@@ -0,0 +1,33 @@
+//! Byte reordering of the selection, e.g. for ROM dumps split over two chips.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reorder {
+    Reverse,
+    /// Merges the two halves, taking bytes alternately from the first and the second.
+    Interleave,
+    /// Moves the even bytes to the first half and the odd bytes to the second.
+    Deinterleave,
+}
+
+impl Reorder {
+    pub fn apply(self, bytes: &mut [u8]) {
+        match self {
+            Reorder::Reverse => bytes.reverse(),
+            Reorder::Interleave => {
+                let (even, odd) = bytes.split_at(bytes.len().div_ceil(2));
+                let mut merged = Vec::with_capacity(bytes.len());
+                for i in 0..even.len() {
+                    merged.push(even[i]);
+                    if let Some(byte) = odd.get(i) {
+                        merged.push(*byte);
+                    }
+                }
+                bytes.copy_from_slice(&merged);
+            }
+            Reorder::Deinterleave => {
+                let split: Vec<u8> = bytes.iter().step_by(2).chain(bytes.iter().skip(1).step_by(2)).copied().collect();
+                bytes.copy_from_slice(&split);
+            }
+        }
+    }
+}
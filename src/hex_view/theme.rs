@@ -1,4 +1,9 @@
-use cosmic::iced::{Color, Size};
+use cosmic::{
+    cosmic_theme,
+    iced::{Color, Size},
+};
+
+use super::layout::Layout;
 
 #[derive(Default)]
 pub struct Theme {
@@ -7,6 +12,14 @@ pub struct Theme {
     pub offset_number: Color,
     pub hex: Color,
     pub ascii: Color,
+    pub selection: Color,
+    pub bookmark: Color,
+    pub search_match: Color,
+    pub modified: Color,
+    /// Imported regions and other annotations.
+    pub annotation: Color,
+    /// Hex digits shown in the offset gutter.
+    pub offset_digits: usize,
 }
 
 impl Theme {
@@ -17,18 +30,85 @@ impl Theme {
             offset_number: Color::from_rgb8(155, 90, 90),
             hex: Color::from_rgb8(90, 90, 90),
             ascii: Color::from_rgb8(90, 90, 90),
+            selection: Color::from_rgb8(180, 200, 240),
+            bookmark: Color::from_rgb8(60, 130, 230),
+            search_match: Color::from_rgb8(230, 180, 40),
+            modified: Color::from_rgb8(220, 70, 70),
+            annotation: Color::from_rgba8(120, 200, 120, 0.5),
+            offset_digits: 8,
         }
     }
 
+    /// Takes the selection, match, bookmark and change colors from the desktop theme, so they follow
+    /// the accent color and suit both dark and light mode.
+    pub fn apply_palette(&mut self, theme: &cosmic_theme::Theme) {
+        let translucent = |color: Color, alpha: f32| Color { a: alpha, ..color };
+        let accent: Color = theme.accent_color().into();
+        self.selection = translucent(accent, 0.35);
+        self.bookmark = accent;
+        self.search_match = translucent(theme.warning_color().into(), 0.6);
+        self.modified = theme.destructive_color().into();
+        self.annotation = translucent(theme.success_color().into(), 0.4);
+    }
+
+    /// White on black with strong highlight colors, for high contrast mode.
+    pub fn apply_high_contrast(&mut self) {
+        self.caret = Color::WHITE;
+        self.background = Color::BLACK;
+        self.offset_number = Color::from_rgb8(255, 230, 0);
+        self.hex = Color::WHITE;
+        self.ascii = Color::WHITE;
+        self.selection = Color::from_rgb8(0, 70, 200);
+        self.bookmark = Color::from_rgb8(0, 220, 255);
+        self.search_match = Color::from_rgb8(140, 60, 0);
+        self.modified = Color::from_rgb8(255, 80, 80);
+        self.annotation = Color::from_rgb8(0, 110, 40);
+    }
+
+    /// Two digits and a space, so a whole row of cells can be drawn as one string.
     pub(crate) fn calc_cell_width(&self, font_measure: Size<f32>) -> f32 {
-        font_measure.width * 2.0 + 5.0
+        font_measure.width * 3.0
     }
 
     pub(crate) fn calc_offset_margin_width(&self, font_measure: Size<f32>) -> f32 {
-        font_measure.width * 8.0 + 10.0
+        font_measure.width * self.offset_digits as f32 + 10.0
     }
 
     pub(crate) fn hex_ascii_spacing(&self) -> f32 {
         5.0
     }
+
+    /// Row geometry for `numbers_in_row` bytes with both panes shown and no group gaps.
+    pub(crate) fn layout(&self, font_measure: Size<f32>, numbers_in_row: usize) -> Layout {
+        Layout {
+            char_width: font_measure.width,
+            gutter_width: self.calc_offset_margin_width(font_measure),
+            cell_width: self.calc_cell_width(font_measure),
+            text_cell_width: font_measure.width,
+            numbers_in_row,
+            group_size: 0,
+            group_gap: 0.0,
+            pane_spacing: self.hex_ascii_spacing(),
+            show_hex: true,
+            show_text: true,
+        }
+    }
+}
+
+/// The opaque color a translucent `color` shows on `background`.
+pub fn blend(color: Color, background: Color) -> Color {
+    let mix = |c: f32, b: f32| c * color.a + b * (1.0 - color.a);
+    Color::from_rgb(mix(color.r, background.r), mix(color.g, background.g), mix(color.b, background.b))
+}
+
+/// WCAG contrast ratio between two colors, from 1 to 21. A translucent `foreground` is blended
+/// over the background first.
+pub fn contrast_ratio(foreground: Color, background: Color) -> f32 {
+    let (a, b) = (luminance(blend(foreground, background)), luminance(background));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+fn luminance(color: Color) -> f32 {
+    let channel = |c: f32| if c <= 0.039_28 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) };
+    0.2126 * channel(color.r) + 0.7152 * channel(color.g) + 0.0722 * channel(color.b)
 }
@@ -0,0 +1,28 @@
+//! Reversible letter substitutions applied to selected bytes.
+
+/// Substitutions of ASCII letters, all other bytes stay as they are.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextTransform {
+    Rot13,
+    /// Maps A to Z, B to Y and so on.
+    Atbash,
+    /// Shifts letters forward by the given number of places.
+    Caesar(u8),
+}
+
+impl TextTransform {
+    pub fn apply(self, byte: u8) -> u8 {
+        let base = match byte {
+            b'a'..=b'z' => b'a',
+            b'A'..=b'Z' => b'A',
+            _ => return byte,
+        };
+        let letter = byte - base;
+        let letter = match self {
+            TextTransform::Rot13 => (letter + 13) % 26,
+            TextTransform::Atbash => 25 - letter,
+            TextTransform::Caesar(shift) => (letter + shift % 26) % 26,
+        };
+        base + letter
+    }
+}
@@ -58,3 +58,180 @@ impl UndoOperation for UndoChangeByte {
         Ok(())
     }
 }
+
+pub struct UndoChangeBytes {
+    pub position: usize,
+    pub old_caret_pos: usize,
+    pub old_value: Vec<u8>,
+
+    pub new_caret_pos: usize,
+    pub new_value: Vec<u8>,
+}
+
+impl UndoChangeBytes {
+    pub fn new(position: usize, old_caret_pos: usize, old_value: Vec<u8>, new_caret_pos: usize, new_value: Vec<u8>) -> Self {
+        Self {
+            position,
+            old_caret_pos,
+            old_value,
+            new_caret_pos,
+            new_value,
+        }
+    }
+}
+
+impl UndoOperation for UndoChangeBytes {
+    fn undo(&self, edit_state: &mut HexView) -> HexResult<()> {
+        let Some(buffer) = edit_state.buffer.as_mut() else {
+            return Ok(());
+        };
+        buffer.set_bytes(self.position, &self.old_value);
+        edit_state.cursor.position = self.old_caret_pos;
+        Ok(())
+    }
+
+    fn redo(&self, edit_state: &mut HexView) -> HexResult<()> {
+        let Some(buffer) = edit_state.buffer.as_mut() else {
+            return Ok(());
+        };
+        buffer.set_bytes(self.position, &self.new_value);
+        edit_state.cursor.position = self.new_caret_pos;
+        Ok(())
+    }
+}
+
+/// Inserts `value` before `position`, growing the buffer.
+pub struct UndoInsertBytes {
+    pub position: usize,
+    pub old_caret_pos: usize,
+    pub new_caret_pos: usize,
+    pub value: Vec<u8>,
+}
+
+impl UndoInsertBytes {
+    pub fn new(position: usize, old_caret_pos: usize, new_caret_pos: usize, value: Vec<u8>) -> Self {
+        Self {
+            position,
+            old_caret_pos,
+            new_caret_pos,
+            value,
+        }
+    }
+}
+
+impl UndoOperation for UndoInsertBytes {
+    fn undo(&self, edit_state: &mut HexView) -> HexResult<()> {
+        let Some(buffer) = edit_state.buffer.as_mut() else {
+            return Ok(());
+        };
+        buffer.remove(self.position..self.position + self.value.len());
+        edit_state.cursor.position = self.old_caret_pos;
+        Ok(())
+    }
+
+    fn redo(&self, edit_state: &mut HexView) -> HexResult<()> {
+        let Some(buffer) = edit_state.buffer.as_mut() else {
+            return Ok(());
+        };
+        buffer.insert(self.position, &self.value);
+        edit_state.cursor.position = self.new_caret_pos;
+        Ok(())
+    }
+}
+
+/// Removes `value` starting at `position`, shrinking the buffer.
+pub struct UndoRemoveBytes {
+    pub position: usize,
+    pub old_caret_pos: usize,
+    pub new_caret_pos: usize,
+    pub value: Vec<u8>,
+}
+
+impl UndoRemoveBytes {
+    pub fn new(position: usize, old_caret_pos: usize, new_caret_pos: usize, value: Vec<u8>) -> Self {
+        Self {
+            position,
+            old_caret_pos,
+            new_caret_pos,
+            value,
+        }
+    }
+}
+
+impl UndoOperation for UndoRemoveBytes {
+    fn undo(&self, edit_state: &mut HexView) -> HexResult<()> {
+        let Some(buffer) = edit_state.buffer.as_mut() else {
+            return Ok(());
+        };
+        buffer.insert(self.position, &self.value);
+        edit_state.cursor.position = self.old_caret_pos;
+        Ok(())
+    }
+
+    fn redo(&self, edit_state: &mut HexView) -> HexResult<()> {
+        let Some(buffer) = edit_state.buffer.as_mut() else {
+            return Ok(());
+        };
+        buffer.remove(self.position..self.position + self.value.len());
+        edit_state.cursor.position = self.new_caret_pos;
+        Ok(())
+    }
+}
+
+/// Replaces every occurrence in `offsets` (positions in the original data) as one step.
+/// The replacement may differ in length from the pattern.
+pub struct UndoReplaceAll {
+    pub offsets: Vec<usize>,
+    pub pattern: Vec<u8>,
+    pub replacement: Vec<u8>,
+    pub caret_pos: usize,
+}
+
+impl UndoReplaceAll {
+    pub fn new(offsets: Vec<usize>, pattern: Vec<u8>, replacement: Vec<u8>, caret_pos: usize) -> Self {
+        Self {
+            offsets,
+            pattern,
+            replacement,
+            caret_pos,
+        }
+    }
+
+    fn splice(data: &[u8], offsets: impl Iterator<Item = usize>, from: &[u8], to: &[u8]) -> Vec<u8> {
+        let mut result = Vec::with_capacity(data.len());
+        let mut last = 0;
+        for offset in offsets {
+            result.extend_from_slice(&data[last..offset]);
+            result.extend_from_slice(to);
+            last = offset + from.len();
+        }
+        result.extend_from_slice(&data[last..]);
+        result
+    }
+}
+
+impl UndoOperation for UndoReplaceAll {
+    fn undo(&self, edit_state: &mut HexView) -> HexResult<()> {
+        let Some(buffer) = edit_state.buffer.as_mut() else {
+            return Ok(());
+        };
+        let delta = self.replacement.len() as isize - self.pattern.len() as isize;
+        let offsets = self
+            .offsets
+            .iter()
+            .enumerate()
+            .map(|(i, offset)| (*offset as isize + i as isize * delta) as usize);
+        buffer.replace(Self::splice(&buffer.data, offsets, &self.replacement, &self.pattern));
+        edit_state.cursor.position = self.caret_pos;
+        Ok(())
+    }
+
+    fn redo(&self, edit_state: &mut HexView) -> HexResult<()> {
+        let Some(buffer) = edit_state.buffer.as_mut() else {
+            return Ok(());
+        };
+        buffer.replace(Self::splice(&buffer.data, self.offsets.iter().copied(), &self.pattern, &self.replacement));
+        edit_state.cursor.position = self.caret_pos.min(buffer.data.len().saturating_sub(1) * 2);
+        Ok(())
+    }
+}
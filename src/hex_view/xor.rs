@@ -0,0 +1,46 @@
+//! Finding known plaintext under XOR keys.
+
+/// Longest repeating key tried.
+pub const MAX_KEY_LEN: usize = 16;
+
+/// A place where `crib` appears when XORed with `key`. The key is aligned to the start of
+/// the data, byte `i` of the data is XORed with `key[i % key.len()]`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct XorHit {
+    pub key: Vec<u8>,
+    pub offset: usize,
+}
+
+/// Searches `data` for `crib` XORed with single byte and repeating keys of up to `max_key_len` bytes.
+/// The crib must be longer than the key to pin it down, so only shorter keys are tried for short cribs.
+/// Keys that are all zero (the plain text) are skipped and at most `limit` hits are returned.
+pub fn find_crib(data: &[u8], crib: &[u8], max_key_len: usize, limit: usize) -> Vec<XorHit> {
+    let mut hits = Vec::new();
+    if crib.is_empty() || crib.len() > data.len() {
+        return hits;
+    }
+    let max_key_len = max_key_len.clamp(1, MAX_KEY_LEN).min(crib.len() - 1).max(1);
+    for key_len in 1..=max_key_len {
+        for offset in 0..=data.len() - crib.len() {
+            let mut key = vec![None; key_len];
+            let consistent = crib.iter().enumerate().all(|(i, plain)| {
+                let value = data[offset + i] ^ plain;
+                let slot = &mut key[(offset + i) % key_len];
+                *slot.get_or_insert(value) == value
+            });
+            if !consistent {
+                continue;
+            }
+            let key: Vec<u8> = key.into_iter().map(|byte| byte.unwrap_or(0)).collect();
+            // A shorter key that repeats would have been found already.
+            if key.iter().all(|byte| *byte == 0) || (1..key_len).any(|len| key_len % len == 0 && key.chunks(len).all(|chunk| chunk == &key[..len])) {
+                continue;
+            }
+            hits.push(XorHit { key, offset });
+            if hits.len() >= limit {
+                return hits;
+            }
+        }
+    }
+    hits
+}
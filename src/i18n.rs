@@ -36,6 +36,52 @@ pub static LANGUAGE_LOADER: LazyLock<FluentLanguageLoader> = LazyLock::new(|| {
     loader
 });
 
+/// Formats `value` with the digit group separator of the active language, e.g. `1,234,567`.
+pub fn format_number(value: u64) -> String {
+    let digits = value.to_string();
+    let separator = crate::fl!("number-group-separator");
+    let mut result = String::with_capacity(digits.len() + digits.len() / 3 * separator.len());
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            result.push_str(&separator);
+        }
+        result.push(digit);
+    }
+    result
+}
+
+pub fn format_signed(value: i64) -> String {
+    let sign = if value < 0 { "-" } else { "" };
+    format!("{}{}", sign, format_number(value.unsigned_abs()))
+}
+
+/// Formats `value` with the decimal separator of the active language. Floats print their shortest
+/// representation that reads back as the same value, so pass an `f32` as is.
+pub fn format_float(value: impl std::fmt::Display) -> String {
+    value.to_string().replace('.', &crate::fl!("number-decimal-separator"))
+}
+
+/// A byte count in bytes, KiB, MiB, GiB or TiB with translated unit names.
+pub fn format_size(bytes: u64) -> String {
+    const UNIT: f64 = 1024.0;
+    if bytes < 1024 {
+        return crate::fl!("size-bytes", value = format_number(bytes));
+    }
+    let mut value = bytes as f64 / UNIT;
+    let mut exponent = 1;
+    while value >= UNIT && exponent < 4 {
+        value /= UNIT;
+        exponent += 1;
+    }
+    let value = format!("{:.1}", value).replace('.', &crate::fl!("number-decimal-separator"));
+    match exponent {
+        1 => crate::fl!("size-kib", value = value),
+        2 => crate::fl!("size-mib", value = value),
+        3 => crate::fl!("size-gib", value = value),
+        _ => crate::fl!("size-tib", value = value),
+    }
+}
+
 /// Request a localized string by ID from the i18n/ directory.
 #[macro_export]
 macro_rules! fl {
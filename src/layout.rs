@@ -0,0 +1,221 @@
+//! Horizontal geometry of a row: the gutter, the hex pane with its group gaps, the text pane and
+//! the columns after it. Drawing and hit-testing both go through [`Layout`], so they can't drift
+//! apart when the font size, the grouping or the shown panes change.
+
+/// The pane the caret is in.
+#[derive(Default, PartialEq, Eq, Debug)]
+pub enum EditMode {
+    #[default]
+    Hex,
+    Ascii,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Layout {
+    pub char_width: f32,
+    /// Width of the offset gutter including its padding.
+    pub gutter_width: f32,
+    pub cell_width: f32,
+    pub text_cell_width: f32,
+    pub numbers_in_row: usize,
+    /// Bytes per group, a gap of `group_gap` separates the groups in the hex pane.
+    pub group_size: usize,
+    pub group_gap: f32,
+    /// Space between the panes and before the extra columns.
+    pub pane_spacing: f32,
+    pub show_hex: bool,
+    pub show_text: bool,
+}
+
+impl Layout {
+    fn gaps_before(&self, column: usize) -> f32 {
+        if self.group_size == 0 {
+            return 0.0;
+        }
+        (column / self.group_size) as f32 * self.group_gap
+    }
+
+    pub fn hex_width(&self) -> f32 {
+        if !self.show_hex || self.numbers_in_row == 0 {
+            return 0.0;
+        }
+        self.numbers_in_row as f32 * self.cell_width + self.gaps_before(self.numbers_in_row - 1)
+    }
+
+    /// Left edge of hex cell `column`.
+    pub fn hex_x(&self, column: usize) -> f32 {
+        self.gutter_width + column as f32 * self.cell_width + self.gaps_before(column)
+    }
+
+    /// Left edge of the text pane.
+    pub fn text_start(&self) -> f32 {
+        let spacing = if self.show_hex && self.show_text { self.pane_spacing } else { 0.0 };
+        self.gutter_width + self.hex_width() + spacing
+    }
+
+    pub fn text_width(&self) -> f32 {
+        if self.show_text {
+            self.numbers_in_row as f32 * self.text_cell_width
+        } else {
+            0.0
+        }
+    }
+
+    /// Left edge of text cell `column`.
+    pub fn text_x(&self, column: usize) -> f32 {
+        self.text_start() + column as f32 * self.text_cell_width
+    }
+
+    /// Left edge of the columns after the text pane.
+    pub fn extra_columns_x(&self) -> f32 {
+        self.text_start() + self.text_width() + self.pane_spacing
+    }
+
+    /// Left edge and width of `count` hex cells from `column`, `None` if the hex pane is hidden.
+    /// The span ends after the digits of the last cell, so it covers as many bytes as the text
+    /// pane span of the same cells.
+    pub fn hex_span(&self, column: usize, count: usize) -> Option<(f32, f32)> {
+        if !self.show_hex || count == 0 {
+            return None;
+        }
+        let x = self.hex_x(column);
+        Some((x, self.hex_x(column + count - 1) + self.char_width * 2.0 - x))
+    }
+
+    /// Left edge and width of `count` text cells from `column`, `None` if the text pane is hidden.
+    pub fn text_span(&self, column: usize, count: usize) -> Option<(f32, f32)> {
+        if !self.show_text || count == 0 {
+            return None;
+        }
+        Some((self.text_x(column), count as f32 * self.text_cell_width))
+    }
+
+    /// Maps `x` in `row` to a caret position, in nibbles, and the pane that was hit. Points in a
+    /// group gap hit the low nibble of the byte before it, points right of the panes hit nothing.
+    pub fn hit_test(&self, x: f32, row: usize) -> Option<(usize, EditMode)> {
+        if self.numbers_in_row == 0 || x < self.gutter_width {
+            return None;
+        }
+        let row_start = row * self.numbers_in_row;
+        let hex_x = x - self.gutter_width;
+        if self.show_hex && hex_x <= self.hex_width() {
+            let (group_size, group_width) = match self.group_size {
+                0 => (self.numbers_in_row, self.numbers_in_row as f32 * self.cell_width + self.group_gap),
+                size => (size, size as f32 * self.cell_width + self.group_gap),
+            };
+            let group = (hex_x / group_width) as usize;
+            let in_group = hex_x - group as f32 * group_width;
+            let cell = ((in_group / self.cell_width) as usize).min(group_size - 1);
+            let column = (group * group_size + cell).min(self.numbers_in_row - 1);
+            let low_nibble = hex_x - (self.hex_x(column) - self.gutter_width) > self.char_width;
+            return Some(((row_start + column) * 2 + low_nibble as usize, EditMode::Hex));
+        }
+        let text_x = x - self.text_start();
+        if self.show_text && text_x >= 0.0 && text_x < self.text_width() {
+            let column = (text_x / self.text_cell_width) as usize;
+            return Some(((row_start + column) * 2, EditMode::Ascii));
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A 100 pixel gutter, 16 bytes per row, ten pixels per character and no group gaps.
+    fn layout() -> Layout {
+        Layout {
+            char_width: 10.0,
+            gutter_width: 100.0,
+            cell_width: 30.0,
+            text_cell_width: 10.0,
+            numbers_in_row: 16,
+            group_size: 4,
+            group_gap: 0.0,
+            pane_spacing: 5.0,
+            show_hex: true,
+            show_text: true,
+        }
+    }
+
+    #[test]
+    fn positions_without_gaps() {
+        let layout = layout();
+        assert_eq!(layout.hex_x(0), 100.0);
+        assert_eq!(layout.hex_x(5), 250.0);
+        assert_eq!(layout.text_start(), 585.0);
+        assert_eq!(layout.text_x(2), 605.0);
+        assert_eq!(layout.extra_columns_x(), 750.0);
+    }
+
+    #[test]
+    fn spans_mark_the_same_bytes_in_both_panes() {
+        let layout = layout();
+        assert_eq!(layout.hex_span(1, 2), Some((130.0, 50.0)));
+        assert_eq!(layout.text_span(1, 2), Some((595.0, 20.0)));
+        // The last bytes of a row, e.g. the first row of a selection.
+        assert_eq!(layout.hex_span(14, 2), Some((520.0, 50.0)));
+        assert_eq!(layout.text_span(14, 2), Some((725.0, 20.0)));
+        assert_eq!(layout.hex_span(3, 0), None);
+    }
+
+    #[test]
+    fn hit_test_hex_nibbles() {
+        let layout = layout();
+        assert_eq!(layout.hit_test(99.0, 0), None);
+        assert_eq!(layout.hit_test(100.0, 0), Some((0, EditMode::Hex)));
+        assert_eq!(layout.hit_test(111.0, 0), Some((1, EditMode::Hex)));
+        assert_eq!(layout.hit_test(135.0, 2), Some((66, EditMode::Hex)));
+        // The right edge belongs to the last cell.
+        assert_eq!(layout.hit_test(580.0, 0), Some((31, EditMode::Hex)));
+    }
+
+    #[test]
+    fn hit_test_text_pane() {
+        let layout = layout();
+        assert_eq!(layout.hit_test(582.0, 0), None);
+        assert_eq!(layout.hit_test(585.0, 0), Some((0, EditMode::Ascii)));
+        assert_eq!(layout.hit_test(609.0, 1), Some((36, EditMode::Ascii)));
+        assert_eq!(layout.hit_test(745.0, 0), None);
+    }
+
+    #[test]
+    fn group_gaps_shift_cells() {
+        let layout = Layout { group_gap: 10.0, ..layout() };
+        assert_eq!(layout.hex_x(3), 190.0);
+        assert_eq!(layout.hex_x(4), 230.0);
+        assert_eq!(layout.hex_width(), 510.0);
+        assert_eq!(layout.hex_span(2, 4), Some((160.0, 120.0)));
+        assert_eq!(layout.hit_test(231.0, 0), Some((8, EditMode::Hex)));
+        // Inside the gap after the first group.
+        assert_eq!(layout.hit_test(225.0, 0), Some((7, EditMode::Hex)));
+        assert_eq!(layout.hit_test(615.0, 0), Some((0, EditMode::Ascii)));
+    }
+
+    #[test]
+    fn font_size_scales_hit_test() {
+        let layout = Layout {
+            char_width: 20.0,
+            gutter_width: 170.0,
+            cell_width: 60.0,
+            text_cell_width: 20.0,
+            ..layout()
+        };
+        assert_eq!(layout.hit_test(170.0 + 60.0 * 7.0 + 25.0, 0), Some((15, EditMode::Hex)));
+    }
+
+    #[test]
+    fn hidden_panes() {
+        let text_only = Layout { show_hex: false, ..layout() };
+        assert_eq!(text_only.text_start(), 100.0);
+        assert_eq!(text_only.hex_span(0, 1), None);
+        assert_eq!(text_only.hit_test(100.0, 0), Some((0, EditMode::Ascii)));
+        assert_eq!(text_only.extra_columns_x(), 265.0);
+
+        let hex_only = Layout { show_text: false, ..layout() };
+        assert_eq!(hex_only.text_span(0, 1), None);
+        assert_eq!(hex_only.hit_test(590.0, 0), None);
+        assert_eq!(hex_only.extra_columns_x(), 585.0);
+    }
+}
@@ -0,0 +1,9 @@
+//! The parts of the hex view that don't depend on the UI: the data buffer, selections, caret
+//! movement, search patterns and the row layout. The application re-exports them from its
+//! `hex_view` module, the benchmarks use them directly.
+
+pub mod buffer;
+pub mod caret;
+pub mod layout;
+pub mod pattern;
+pub mod selection;
@@ -5,13 +5,24 @@ use std::sync::OnceLock;
 use cosmic_text::SyntaxSystem;
 
 mod app;
+mod cli;
 mod config;
 pub mod hex_view;
 mod i18n;
+#[cfg(feature = "tracing")]
+mod trace;
 pub type HexResult<T> = anyhow::Result<T>;
 pub static SYNTAX_SYSTEM: OnceLock<SyntaxSystem> = OnceLock::new();
 
 fn main() -> cosmic::iced::Result {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(code) = cli::run(&args) {
+        std::process::exit(code);
+    }
+
+    #[cfg(feature = "tracing")]
+    trace::init();
+
     // Get the system's preferred languages.
     let requested_languages = i18n_embed::DesktopLanguageRequester::requested_languages();
 
@@ -49,6 +60,6 @@ fn main() -> cosmic::iced::Result {
     // Settings for configuring the application window and iced runtime.
     let settings = cosmic::app::Settings::default().size_limits(cosmic::iced::Limits::NONE.min_width(360.0).min_height(180.0));
 
-    // Starts the application's event loop with `()` as the application's flags.
-    cosmic::app::run::<app::AppModel>(settings, ())
+    // Starts the application's event loop with the files to open as the application's flags.
+    cosmic::app::run::<app::AppModel>(settings, cli::locations(&args))
 }
@@ -0,0 +1,81 @@
+//! Byte patterns with wildcards, e.g. `DE AD ?? EF`.
+
+/// Hex pairs where `??` matches any byte.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pattern(Vec<Option<u8>>);
+
+impl Pattern {
+    /// Parses hex pairs and `??` wildcards, ignoring whitespace. Returns `None` for anything else
+    /// or a pattern of wildcards only.
+    pub fn parse(text: &str) -> Option<Self> {
+        let chars: Vec<char> = text.chars().filter(|ch| !ch.is_whitespace()).collect();
+        if chars.is_empty() || !chars.len().is_multiple_of(2) {
+            return None;
+        }
+        let bytes = chars
+            .chunks(2)
+            .map(|pair| match pair {
+                ['?', '?'] => Some(None),
+                [high, low] => Some(Some((high.to_digit(16)? << 4 | low.to_digit(16)?) as u8)),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+        bytes.iter().any(Option::is_some).then_some(Self(bytes))
+    }
+
+    /// Number of bytes matched, never zero.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn matches(&self, window: &[u8]) -> bool {
+        window.len() == self.0.len() && self.0.iter().zip(window).all(|(byte, data)| byte.is_none_or(|byte| byte == *data))
+    }
+
+    /// Offsets of all, possibly overlapping, occurrences in `data`. Candidates are found by the first
+    /// fixed byte, only those are compared in full.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(data = data.len())))]
+    pub fn find_all(&self, data: &[u8]) -> Vec<usize> {
+        let Some((anchor, anchor_byte)) = self.0.iter().enumerate().find_map(|(i, byte)| byte.map(|byte| (i, byte))) else {
+            return Vec::new();
+        };
+        let Some(last_start) = data.len().checked_sub(self.len()) else {
+            return Vec::new();
+        };
+        data[anchor..=last_start + anchor]
+            .iter()
+            .enumerate()
+            .filter(|(_, byte)| **byte == anchor_byte)
+            .map(|(start, _)| start)
+            .filter(|start| self.matches(&data[*start..*start + self.len()]))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_hex_pairs_and_wildcards() {
+        assert_eq!(Pattern::parse("DE ad ?? ef").map(|pattern| pattern.len()), Some(4));
+        assert_eq!(Pattern::parse("?? ??"), None);
+        assert_eq!(Pattern::parse("ABC"), None);
+        assert_eq!(Pattern::parse("G0"), None);
+        assert_eq!(Pattern::parse(""), None);
+    }
+
+    #[test]
+    fn find_all_with_wildcards() {
+        let pattern = Pattern::parse("?? 00 FF").unwrap();
+        assert_eq!(pattern.find_all(&[1, 0, 0xFF, 2, 0, 0xFF]), vec![0, 3]);
+        assert_eq!(pattern.find_all(&[0, 0xFF]), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn find_all_overlapping() {
+        let pattern = Pattern::parse("AA AA").unwrap();
+        assert_eq!(pattern.find_all(&[0xAA, 0xAA, 0xAA]), vec![0, 1]);
+    }
+}
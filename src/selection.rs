@@ -0,0 +1,119 @@
+use std::ops::Range;
+
+/// A selected byte range. `anchor` is where the selection started, `lead` is the
+/// end that moves with the caret. Both are byte offsets and inclusive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Selection {
+    pub anchor: usize,
+    pub lead: usize,
+    /// Bytes per row for a block selection, which covers the same columns in every row
+    /// between `anchor` and `lead`.
+    pub row_width: Option<usize>,
+}
+
+impl Selection {
+    pub fn new(anchor: usize, lead: usize) -> Self {
+        Self { anchor, lead, row_width: None }
+    }
+
+    pub fn block(anchor: usize, lead: usize, row_width: usize) -> Self {
+        Self {
+            anchor,
+            lead,
+            row_width: Some(row_width.max(1)),
+        }
+    }
+
+    pub fn start(&self) -> usize {
+        self.anchor.min(self.lead)
+    }
+
+    pub fn end(&self) -> usize {
+        self.anchor.max(self.lead)
+    }
+
+    /// Number of selected bytes, at least one.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        match self.row_width {
+            None => self.end() - self.start() + 1,
+            Some(width) => {
+                let (first, last) = self.columns(width);
+                (self.end() / width - self.start() / width + 1) * (last - first + 1)
+            }
+        }
+    }
+
+    pub fn contains(&self, offset: usize) -> bool {
+        if !(self.start() <= offset && offset <= self.end()) {
+            return false;
+        }
+        self.row_width.is_none_or(|width| {
+            let (first, last) = self.columns(width);
+            (first..=last).contains(&(offset % width))
+        })
+    }
+
+    /// The span from the first to the last selected byte.
+    pub fn range(&self) -> Range<usize> {
+        self.start()..self.end() + 1
+    }
+
+    /// The selected runs overlapping `within`, clipped to it. Block selections have one run per row.
+    pub fn runs(&self, within: Range<usize>) -> Vec<Range<usize>> {
+        let clip = |run: Range<usize>| run.start.max(within.start)..run.end.min(within.end);
+        let runs = match self.row_width {
+            None => vec![clip(self.range())],
+            Some(width) => {
+                let (first, last) = self.columns(width);
+                let first_row = (self.start() / width).max(within.start / width);
+                let last_row = (self.end() / width).min(within.end.saturating_sub(1) / width);
+                (first_row..=last_row).map(|row| clip(row * width + first..row * width + last + 1)).collect()
+            }
+        };
+        runs.into_iter().filter(|run| run.start < run.end).collect()
+    }
+
+    /// First and last selected column of a block selection.
+    fn columns(&self, width: usize) -> (usize, usize) {
+        let (a, b) = (self.anchor % width, self.lead % width);
+        (a.min(b), a.max(b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear_selection() {
+        let selection = Selection::new(9, 4);
+        assert_eq!(selection.len(), 6);
+        assert_eq!(selection.range(), 4..10);
+        assert!(selection.contains(4) && selection.contains(9));
+        assert!(!selection.contains(10));
+        assert_eq!(selection.runs(0..100), vec![4..10]);
+        assert_eq!(selection.runs(6..8), vec![6..8]);
+        assert_eq!(selection.runs(10..20), Vec::<Range<usize>>::new());
+    }
+
+    #[test]
+    fn block_selection_has_a_run_per_row() {
+        // Columns 2 to 5 of rows 1 to 3, eight bytes per row.
+        let selection = Selection::block(29, 10, 8);
+        assert_eq!(selection.len(), 12);
+        assert!(selection.contains(13) && selection.contains(18));
+        assert!(!selection.contains(14) && !selection.contains(17));
+        assert_eq!(selection.runs(0..100), vec![10..14, 18..22, 26..30]);
+        // Clipped to the bytes that are drawn or still exist.
+        assert_eq!(selection.runs(12..27), vec![12..14, 18..22, 26..27]);
+        assert_eq!(selection.runs(0..20), vec![10..14, 18..20]);
+    }
+
+    #[test]
+    fn block_selection_on_one_row() {
+        let selection = Selection::block(3, 6, 16);
+        assert_eq!(selection.len(), 4);
+        assert_eq!(selection.runs(0..16), vec![3..7]);
+    }
+}
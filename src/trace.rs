@@ -0,0 +1,19 @@
+//! Timing spans for file IO, searching and rendering, built with `--features tracing`.
+//!
+//! Every span logs its duration to stderr when it closes. `COSMIC_HEX_TRACE` takes an
+//! `EnvFilter` directive and defaults to `cosmic_hex=debug`, `cosmic_hex=trace` adds a span
+//! for every frame. `log` records are forwarded as well.
+
+use tracing_subscriber::{fmt::format::FmtSpan, EnvFilter};
+
+pub fn init() {
+    let filter = EnvFilter::try_from_env("COSMIC_HEX_TRACE").unwrap_or_else(|_| EnvFilter::new("cosmic_hex=debug"));
+    if let Err(err) = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_writer(std::io::stderr)
+        .try_init()
+    {
+        eprintln!("failed to set up tracing: {}", err);
+    }
+}